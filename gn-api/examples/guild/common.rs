@@ -11,6 +11,9 @@ use gn_test_data::*;
 
 use std::sync::Arc;
 
+// NOTE test accounts sign verification messages that never expire
+pub const EXPIRES_AT: u32 = u32::MAX;
+
 pub fn dummy_users() -> Vec<Arc<EthSigner>> {
     let mut seed = ACCOUNT_SEED;
     (0..N_TEST_ACCOUNTS)
@@ -159,23 +162,23 @@ pub async fn join_guilds(api: Api, users: &[Arc<EthSigner>]) {
 }
 
 pub async fn register_users(api: Api, users: &[Arc<EthSigner>]) {
-    let register_address_payloads = users
-        .iter()
-        .map(|acc| {
-            let msg = gn_common::utils::verification_msg(acc.account_id());
-
-            let signature = match acc.sign(msg.as_bytes()) {
-                subxt::utils::MultiSignature::Ecdsa(sig) => sig,
-                _ => unreachable!(),
-            };
-
-            let id_with_auth = IdentityWithAuth::Ecdsa(
-                Identity::Address20(acc.evm_address()),
-                EcdsaSignature(signature),
-            );
-            tx::register(id_with_auth, 0)
-        })
-        .collect::<Vec<_>>();
+    let mut register_address_payloads = Vec::with_capacity(users.len());
+    for acc in users {
+        let msg = query::verification_msg(api.clone(), acc.account_id(), EXPIRES_AT)
+            .await
+            .expect("failed to fetch verification message");
+
+        let signature = match acc.sign(msg.as_bytes()) {
+            subxt::utils::MultiSignature::Ecdsa(sig) => sig,
+            _ => unreachable!(),
+        };
+
+        let id_with_auth = IdentityWithAuth::Ecdsa(
+            Identity::Address20(acc.evm_address()),
+            EcdsaSignature(signature),
+        );
+        register_address_payloads.push(tx::register(id_with_auth, 0, EXPIRES_AT));
+    }
 
     let register_discord_payloads = users
         .iter()
@@ -187,6 +190,7 @@ pub async fn register_users(api: Api, users: &[Arc<EthSigner>]) {
                     [0u8; 64],
                 ),
                 1,
+                EXPIRES_AT,
             )
         })
         .collect::<Vec<_>>();
@@ -2,6 +2,7 @@ use crate::common::*;
 use crate::oracle::*;
 use ethers::types::{Address, U256};
 use gn_api::{
+    query,
     tx::{self, Signer},
     Api,
 };
@@ -26,13 +27,25 @@ const GNOSIS_ERC721_ADDRESS_1: &str = "22c1f6050e56d2876009903609a2cc3fef83b415"
 const GNOSIS_ERC721_ID_1: &str = "5819774";
 
 const ADDRESS: &str = "e43878ce78934fe8007748ff481f03b8ee3b97de";
-const SIGNATURE: &str = "a7d8263c96a8bb689d462b2782a45b81f02777607c27d1b322a1c46910482e274320fbf353a543a1504dc3c0ded9c2930dffc4b15541d97da7b240f40416f12a1b";
+const SIGNATURE_ENV: &str = "TOKEN_EXAMPLE_SIGNATURE";
 
 pub async fn token(api: Api, root: Arc<Signer>) {
+    // NOTE the verification message depends on the chain, the registration
+    // nonce of root and the expiry block, so the owner of the test address
+    // has to sign it for the network the example is run against
+    let msg = query::verification_msg(api.clone(), root.account_id(), EXPIRES_AT)
+        .await
+        .expect("failed to fetch verification message");
+    let Ok(signature_hex) = std::env::var(SIGNATURE_ENV) else {
+        println!("sign the message below with 0x{ADDRESS} and set {SIGNATURE_ENV}:\n{msg}");
+        return;
+    };
+
     let _operators = init_operators(api.clone(), Arc::clone(&root)).await;
 
     let mut signature = [0u8; 65];
-    hex::decode_to_slice(SIGNATURE, &mut signature).expect("this should not fail");
+    hex::decode_to_slice(signature_hex.trim_start_matches("0x"), &mut signature)
+        .expect("invalid signature");
     signature[64] -= 27; // ethereum's eip-115 normalization stuff
     let mut address = [0u8; 20];
     hex::decode_to_slice(ADDRESS, &mut address).expect("this should not fail");
@@ -41,7 +54,7 @@ pub async fn token(api: Api, root: Arc<Signer>) {
     let evm_identity = IdentityWithAuth::Ecdsa(identity, EcdsaSignature(signature));
 
     let index = 0;
-    let tx_payload = tx::register(evm_identity, index);
+    let tx_payload = tx::register(evm_identity, index, EXPIRES_AT);
     tx::send::in_block(api.clone(), &tx_payload, Arc::clone(&root))
         .await
         .expect("failed to register");
@@ -6,18 +6,35 @@ use gn_common::merkle::Proof as MerkleProof;
 use gn_common::Role;
 use paste::paste;
 
+type CompositeFilter = gn_common::filter::Composite;
 type IdentityVec = Vec<Identity>;
+type RoleFilter = Filter;
 type Guild = gn_common::Guild<AccountId>;
 type GuildFilter = gn_common::filter::Guild;
-type RuntimeFilter = runtime::runtime_types::gn_common::filter::Filter;
+type RuntimeBoundedVec<T> = runtime::runtime_types::bounded_collections::bounded_vec::BoundedVec<T>;
+type RuntimeCompositeFilter =
+    runtime::runtime_types::gn_common::filter::Composite<Vec<RuntimeGuildFilter>>;
+type RuntimeFilter =
+    runtime::runtime_types::gn_common::filter::Filter<RuntimeBoundedVec<RuntimeGuildFilter>>;
 type RuntimeFilterLogic = runtime::runtime_types::gn_common::filter::Logic;
-type RuntimeGuild = runtime::runtime_types::gn_common::Guild<AccountId>;
+type RuntimeGuild = runtime::runtime_types::gn_common::Guild<
+    AccountId,
+    RuntimeBoundedVec<u8>,
+    RuntimeBoundedVec<[u8; 32]>,
+>;
 type RuntimeGuildFilter = runtime::runtime_types::gn_common::filter::Guild;
 type RuntimeIdentity = runtime::runtime_types::gn_common::identity::Identity;
 type RuntimeIdentityVec = Vec<RuntimeIdentity>;
 type RuntimeIdentityWithAuth = runtime::runtime_types::gn_common::identity::auth::IdentityWithAuth;
 type RuntimeMerkleProof = runtime::runtime_types::gn_common::merkle::Proof;
-type RuntimeRole = runtime::runtime_types::gn_common::Role;
+type RuntimeRoleFilter = runtime::runtime_types::gn_common::filter::Filter<Vec<RuntimeGuildFilter>>;
+type RuntimeRole = runtime::runtime_types::gn_common::Role<
+    RuntimeFilter,
+    (
+        RuntimeBoundedVec<RuntimeBoundedVec<u8>>,
+        RuntimeBoundedVec<u8>,
+    ),
+>;
 
 macro_rules! cast {
     ($a:ident, $name:tt) => {
@@ -35,12 +52,14 @@ macro_rules! cast {
     };
 }
 
+cast!(CompositeFilter, composite_filter);
 cast!(Filter, filter);
 cast!(FilterLogic, filter_logic);
 cast!(Identity, id);
 cast!(Guild, guild);
 cast!(GuildFilter, guild_filter);
 cast!(Role, role);
+cast!(RoleFilter, role_filter);
 cast!(IdentityVec, id_vec);
 cast!(IdentityWithAuth, id_with_auth);
 cast!(MerkleProof, proof);
@@ -1,13 +1,15 @@
-use super::FilteredRequirements;
-use crate::{cast, runtime, AccountId, Api, Request, SessionKeys, SubxtError, H256};
-use gn_common::filter::Guild as GuildFilter;
-use gn_common::identity::Identity;
-use gn_common::{Guild, GuildName, RequestIdentifier, RoleName};
+use super::{DepositAmounts, FilteredRequirements};
+use crate::{cast, runtime, AccountId, Api, Balance, Request, SessionKeys, SubxtError, H256};
+use gn_common::eip712::Registration;
+use gn_common::filter::{Filter, Guild as GuildFilter, Logic as FilterLogic};
+use gn_common::identity::{siwe::SiweMessage, Identity};
+use gn_common::{Guild, GuildName, RequestIdentifier, Role, RoleName};
 use gn_engine::RequirementsWithLogic;
 use subxt::ext::codec::Decode;
+use subxt::rpc::rpc_params;
 use subxt::storage::address::{StorageHasher, StorageMapKey};
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 pub async fn active_operators(api: Api) -> Result<Vec<AccountId>, SubxtError> {
     let operators = runtime::storage().oracle().active_operators();
@@ -17,6 +19,7 @@ pub async fn active_operators(api: Api) -> Result<Vec<AccountId>, SubxtError> {
         .await?
         .fetch(&operators)
         .await?
+        .map(|operators| operators.0)
         .unwrap_or_default())
 }
 
@@ -41,23 +44,76 @@ pub async fn is_validator_added(api: Api, id: &AccountId) -> Result<bool, SubxtE
         .await?
         .ok_or(SubxtError::Other("empty validator set".to_string()))?;
 
-    Ok(validators.contains(id))
+    Ok(validators.0.contains(id))
 }
 
 pub async fn user_identity(api: Api, user_id: &AccountId) -> Result<Vec<Identity>, SubxtError> {
-    let mut i = 0u8;
-    let mut identities = Vec::new();
-    while let Some(identity) = api
-        .storage()
-        .at(None)
-        .await?
-        .fetch(&runtime::storage().guild().user_data(user_id, i))
-        .await?
-    {
-        identities.push(cast::id::from_runtime(identity));
-        i += 1;
-    }
-    Ok(identities)
+    let identities: Vec<(u8, Identity)> = api
+        .rpc()
+        .request("guild_identities", rpc_params![user_id])
+        .await?;
+    Ok(identities
+        .into_iter()
+        .map(|(_index, identity)| identity)
+        .collect())
+}
+
+pub async fn identity_owner(api: Api, identity: Identity) -> Result<Option<AccountId>, SubxtError> {
+    let owner = runtime::storage()
+        .guild()
+        .identity_owners(cast::id::to_runtime(identity));
+    api.storage().at(None).await?.fetch(&owner).await
+}
+
+pub async fn registration_nonce(api: Api, user_id: &AccountId) -> Result<u64, SubxtError> {
+    let nonce = runtime::storage().guild().registration_nonces(user_id);
+    api.storage().at(None).await?.fetch_or_default(&nonce).await
+}
+
+/// Message to be signed by an identity that `user_id` wants to register,
+/// valid until the `expires_at` block.
+pub async fn verification_msg(
+    api: Api,
+    user_id: &AccountId,
+    expires_at: u32,
+) -> Result<String, SubxtError> {
+    let nonce = registration_nonce(api.clone(), user_id).await?;
+    Ok(gn_common::utils::verification_msg(
+        user_id,
+        api.genesis_hash(),
+        nonce,
+        expires_at.into(),
+    ))
+}
+
+/// EIP-712 typed data to be signed by an EVM address that `user_id` wants
+/// to register, valid until the `expires_at` block.
+pub async fn registration_typed_data(
+    api: Api,
+    user_id: &AccountId,
+    expires_at: u32,
+) -> Result<Registration, SubxtError> {
+    let nonce = registration_nonce(api.clone(), user_id).await?;
+    Ok(Registration::new(
+        user_id,
+        api.genesis_hash(),
+        nonce,
+        expires_at.into(),
+    ))
+}
+
+/// EIP-4361 message to be signed by `address` when `user_id` registers it
+/// via `register_siwe`, valid until the `expires_at` block.
+pub async fn siwe_msg(
+    api: Api,
+    user_id: &AccountId,
+    siwe: SiweMessage,
+    expires_at: u32,
+) -> Result<String, SubxtError> {
+    let nonce = registration_nonce(api.clone(), user_id).await?;
+    Ok(siwe
+        .bind_registration(user_id, api.genesis_hash(), nonce, expires_at.into())
+        .to_string())
 }
 
 pub async fn members(
@@ -65,29 +121,67 @@ pub async fn members(
     filter: &GuildFilter,
     page_size: u32,
 ) -> Result<Vec<AccountId>, SubxtError> {
-    let mut keys = Vec::new();
-    let role_ids = role_ids(api.clone(), filter, page_size).await?;
-    for role_id in role_ids.into_iter() {
-        let mut query_key = runtime::storage().guild().members_root().to_root_bytes();
-        StorageMapKey::new(role_id, StorageHasher::Blake2_128).to_bytes(&mut query_key);
-        let mut storage_keys = api
-            .storage()
-            .at(None)
-            .await?
-            .fetch_keys(&query_key, page_size, None)
-            .await?;
-        keys.append(&mut storage_keys);
+    let role_names = if let Some(role_name) = filter.role {
+        vec![role_name]
+    } else {
+        roles(api.clone(), filter.name).await?
+    };
+    let mut members = BTreeSet::new();
+    for role_name in role_names {
+        let mut start: Option<AccountId> = None;
+        loop {
+            let page: Vec<AccountId> = api
+                .rpc()
+                .request(
+                    "guild_members",
+                    rpc_params![filter.name, role_name, start, page_size],
+                )
+                .await?;
+            let is_last_page = page.len() < page_size as usize || page.is_empty();
+            start = page.last().cloned();
+            members.extend(page);
+            if is_last_page {
+                break;
+            }
+        }
     }
-    // NOTE unwrap is fine because we are creating an account id from 32 bytes
-    let key_map: BTreeMap<AccountId, usize> = keys
-        .iter()
-        .enumerate()
-        .map(|(i, key)| {
-            let id: [u8; 32] = key.0[96..128].try_into().unwrap();
-            (AccountId::from(id), i)
-        })
-        .collect();
-    Ok(key_map.into_keys().collect())
+    Ok(members.into_iter().collect())
+}
+
+pub async fn is_member(
+    api: Api,
+    guild_name: GuildName,
+    role_name: RoleName,
+    account: &AccountId,
+) -> Result<bool, SubxtError> {
+    api.rpc()
+        .request(
+            "guild_isMember",
+            rpc_params![guild_name, role_name, account],
+        )
+        .await
+}
+
+pub async fn roles(api: Api, guild_name: GuildName) -> Result<Vec<RoleName>, SubxtError> {
+    api.rpc()
+        .request("guild_roles", rpc_params![guild_name])
+        .await
+}
+
+pub async fn member_count(api: Api, filter: &GuildFilter) -> Result<u32, SubxtError> {
+    let id = if let Some(role_name) = filter.role {
+        role_id(api.clone(), filter.name, role_name).await?
+    } else {
+        guild_id(api.clone(), filter.name).await?
+    };
+    let member_count = runtime::storage().guild().member_count(id);
+    Ok(api
+        .storage()
+        .at(None)
+        .await?
+        .fetch(&member_count)
+        .await?
+        .unwrap_or_default())
 }
 
 pub async fn guild_id(api: Api, name: GuildName) -> Result<H256, SubxtError> {
@@ -158,6 +252,39 @@ pub async fn role_ids(
     Ok(role_ids)
 }
 
+/// Returns at most `page_size` roles of the account starting after the role
+/// with id `start`. Roles are ordered by their ids, so the id of the last
+/// role of a page is the start of the next one.
+pub async fn roles_of(
+    api: Api,
+    account: &AccountId,
+    start: Option<H256>,
+    page_size: u32,
+) -> Result<Vec<(H256, GuildName, RoleName)>, SubxtError> {
+    let storage = api.storage().at(None).await?;
+    let account_roles = runtime::storage().guild().account_roles(account);
+    let role_ids: BTreeSet<H256> = storage
+        .fetch(&account_roles)
+        .await?
+        .map(|role_ids| role_ids.0.into_iter().collect())
+        .unwrap_or_default();
+
+    let mut roles = Vec::new();
+    for role_id in role_ids
+        .into_iter()
+        .filter(|role_id| start.map_or(true, |start| *role_id > start))
+        .take(page_size as usize)
+    {
+        let role_names = runtime::storage().guild().role_name_map(role_id);
+        let (guild_name, role_name) = storage
+            .fetch(&role_names)
+            .await?
+            .ok_or_else(|| SubxtError::Other(format!("no Role with id: {role_id:?}")))?;
+        roles.push((role_id, guild_name, role_name));
+    }
+    Ok(roles)
+}
+
 pub async fn oracle_request(api: Api, id: RequestIdentifier) -> Result<Request, SubxtError> {
     let key = runtime::storage().oracle().requests(id);
     let request = api
@@ -195,16 +322,11 @@ pub async fn guilds(
 ) -> Result<Vec<Guild<AccountId>>, SubxtError> {
     let mut guilds = Vec::new();
     if let Some(name) = filter {
-        let guild_id = guild_id(api.clone(), name).await?;
-        let guild_addr = runtime::storage().guild().guilds(guild_id);
-        let guild = api
-            .storage()
-            .at(None)
-            .await?
-            .fetch(&guild_addr)
-            .await?
-            .ok_or_else(|| SubxtError::Other(format!("no Guild with name: {name:#?}")))?;
-        guilds.push(cast::guild::from_runtime(guild));
+        let guild: Option<Guild<AccountId>> =
+            api.rpc().request("guild_guild", rpc_params![name]).await?;
+        guilds.push(
+            guild.ok_or_else(|| SubxtError::Other(format!("no Guild with name: {name:#?}")))?,
+        );
     } else {
         let root = runtime::storage().guild().guilds_root();
         let mut iter = api.storage().at(None).await?.iter(root, page_size).await?;
@@ -216,22 +338,83 @@ pub async fn guilds(
     Ok(guilds)
 }
 
+pub async fn admins(api: Api, guild_name: GuildName) -> Result<Vec<AccountId>, SubxtError> {
+    let guild_id = guild_id(api.clone(), guild_name).await?;
+    let admins = runtime::storage().guild().admins(guild_id);
+    Ok(api
+        .storage()
+        .at(None)
+        .await?
+        .fetch(&admins)
+        .await?
+        .map(|admins| admins.0)
+        .unwrap_or_default())
+}
+
+pub async fn bans(api: Api, guild_name: GuildName) -> Result<Vec<AccountId>, SubxtError> {
+    let guild_id = guild_id(api.clone(), guild_name).await?;
+    let bans = runtime::storage().guild().bans(guild_id);
+    Ok(api
+        .storage()
+        .at(None)
+        .await?
+        .fetch(&bans)
+        .await?
+        .map(|bans| bans.0)
+        .unwrap_or_default())
+}
+
+pub async fn deposit_amounts(api: Api) -> Result<DepositAmounts, SubxtError> {
+    let constants = api.constants();
+    Ok(DepositAmounts {
+        guild: constants.at(&runtime::constants().guild().guild_deposit())?,
+        role: constants.at(&runtime::constants().guild().role_deposit())?,
+        allowlist_byte: constants.at(&runtime::constants().guild().allowlist_byte_deposit())?,
+        identity: constants.at(&runtime::constants().guild().identity_deposit())?,
+    })
+}
+
+pub async fn guild_deposit(
+    api: Api,
+    guild_name: GuildName,
+) -> Result<Option<(AccountId, Balance)>, SubxtError> {
+    let guild_id = guild_id(api.clone(), guild_name).await?;
+    let deposit = runtime::storage().guild().deposits(guild_id);
+    api.storage().at(None).await?.fetch(&deposit).await
+}
+
+pub async fn role_deposit(
+    api: Api,
+    guild_name: GuildName,
+    role_name: RoleName,
+) -> Result<Option<(AccountId, Balance)>, SubxtError> {
+    let role_id = role_id(api.clone(), guild_name, role_name).await?;
+    let deposit = runtime::storage().guild().deposits(role_id);
+    api.storage().at(None).await?.fetch(&deposit).await
+}
+
+pub async fn identity_deposit(
+    api: Api,
+    user_id: &AccountId,
+    index: u8,
+) -> Result<Option<Balance>, SubxtError> {
+    let deposit = runtime::storage().guild().identity_deposits(user_id, index);
+    api.storage().at(None).await?.fetch(&deposit).await
+}
+
 pub async fn filtered_requirements(
     api: Api,
     guild_name: GuildName,
     role_name: RoleName,
 ) -> Result<FilteredRequirements, SubxtError> {
-    let role_id = role_id(api.clone(), guild_name, role_name).await?;
-    let role_key = runtime::storage().guild().roles(role_id);
-    let role = api
-        .storage()
-        .at(None)
-        .await?
-        .fetch(&role_key)
-        .await?
-        .ok_or_else(|| SubxtError::Other(format!("no role with name: {role_name:#?}")))?;
+    let role: Option<Role> = api
+        .rpc()
+        .request("guild_role", rpc_params![guild_name, role_name])
+        .await?;
+    let role =
+        role.ok_or_else(|| SubxtError::Other(format!("no role with name: {role_name:#?}")))?;
 
-    FilteredRequirements::try_from(cast::role::from_runtime(role))
+    FilteredRequirements::try_from(role)
 }
 
 pub async fn requirements(
@@ -253,10 +436,44 @@ pub async fn allowlist(
     let offchain_key = gn_common::offchain_allowlist_key(role_id.as_ref());
 
     let maybe_encoded_allowlist = api.rpc().offchain(&offchain_key).await?;
-    maybe_encoded_allowlist
-        .map(|x| Vec::<Identity>::decode(&mut &x.0[..]))
-        .transpose()
-        .map_err(SubxtError::Codec)
+    if let Some(encoded_allowlist) = maybe_encoded_allowlist {
+        return Vec::<Identity>::decode(&mut &encoded_allowlist.0[..])
+            .map(Some)
+            .map_err(SubxtError::Codec);
+    }
+
+    // large allowlists are uploaded in chunks
+    let Some((root, n_leaves)) = filtered_requirements(api.clone(), guild_name, role_name)
+        .await?
+        .filter
+        .and_then(|filter| filter.allowlist_root())
+    else {
+        return Ok(None);
+    };
+    let mut allowlist = Vec::with_capacity(n_leaves as usize);
+    let mut chunk_index = 0;
+    while allowlist.len() < n_leaves as usize {
+        let chunk_key = gn_common::offchain_allowlist_chunk_key(role_id.as_ref(), chunk_index);
+        let Some(encoded_chunk) = api.rpc().offchain(&chunk_key).await? else {
+            // not every chunk has been uploaded yet
+            return Ok(None);
+        };
+        let mut chunk =
+            Vec::<Identity>::decode(&mut &encoded_chunk.0[..]).map_err(SubxtError::Codec)?;
+        allowlist.append(&mut chunk);
+        chunk_index += 1;
+    }
+
+    // NOTE chunks are not checked onchain, so the assembled list is
+    // checked against the root here
+    let assembled_root =
+        Filter::<Vec<GuildFilter>>::allowlist(&allowlist, FilterLogic::And).allowlist_root();
+    if assembled_root != Some((root, n_leaves)) {
+        return Err(SubxtError::Other(format!(
+            "allowlist chunks don't match the root of role: {role_name:#?}"
+        )));
+    }
+    Ok(Some(allowlist))
 }
 
 pub async fn next_session_keys(api: Api, validator: &AccountId) -> Result<SessionKeys, SubxtError> {
@@ -1,11 +1,19 @@
 mod functions;
 pub use functions::*;
 
-use crate::SubxtError;
+use crate::{Balance, SubxtError};
 use gn_common::Role;
 use gn_engine::RequirementsWithLogic;
 use serde::{Deserialize, Serialize};
 
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct DepositAmounts {
+    pub guild: Balance,
+    pub role: Balance,
+    pub allowlist_byte: Balance,
+    pub identity: Balance,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct FilteredRequirements {
     pub filter: Option<gn_common::filter::Filter>,
@@ -10,7 +10,10 @@ pub use subxt::tx::Signer as SignerT;
 
 pub type Signer = subxt::tx::PairSigner<ClientConfig, Keypair>;
 
-use crate::{Api, ClientConfig, SubxtError};
+use crate::{runtime, Api, ClientConfig, SubxtError};
+use gn_common::identity::Identity;
+use gn_common::merkle::Proof as MerkleProof;
+use gn_common::{GuildName, RoleName};
 use std::sync::Arc;
 
 pub async fn api_with_signer(
@@ -28,3 +31,33 @@ pub fn signer(seed: &str, password: Option<&str>) -> Result<Arc<Signer>, SubxtEr
         Keypair::from_string(seed, password).map_err(|e| SubxtError::Other(e.to_string()))?;
     Ok(Arc::new(Signer::new(keypair)))
 }
+
+/// Uploads the allowlist of a role created with `create_role_with_allowlist_root`
+/// in chunks of `MaxAllowlistLen` identities, submitting every chunk at once.
+/// Every chunk is accompanied by its Merkle proof against the role's root.
+pub async fn upload_allowlist<S: SignerT<ClientConfig>>(
+    api: Api,
+    guild_name: GuildName,
+    role_name: RoleName,
+    allowlist: &[Identity],
+    signer: Arc<S>,
+) -> Result<(), SubxtError> {
+    let chunk_len = api
+        .constants()
+        .at(&runtime::constants().guild().max_allowlist_len())?;
+    let payloads = allowlist
+        .chunks(chunk_len as usize)
+        .enumerate()
+        .map(|(chunk_index, chunk)| {
+            let proof = MerkleProof::chunk(allowlist, chunk_index, chunk_len as usize);
+            upload_allowlist_chunk(
+                guild_name,
+                role_name,
+                chunk_index as u32,
+                chunk.to_vec(),
+                proof,
+            )
+        })
+        .collect::<Vec<_>>();
+    send::batch(api, payloads.iter(), signer).await
+}
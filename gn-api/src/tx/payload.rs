@@ -1,6 +1,9 @@
 use crate::{cast, runtime, AccountId, MultiAddress, OracleCallback, SessionKeys, SubxtError};
-use gn_common::filter::{Guild as GuildFilter, Logic as FilterLogic};
-use gn_common::identity::{Identity, IdentityWithAuth};
+use gn_common::filter::{
+    Composite as CompositeFilter, Filter, Guild as GuildFilter, Logic as FilterLogic,
+};
+use gn_common::hash::Hash;
+use gn_common::identity::{EcdsaSignature, Identity, IdentityWithAuth};
 use gn_common::merkle::Proof as MerkleProof;
 use gn_common::{GuildName, RoleName};
 use gn_engine::RequirementsWithLogic;
@@ -65,6 +68,69 @@ pub fn create_guild(guild_name: GuildName, metadata: Vec<u8>) -> impl TxPayloadT
     runtime::tx().guild().create_guild(guild_name, metadata)
 }
 
+pub fn update_guild_metadata(guild_name: GuildName, metadata: Vec<u8>) -> impl TxPayloadT {
+    runtime::tx()
+        .guild()
+        .update_guild_metadata(guild_name, metadata)
+}
+
+pub fn transfer_guild_ownership(guild_name: GuildName, new_owner: &AccountId) -> impl TxPayloadT {
+    runtime::tx()
+        .guild()
+        .transfer_guild_ownership(guild_name, new_owner.clone())
+}
+
+pub fn accept_guild_ownership(guild_name: GuildName) -> impl TxPayloadT {
+    runtime::tx().guild().accept_guild_ownership(guild_name)
+}
+
+pub fn add_admin(guild_name: GuildName, admin: &AccountId) -> impl TxPayloadT {
+    runtime::tx().guild().add_admin(guild_name, admin.clone())
+}
+
+pub fn remove_admin(guild_name: GuildName, admin: &AccountId) -> impl TxPayloadT {
+    runtime::tx()
+        .guild()
+        .remove_admin(guild_name, admin.clone())
+}
+
+pub fn kick_member(
+    guild_name: GuildName,
+    role_name: RoleName,
+    member: &AccountId,
+    reason: Vec<u8>,
+) -> impl TxPayloadT {
+    runtime::tx()
+        .guild()
+        .kick_member(guild_name, role_name, member.clone(), reason)
+}
+
+pub fn ban_account(guild_name: GuildName, account: &AccountId, reason: Vec<u8>) -> impl TxPayloadT {
+    runtime::tx()
+        .guild()
+        .ban_account(guild_name, account.clone(), reason)
+}
+
+pub fn unban_account(guild_name: GuildName, account: &AccountId) -> impl TxPayloadT {
+    runtime::tx()
+        .guild()
+        .unban_account(guild_name, account.clone())
+}
+
+pub fn delete_guild(guild_name: GuildName, max_members: u32) -> impl TxPayloadT {
+    runtime::tx().guild().delete_guild(guild_name, max_members)
+}
+
+pub fn delete_role(
+    guild_name: GuildName,
+    role_name: RoleName,
+    max_members: u32,
+) -> impl TxPayloadT {
+    runtime::tx()
+        .guild()
+        .delete_role(guild_name, role_name, max_members)
+}
+
 pub fn create_free_role(guild_name: GuildName, role_name: RoleName) -> impl TxPayloadT {
     runtime::tx()
         .guild()
@@ -91,6 +157,44 @@ pub fn create_role_with_allowlist(
     ))
 }
 
+pub fn create_role_with_allowlist_root(
+    guild_name: GuildName,
+    role_name: RoleName,
+    root: Hash,
+    n_leaves: u32,
+    filter_logic: FilterLogic,
+    requirements: Option<RequirementsWithLogic>,
+) -> Result<impl TxPayloadT, SubxtError> {
+    let serialized_requirements = requirements
+        .map(RequirementsWithLogic::into_serialized_tuple)
+        .transpose()
+        .map_err(|e| SubxtError::Other(e.to_string()))?;
+    Ok(runtime::tx().guild().create_role_with_allowlist_root(
+        guild_name,
+        role_name,
+        root,
+        n_leaves,
+        cast::filter_logic::to_runtime(filter_logic),
+        serialized_requirements,
+    ))
+}
+
+pub fn upload_allowlist_chunk(
+    guild_name: GuildName,
+    role_name: RoleName,
+    chunk_index: u32,
+    chunk: Vec<Identity>,
+    proof: MerkleProof,
+) -> impl TxPayloadT {
+    runtime::tx().guild().upload_allowlist_chunk(
+        guild_name,
+        role_name,
+        chunk_index,
+        cast::id_vec::to_runtime(chunk),
+        cast::proof::to_runtime(proof),
+    )
+}
+
 pub fn create_child_role(
     guild_name: GuildName,
     role_name: RoleName,
@@ -111,6 +215,26 @@ pub fn create_child_role(
     ))
 }
 
+pub fn create_role_with_composite_filter(
+    guild_name: GuildName,
+    role_name: RoleName,
+    filter: CompositeFilter,
+    filter_logic: FilterLogic,
+    requirements: Option<RequirementsWithLogic>,
+) -> Result<impl TxPayloadT, SubxtError> {
+    let serialized_requirements = requirements
+        .map(RequirementsWithLogic::into_serialized_tuple)
+        .transpose()
+        .map_err(|e| SubxtError::Other(e.to_string()))?;
+    Ok(runtime::tx().guild().create_role_with_composite_filter(
+        guild_name,
+        role_name,
+        cast::composite_filter::to_runtime(filter),
+        cast::filter_logic::to_runtime(filter_logic),
+        serialized_requirements,
+    ))
+}
+
 pub fn create_unfiltered_role(
     guild_name: GuildName,
     role_name: RoleName,
@@ -124,10 +248,100 @@ pub fn create_unfiltered_role(
         .create_unfiltered_role(guild_name, role_name, serialized_requirements))
 }
 
-pub fn register(identity_with_auth: IdentityWithAuth, index: u8) -> impl TxPayloadT {
+pub fn update_role_requirements(
+    guild_name: GuildName,
+    role_name: RoleName,
+    requirements: Option<RequirementsWithLogic>,
+) -> Result<impl TxPayloadT, SubxtError> {
+    let serialized_requirements = requirements
+        .map(RequirementsWithLogic::into_serialized_tuple)
+        .transpose()
+        .map_err(|e| SubxtError::Other(e.to_string()))?;
+    Ok(runtime::tx().guild().update_role_requirements(
+        guild_name,
+        role_name,
+        serialized_requirements,
+    ))
+}
+
+pub fn update_role_filter(
+    guild_name: GuildName,
+    role_name: RoleName,
+    filter: Option<Filter>,
+) -> impl TxPayloadT {
+    runtime::tx().guild().update_role_filter(
+        guild_name,
+        role_name,
+        filter.map(cast::role_filter::to_runtime),
+    )
+}
+
+pub fn update_allowlist(
+    guild_name: GuildName,
+    role_name: RoleName,
+    allowlist: Vec<Identity>,
+    max_members: Option<u32>,
+) -> impl TxPayloadT {
+    runtime::tx().guild().update_allowlist(
+        guild_name,
+        role_name,
+        cast::id_vec::to_runtime(allowlist),
+        max_members,
+    )
+}
+
+pub fn extend_allowlist(
+    guild_name: GuildName,
+    role_name: RoleName,
+    allowlist: Vec<Identity>,
+    additions: Vec<Identity>,
+) -> impl TxPayloadT {
+    runtime::tx().guild().extend_allowlist(
+        guild_name,
+        role_name,
+        cast::id_vec::to_runtime(allowlist),
+        cast::id_vec::to_runtime(additions),
+    )
+}
+
+pub fn set_role_validity(
+    guild_name: GuildName,
+    role_name: RoleName,
+    validity: Option<u32>,
+) -> impl TxPayloadT {
     runtime::tx()
         .guild()
-        .register(cast::id_with_auth::to_runtime(identity_with_auth), index)
+        .set_role_validity(guild_name, role_name, validity)
+}
+
+pub fn register(
+    identity_with_auth: IdentityWithAuth,
+    index: u8,
+    expires_at: u32,
+) -> impl TxPayloadT {
+    runtime::tx().guild().register(
+        cast::id_with_auth::to_runtime(identity_with_auth),
+        index,
+        expires_at,
+    )
+}
+
+pub fn register_siwe(
+    message: String,
+    signature: EcdsaSignature,
+    index: u8,
+    expires_at: u32,
+) -> impl TxPayloadT {
+    runtime::tx().guild().register_siwe(
+        runtime::runtime_types::bounded_collections::bounded_vec::BoundedVec(message.into_bytes()),
+        runtime::runtime_types::gn_common::identity::auth::EcdsaSignature(signature.0),
+        index,
+        expires_at,
+    )
+}
+
+pub fn unregister(index: u8) -> impl TxPayloadT {
+    runtime::tx().guild().unregister(index)
 }
 
 pub fn join(
@@ -140,6 +354,18 @@ pub fn join(
         .join(guild_name, role_name, proof.map(cast::proof::to_runtime))
 }
 
+pub fn join_many(roles: Vec<(GuildName, RoleName, Option<MerkleProof>)>) -> impl TxPayloadT {
+    let roles = roles
+        .into_iter()
+        .map(|(guild_name, role_name, proof)| {
+            (guild_name, role_name, proof.map(cast::proof::to_runtime))
+        })
+        .collect();
+    runtime::tx()
+        .guild()
+        .join_many(runtime::runtime_types::bounded_collections::bounded_vec::BoundedVec(roles))
+}
+
 pub fn leave(guild_name: GuildName, role_name: RoleName) -> impl TxPayloadT {
     runtime::tx().guild().leave(guild_name, role_name)
 }
@@ -1,13 +1,14 @@
-use super::{Identity as CliIdentity, QUERY_ERROR, TX_ERROR};
+use super::{Identity as CliIdentity, SiweOpts, OTHER_EXPIRES_AT, QUERY_ERROR, TX_ERROR};
 use gn_api::{
     query,
     tx::{self, Signer},
-    Api,
+    AccountId, Api,
 };
-use gn_common::identity::{Identity, IdentityWithAuth};
+use gn_common::identity::{siwe, ContractSignature, Identity, IdentityWithAuth};
 use gn_common::merkle::Proof as MerkleProof;
 use gn_common::pad::pad_to_n_bytes;
 
+use std::str::FromStr;
 use std::sync::Arc;
 
 pub struct ProofIndices {
@@ -20,21 +21,65 @@ pub async fn register_identity(api: Api, signer: Arc<Signer>, identity: CliIdent
         CliIdentity::Discord { id, index } => {
             let padded_id = pad_to_n_bytes::<64, _>(&format!("discord:{}", id));
             let identity_with_auth = IdentityWithAuth::Other(Identity::Other(padded_id), [0u8; 64]);
-            tx::register(identity_with_auth, index)
+            tx::register(identity_with_auth, index, OTHER_EXPIRES_AT)
         }
         CliIdentity::Telegram { id, index } => {
             let padded_id = pad_to_n_bytes::<64, _>(&format!("telegram:{}", id));
             let identity_with_auth = IdentityWithAuth::Other(Identity::Other(padded_id), [0u8; 64]);
-            tx::register(identity_with_auth, index)
+            tx::register(identity_with_auth, index, OTHER_EXPIRES_AT)
         }
         CliIdentity::Evm {
             address,
             signature,
+            expires_at,
             index,
+            eip712,
+            siwe,
         } => {
             let identity_with_auth =
                 IdentityWithAuth::from_evm(&address, &signature).expect("invalid parameters");
-            tx::register(identity_with_auth, index)
+            let IdentityWithAuth::Ecdsa(Identity::Address20(address), signature) = identity_with_auth else {
+                unreachable!("evm identities are ecdsa authenticated")
+            };
+            if let Some(siwe) = siwe.message(address) {
+                let message = query::siwe_msg(api.clone(), signer.account_id(), siwe, expires_at)
+                    .await
+                    .expect(QUERY_ERROR);
+                let payload = tx::register_siwe(message, signature, index, expires_at);
+                tx::send::ready(api, &payload, signer)
+                    .await
+                    .expect(TX_ERROR);
+                return;
+            }
+            let identity_with_auth = if eip712 {
+                IdentityWithAuth::Eip712(Identity::Address20(address), signature)
+            } else {
+                identity_with_auth
+            };
+            tx::register(identity_with_auth, index, expires_at)
+        }
+        CliIdentity::Contract {
+            address,
+            chain_id,
+            expires_at,
+            index,
+            signature,
+        } => {
+            let address = siwe::parse_address(&address).expect("invalid address");
+            let mut contract_signature = ContractSignature {
+                chain_id,
+                signature: [0u8; 65],
+            };
+            if let Some(signature) = signature {
+                hex::decode_to_slice(
+                    signature.trim_start_matches("0x"),
+                    &mut contract_signature.signature,
+                )
+                .expect("invalid signature");
+            }
+            let identity_with_auth =
+                IdentityWithAuth::Eip1271(Identity::Address20(address), contract_signature);
+            tx::register(identity_with_auth, index, expires_at)
         }
     };
 
@@ -43,6 +88,51 @@ pub async fn register_identity(api: Api, signer: Arc<Signer>, identity: CliIdent
         .expect(TX_ERROR);
 }
 
+pub async fn siwe_msg(
+    api: Api,
+    signer: Arc<Signer>,
+    address: &str,
+    expires_at: u32,
+    siwe: SiweOpts,
+) {
+    let address = siwe::parse_address(address).expect("invalid address");
+    let siwe = siwe.message(address).expect("missing SIWE options");
+    let message = query::siwe_msg(api, signer.account_id(), siwe, expires_at)
+        .await
+        .expect(QUERY_ERROR);
+    println!("{message}");
+}
+
+pub async fn typed_data(
+    api: Api,
+    signer: Arc<Signer>,
+    expires_at: u32,
+    contract: Option<String>,
+    chain_id: Option<u64>,
+) {
+    let registration = query::registration_typed_data(api, signer.account_id(), expires_at)
+        .await
+        .expect(QUERY_ERROR);
+    if let Some(address) = contract {
+        let address = siwe::parse_address(&address).expect("invalid address");
+        let chain_id = chain_id.expect("missing chain id");
+        println!(
+            "{}",
+            registration.contract_typed_data_json(chain_id, address)
+        );
+    } else {
+        println!("{}", registration.typed_data_json());
+    }
+}
+
+pub async fn unregister_identity(api: Api, signer: Arc<Signer>, index: u8) {
+    let payload = tx::unregister(index);
+
+    tx::send::ready(api, &payload, signer)
+        .await
+        .expect(TX_ERROR);
+}
+
 pub async fn join(
     api: Api,
     signer: Arc<Signer>,
@@ -68,3 +158,22 @@ pub async fn join(
         .await
         .expect(TX_ERROR);
 }
+
+pub async fn transfer_ownership(api: Api, signer: Arc<Signer>, guild: String, account: &str) {
+    let guild = pad_to_n_bytes::<32, _>(&guild);
+    let new_owner = AccountId::from_str(account).expect("invalid account id string");
+    let payload = tx::transfer_guild_ownership(guild, &new_owner);
+
+    tx::send::ready(api, &payload, signer)
+        .await
+        .expect(TX_ERROR);
+}
+
+pub async fn accept_ownership(api: Api, signer: Arc<Signer>, guild: String) {
+    let guild = pad_to_n_bytes::<32, _>(&guild);
+    let payload = tx::accept_guild_ownership(guild);
+
+    tx::send::ready(api, &payload, signer)
+        .await
+        .expect(TX_ERROR);
+}
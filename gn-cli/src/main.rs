@@ -10,6 +10,7 @@ mod sudo;
 mod transfer;
 
 use gn_api::tx;
+use gn_common::identity::siwe::SiweMessage;
 use sp_core::crypto::{ExposeSecret, SecretString, Zeroize};
 use structopt::StructOpt;
 
@@ -17,6 +18,9 @@ use std::path::PathBuf;
 
 const TX_ERROR: &str = "failed to send tx";
 const QUERY_ERROR: &str = "failed to execute query";
+// NOTE off-chain identities don't sign the verification message, so there's
+// nothing to expire
+const OTHER_EXPIRES_AT: u32 = u32::MAX;
 
 #[derive(StructOpt)]
 pub enum Command {
@@ -27,6 +31,10 @@ pub enum Command {
         /// Activate operator before starting to listen to events
         #[structopt(long)]
         activate: bool,
+        /// JSON-RPC endpoint of an EVM chain given as '<chain id>=<url>',
+        /// used for checking contract wallet signatures
+        #[structopt(long = "evm-rpc", parse(try_from_str = oracle::parse_rpc_url))]
+        evm_rpc: Vec<(u64, String)>,
     },
     /// Chain interactions that require sudo access
     Sudo(SudoSubCmd),
@@ -105,6 +113,12 @@ pub enum KeySubCmd {
 pub enum GuildSubCmd {
     /// Register an identity on Guild Network
     Register(Identity),
+    /// Remove a registered identity from Guild Network
+    Unregister {
+        /// Index among the user's registered identities
+        #[structopt(long, short, default_value = "0")]
+        index: u8,
+    },
     /// Join a specific role in a guild
     Join {
         /// Guild name
@@ -120,6 +134,40 @@ pub enum GuildSubCmd {
         #[structopt(long, short, requires("id"))]
         leaf: Option<usize>,
     },
+    /// Print the SIWE message an EVM address should sign for registration
+    SiweMsg {
+        address: String,
+        /// Expiry block of the signed verification message
+        expires_at: u32,
+        #[structopt(flatten)]
+        siwe: SiweOpts,
+    },
+    /// Print the EIP-712 typed data an EVM address should sign for registration
+    TypedData {
+        /// Expiry block of the signed typed data
+        expires_at: u32,
+        /// Address of the contract wallet that signs the typed data
+        #[structopt(long)]
+        contract: Option<String>,
+        /// Chain id of the contract wallet, required if the contract is set
+        #[structopt(long)]
+        chain_id: Option<u64>,
+    },
+    /// Offer the ownership of a guild to another account
+    Transfer {
+        /// Guild name
+        #[structopt(long, short)]
+        guild: String,
+        /// The account the ownership is offered to
+        #[structopt(long, short)]
+        account: String,
+    },
+    /// Accept a pending guild ownership offer
+    Accept {
+        /// Guild name
+        #[structopt(long, short)]
+        guild: String,
+    },
 }
 
 #[derive(StructOpt)]
@@ -140,9 +188,58 @@ pub enum Identity {
     Evm {
         address: String,
         signature: String,
+        /// Expiry block of the signed verification message
+        expires_at: u32,
         #[structopt(default_value = "0")]
         index: u8,
+        /// The signature is over the EIP-712 registration typed data
+        #[structopt(long, conflicts_with("siwe-domain"))]
+        eip712: bool,
+        #[structopt(flatten)]
+        siwe: SiweOpts,
     },
+    /// EIP-1271 contract wallet that signed the EIP-712 registration typed data
+    Contract {
+        address: String,
+        /// Chain id of the contract wallet
+        chain_id: u64,
+        /// Expiry block of the signed typed data
+        expires_at: u32,
+        #[structopt(default_value = "0")]
+        index: u8,
+        /// Signature of the wallet, omit it if the typed data was approved onchain
+        #[structopt(long)]
+        signature: Option<String>,
+    },
+}
+
+/// Options of an EIP-4361 (Sign-In with Ethereum) verification message
+#[derive(StructOpt)]
+pub struct SiweOpts {
+    /// Domain requesting the signature, enables SIWE registration
+    #[structopt(long, requires_all(&["siwe-uri", "siwe-issued-at"]))]
+    siwe_domain: Option<String>,
+    /// URI of the resource that is the subject of the signing
+    #[structopt(long)]
+    siwe_uri: Option<String>,
+    /// EIP-155 chain id of the signing wallet
+    #[structopt(long, default_value = "1")]
+    siwe_chain_id: u64,
+    /// RFC 3339 timestamp of when the message was generated
+    #[structopt(long)]
+    siwe_issued_at: Option<String>,
+}
+
+impl SiweOpts {
+    pub fn message(self, address: [u8; 20]) -> Option<SiweMessage> {
+        Some(SiweMessage::new(
+            self.siwe_domain?,
+            address,
+            self.siwe_uri?,
+            self.siwe_chain_id,
+            self.siwe_issued_at?,
+        ))
+    }
 }
 
 #[derive(StructOpt)]
@@ -231,6 +328,9 @@ async fn main() {
         Command::Guild(GuildSubCmd::Register(identity)) => {
             guild::register_identity(api, signer, identity).await
         }
+        Command::Guild(GuildSubCmd::Unregister { index }) => {
+            guild::unregister_identity(api, signer, index).await
+        }
         Command::Guild(GuildSubCmd::Join {
             guild,
             role,
@@ -242,6 +342,22 @@ async fn main() {
                 .map(|(i, l)| guild::ProofIndices { id: i, leaf: l });
             guild::join(api, signer, guild, role, indices).await
         }
+        Command::Guild(GuildSubCmd::SiweMsg {
+            address,
+            expires_at,
+            siwe,
+        }) => guild::siwe_msg(api, signer, &address, expires_at, siwe).await,
+        Command::Guild(GuildSubCmd::TypedData {
+            expires_at,
+            contract,
+            chain_id,
+        }) => guild::typed_data(api, signer, expires_at, contract, chain_id).await,
+        Command::Guild(GuildSubCmd::Transfer { guild, account }) => {
+            guild::transfer_ownership(api, signer, guild, &account).await
+        }
+        Command::Guild(GuildSubCmd::Accept { guild }) => {
+            guild::accept_ownership(api, signer, guild).await
+        }
         Command::Key(KeySubCmd::Generate { curve }) => {
             key::generate(&curve, opt.password.expose_secret())
         }
@@ -252,7 +368,9 @@ async fn main() {
             let keys = key::rotate(api.clone()).await;
             key::set(api, signer, keys).await
         }
-        Command::Oracle { activate } => oracle::oracle(api, signer, activate).await,
+        Command::Oracle { activate, evm_rpc } => {
+            oracle::oracle(api, signer, activate, evm_rpc.into_iter().collect()).await
+        }
         Command::Sudo(SudoSubCmd::Oracle { method }) => match method {
             OracleMethod::Register { account } => {
                 sudo::sudo(
@@ -2,17 +2,35 @@ use futures::{future::try_join_all, StreamExt};
 use gn_api::{
     query,
     tx::{self, Signer},
-    Api, GuildCall, OracleCallback, OracleRequest, SubxtError,
+    AccountId, Api, GuildCall, OracleCallback, OracleRequest, SubxtError,
 };
-use gn_common::identity::Identity;
+use gn_common::eip712::contract_registration_digest;
+use gn_common::identity::{Identity, IdentityWithAuth};
 use gn_common::utils::{matches_variant, verification_msg};
-use gn_common::{RequestData, RequestIdentifier};
+use gn_common::{GuildName, RequestData, RequestIdentifier, RoleName};
+use gn_engine::eip1271;
 
 use std::collections::HashMap;
 use std::str::FromStr;
 use std::sync::Arc;
 
-pub async fn oracle(api: Api, operator: Arc<Signer>, activate: bool) {
+/// JSON-RPC endpoints of the EVM chains, keyed by their chain ids.
+pub type RpcUrls = HashMap<u64, String>;
+
+/// Parses an RPC endpoint given as `<chain id>=<url>`.
+pub fn parse_rpc_url(input: &str) -> Result<(u64, String), String> {
+    let Some((chain_id, url)) = input.split_once('=') else {
+        return Err(format!("expected '<chain id>=<url>', got '{input}'"))
+    };
+    let chain_id = chain_id
+        .trim()
+        .parse()
+        .map_err(|error| format!("invalid chain id '{chain_id}': {error}"))?;
+    Ok((chain_id, url.trim().to_string()))
+}
+
+pub async fn oracle(api: Api, operator: Arc<Signer>, activate: bool, rpc_urls: RpcUrls) {
+    let rpc_urls = Arc::new(rpc_urls);
     if !query::is_operator_registered(api.clone(), operator.account_id())
         .await
         .expect("failed to fetch operator info")
@@ -57,7 +75,12 @@ pub async fn oracle(api: Api, operator: Arc<Signer>, activate: bool) {
                             event_details.as_event::<OracleRequest>().ok().flatten()
                         })
                         .collect::<Vec<OracleRequest>>();
-                    submit_answers(api.clone(), Arc::clone(&operator), requests)
+                    submit_answers(
+                        api.clone(),
+                        Arc::clone(&operator),
+                        Arc::clone(&rpc_urls),
+                        requests,
+                    )
                 }
                 Err(err) => log::error!("invalid block events: {err}"),
             },
@@ -67,7 +90,12 @@ pub async fn oracle(api: Api, operator: Arc<Signer>, activate: bool) {
     log::error!("block subscription aborted");
 }
 
-fn submit_answers(api: Api, signer: Arc<Signer>, requests: Vec<OracleRequest>) {
+fn submit_answers(
+    api: Api,
+    signer: Arc<Signer>,
+    rpc_urls: Arc<RpcUrls>,
+    requests: Vec<OracleRequest>,
+) {
     tokio::spawn(async move {
         let answer_futures = requests
             .into_iter()
@@ -95,7 +123,7 @@ fn submit_answers(api: Api, signer: Arc<Signer>, requests: Vec<OracleRequest>) {
                     request.fee
                 );
 
-                compile_answer(api.clone(), request.request_id)
+                compile_answer(api.clone(), &rpc_urls, request.request_id)
             })
             .collect::<Vec<_>>();
 
@@ -112,19 +140,65 @@ fn submit_answers(api: Api, signer: Arc<Signer>, requests: Vec<OracleRequest>) {
 
 async fn compile_answer(
     api: Api,
+    rpc_urls: &RpcUrls,
     request_id: RequestIdentifier,
 ) -> Result<OracleCallback, SubxtError> {
     let oracle_request = query::oracle_request(api.clone(), request_id).await?;
 
-    let oracle_answer = match oracle_request.data {
+    let result = match oracle_request.data {
         RequestData::Register {
             identity_with_auth,
             index: _,
+            nonce,
+            expires_at,
         } => {
             log::info!("[registration request] acc: {}", oracle_request.requester);
-            // deserialize user identities
-            let expected_msg = verification_msg(&oracle_request.requester);
-            identity_with_auth.verify(expected_msg)
+            let access = if let IdentityWithAuth::Eip1271(
+                Identity::Address20(contract),
+                contract_signature,
+            ) = identity_with_auth
+            {
+                // rebuild the typed data the contract wallet signed, the
+                // chain id is part of its domain, so the signature is
+                // rejected if it was made for another chain
+                let digest = contract_registration_digest(
+                    &oracle_request.requester,
+                    api.genesis_hash(),
+                    nonce,
+                    expires_at,
+                    contract_signature.chain_id,
+                    contract,
+                );
+                if let Some(rpc_url) = rpc_urls.get(&contract_signature.chain_id) {
+                    eip1271::is_valid_signature(
+                        rpc_url,
+                        &contract,
+                        digest,
+                        contract_signature.signature_bytes(),
+                    )
+                    .await
+                    .unwrap_or_else(|error| {
+                        log::warn!("contract signature check failed: {}", error);
+                        false
+                    })
+                } else {
+                    log::warn!(
+                        "no rpc endpoint for chain id: {}",
+                        contract_signature.chain_id
+                    );
+                    false
+                }
+            } else {
+                // rebuild the message the user signed
+                let expected_msg = verification_msg(
+                    &oracle_request.requester,
+                    api.genesis_hash(),
+                    nonce,
+                    expires_at,
+                );
+                identity_with_auth.verify(expected_msg)
+            };
+            vec![u8::from(access)]
         }
         RequestData::ReqCheck {
             account,
@@ -137,48 +211,73 @@ async fn compile_answer(
                 guild_name,
                 role_name,
             );
-            // fetch requirements
-            let requirements_with_logic = query::requirements(api.clone(), guild_name, role_name)
-                .await?
-                .ok_or(SubxtError::Other("no requirements found".to_string()))?;
-            // build requireemnt tree from logic
-            let requirement_tree = requiem::LogicTree::from_str(&requirements_with_logic.logic)
-                .map_err(|e| SubxtError::Other(e.to_string()))?;
-            let identities = query::user_identity(api.clone(), &account).await?;
-            let maybe_address = identities
-                .iter()
-                .find(|&x| matches_variant(x, &Identity::Address20([0u8; 20])));
-
-            if let Some(address) = maybe_address {
-                let requirement_futures = requirements_with_logic
-                    .requirements
-                    .iter()
-                    .map(|req| req.check(address))
-                    .collect::<Vec<_>>();
-                match futures::future::try_join_all(requirement_futures).await {
-                    Ok(boolean_vec) => {
-                        let requirement_check_map: HashMap<u32, bool> = boolean_vec
-                            .into_iter()
-                            .enumerate()
-                            .map(|(i, b)| (i as u32, b))
-                            .collect();
-                        requirement_tree
-                            .evaluate(&requirement_check_map)
-                            .unwrap_or(false)
-                    }
-                    Err(error) => {
-                        log::warn!("requirement check failed: {}", error);
-                        false
-                    }
-                }
-            } else {
-                log::warn!("requirement check failed: no registered evm identity");
-                false
+            let access = check_requirements(api.clone(), &account, guild_name, role_name).await?;
+            vec![u8::from(access)]
+        }
+        RequestData::ReqCheckMany { account, roles } => {
+            log::info!(
+                "[batched requirement check request] acc: {}, roles: {:?}",
+                account,
+                roles,
+            );
+            let mut result = Vec::with_capacity(roles.len());
+            for (guild_name, role_name) in roles {
+                let access =
+                    check_requirements(api.clone(), &account, guild_name, role_name).await?;
+                result.push(u8::from(access));
             }
+            result
         }
     };
 
-    let result = vec![u8::from(oracle_answer)];
     log::info!("oracle answer ({}): {:?}", request_id, result);
     Ok(tx::oracle_callback(request_id, result))
 }
+
+async fn check_requirements(
+    api: Api,
+    account: &AccountId,
+    guild_name: GuildName,
+    role_name: RoleName,
+) -> Result<bool, SubxtError> {
+    // fetch requirements, the role might have been deleted since the
+    // request, which must not prevent answering the rest of a batch
+    let Some(requirements_with_logic) =
+        query::requirements(api.clone(), guild_name, role_name).await? else {
+        log::warn!("requirement check failed: no requirements found");
+        return Ok(false);
+    };
+    // build requireemnt tree from logic
+    let requirement_tree = requiem::LogicTree::from_str(&requirements_with_logic.logic)
+        .map_err(|e| SubxtError::Other(e.to_string()))?;
+    let identities = query::user_identity(api, account).await?;
+    let maybe_address = identities
+        .iter()
+        .find(|&x| matches_variant(x, &Identity::Address20([0u8; 20])));
+
+    let Some(address) = maybe_address else {
+        log::warn!("requirement check failed: no registered evm identity");
+        return Ok(false);
+    };
+    let requirement_futures = requirements_with_logic
+        .requirements
+        .iter()
+        .map(|req| req.check(address))
+        .collect::<Vec<_>>();
+    match futures::future::try_join_all(requirement_futures).await {
+        Ok(boolean_vec) => {
+            let requirement_check_map: HashMap<u32, bool> = boolean_vec
+                .into_iter()
+                .enumerate()
+                .map(|(i, b)| (i as u32, b))
+                .collect();
+            Ok(requirement_tree
+                .evaluate(&requirement_check_map)
+                .unwrap_or(false))
+        }
+        Err(error) => {
+            log::warn!("requirement check failed: {}", error);
+            Ok(false)
+        }
+    }
+}
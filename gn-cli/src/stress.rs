@@ -1,3 +1,4 @@
+use super::OTHER_EXPIRES_AT;
 use ethers::core::k256::ecdsa::SigningKey;
 use ethers::signers::{LocalWallet, Signer};
 use futures::future::try_join_all;
@@ -39,7 +40,7 @@ pub async fn register_other_identity(api: Api, num: usize, seed: &str, tps: usiz
                 let identity_with_auth = IdentityWithAuth::Other(identity, [0u8; 64]);
                 id += 1;
                 increment_array(&mut seed_bytes, &mut index);
-                let payload = tx::register(identity_with_auth, id_index);
+                let payload = tx::register(identity_with_auth, id_index, OTHER_EXPIRES_AT);
                 tx::send::owned(api.clone(), payload, signer, TxStatus::Ready)
             })
             .collect::<Vec<_>>();
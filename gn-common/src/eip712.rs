@@ -0,0 +1,466 @@
+//! Hashing of [EIP-712](https://eips.ethereum.org/EIPS/eip-712) typed
+//! structured data.
+use crate::hash::{keccak256, Hash};
+use crate::Encode;
+use scale_info::prelude::format;
+use scale_info::prelude::string::String;
+use scale_info::prelude::vec::Vec;
+
+pub const EIP712_PREFIX: &[u8] = b"\x19\x01";
+pub const REGISTRATION_DOMAIN_NAME: &str = "Guild Network";
+pub const REGISTRATION_DOMAIN_VERSION: &str = "1";
+
+/// A struct that can be hashed according to EIP-712.
+pub trait Eip712Struct {
+    /// The `encodeType` of the struct, i.e. its own type string followed by
+    /// the alphabetically sorted type strings of the referenced structs.
+    const TYPE: &'static str;
+
+    /// The `encodeData` of the struct, i.e. the concatenated 32 byte
+    /// encodings of its members in declaration order.
+    fn encode_data(&self) -> Vec<u8>;
+
+    fn type_hash() -> Hash {
+        keccak256(Self::TYPE)
+    }
+
+    fn hash_struct(&self) -> Hash {
+        let mut encoded = Self::type_hash().to_vec();
+        encoded.extend_from_slice(&self.encode_data());
+        keccak256(encoded)
+    }
+}
+
+/// The `EIP712Domain` struct. Members that are `None` are omitted both from
+/// the type string and the encoded data.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Domain<'a> {
+    pub name: &'a str,
+    pub version: &'a str,
+    pub chain_id: Option<u64>,
+    pub verifying_contract: Option<[u8; 20]>,
+    pub salt: Option<[u8; 32]>,
+}
+
+impl<'a> Domain<'a> {
+    pub fn new(name: &'a str, version: &'a str) -> Self {
+        Self {
+            name,
+            version,
+            chain_id: None,
+            verifying_contract: None,
+            salt: None,
+        }
+    }
+
+    pub fn type_string(&self) -> String {
+        let mut members = Vec::from(["string name", "string version"]);
+        if self.chain_id.is_some() {
+            members.push("uint256 chainId");
+        }
+        if self.verifying_contract.is_some() {
+            members.push("address verifyingContract");
+        }
+        if self.salt.is_some() {
+            members.push("bytes32 salt");
+        }
+        format!("EIP712Domain({})", members.join(","))
+    }
+
+    pub fn separator(&self) -> Hash {
+        let mut encoded = keccak256(self.type_string()).to_vec();
+        encoded.extend_from_slice(&encode_string(self.name));
+        encoded.extend_from_slice(&encode_string(self.version));
+        if let Some(chain_id) = self.chain_id {
+            encoded.extend_from_slice(&encode_uint(chain_id));
+        }
+        if let Some(verifying_contract) = self.verifying_contract {
+            encoded.extend_from_slice(&encode_address(verifying_contract));
+        }
+        if let Some(salt) = self.salt {
+            encoded.extend_from_slice(&salt);
+        }
+        keccak256(encoded)
+    }
+
+    /// Digest of `message` that is signed via `eth_signTypedData`.
+    pub fn typed_data_hash<S: Eip712Struct>(&self, message: &S) -> Hash {
+        let mut encoded = EIP712_PREFIX.to_vec();
+        encoded.extend_from_slice(&self.separator());
+        encoded.extend_from_slice(&message.hash_struct());
+        keccak256(encoded)
+    }
+}
+
+/// Typed data that has to be signed by an EVM address in order to be
+/// registered to the given account.
+///
+/// It contains the same fields as
+/// [`verification_msg`](crate::utils::verification_msg).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Registration {
+    /// SCALE encoded account the address is registered to
+    pub account: Vec<u8>,
+    pub genesis_hash: [u8; 32],
+    pub nonce: u64,
+    pub expires_at: u64,
+}
+
+impl Registration {
+    pub fn new<T: Encode, H: AsRef<[u8]>>(
+        account: T,
+        genesis_hash: H,
+        nonce: u64,
+        expires_at: u64,
+    ) -> Self {
+        Self {
+            account: account.encode(),
+            genesis_hash: encode_fixed_bytes(genesis_hash.as_ref()),
+            nonce,
+            expires_at,
+        }
+    }
+
+    pub fn domain() -> Domain<'static> {
+        Domain::new(REGISTRATION_DOMAIN_NAME, REGISTRATION_DOMAIN_VERSION)
+    }
+
+    /// Domain of the typed data signed by a contract wallet. It is bound to
+    /// the chain and the address of the wallet, so the signature cannot be
+    /// replayed by a wallet with the same address on another chain.
+    pub fn contract_domain(chain_id: u64, contract: [u8; 20]) -> Domain<'static> {
+        Domain {
+            chain_id: Some(chain_id),
+            verifying_contract: Some(contract),
+            ..Self::domain()
+        }
+    }
+
+    pub fn digest(&self) -> Hash {
+        Self::domain().typed_data_hash(self)
+    }
+
+    pub fn contract_digest(&self, chain_id: u64, contract: [u8; 20]) -> Hash {
+        Self::contract_domain(chain_id, contract).typed_data_hash(self)
+    }
+
+    /// JSON input of `eth_signTypedData_v4`.
+    pub fn typed_data_json(&self) -> String {
+        self.typed_data_json_with(&Self::domain())
+    }
+
+    /// JSON input of `eth_signTypedData_v4` for contract wallets.
+    pub fn contract_typed_data_json(&self, chain_id: u64, contract: [u8; 20]) -> String {
+        self.typed_data_json_with(&Self::contract_domain(chain_id, contract))
+    }
+
+    fn typed_data_json_with(&self, domain: &Domain) -> String {
+        let mut domain_types = Vec::from([
+            "{\"name\":\"name\",\"type\":\"string\"}",
+            "{\"name\":\"version\",\"type\":\"string\"}",
+        ]);
+        let mut domain_values = Vec::from([
+            format!("\"name\":\"{}\"", domain.name),
+            format!("\"version\":\"{}\"", domain.version),
+        ]);
+        if let Some(chain_id) = domain.chain_id {
+            domain_types.push("{\"name\":\"chainId\",\"type\":\"uint256\"}");
+            domain_values.push(format!("\"chainId\":{chain_id}"));
+        }
+        if let Some(verifying_contract) = domain.verifying_contract {
+            domain_types.push("{\"name\":\"verifyingContract\",\"type\":\"address\"}");
+            domain_values.push(format!(
+                "\"verifyingContract\":\"0x{}\"",
+                hex::encode(verifying_contract)
+            ));
+        }
+        if let Some(salt) = domain.salt {
+            domain_types.push("{\"name\":\"salt\",\"type\":\"bytes32\"}");
+            domain_values.push(format!("\"salt\":\"0x{}\"", hex::encode(salt)));
+        }
+        format!(
+            concat!(
+                "{{\"types\":{{",
+                "\"EIP712Domain\":[{}],",
+                "\"GuildNetworkRegistration\":[{{\"name\":\"account\",\"type\":\"bytes\"}},",
+                "{{\"name\":\"genesisHash\",\"type\":\"bytes32\"}},",
+                "{{\"name\":\"nonce\",\"type\":\"uint64\"}},",
+                "{{\"name\":\"expiresAt\",\"type\":\"uint64\"}}]}},",
+                "\"primaryType\":\"GuildNetworkRegistration\",",
+                "\"domain\":{{{}}},",
+                "\"message\":{{\"account\":\"0x{}\",\"genesisHash\":\"0x{}\",",
+                "\"nonce\":\"{}\",\"expiresAt\":\"{}\"}}}}"
+            ),
+            domain_types.join(","),
+            domain_values.join(","),
+            hex::encode(&self.account),
+            hex::encode(self.genesis_hash),
+            self.nonce,
+            self.expires_at,
+        )
+    }
+}
+
+impl Eip712Struct for Registration {
+    const TYPE: &'static str =
+        "GuildNetworkRegistration(bytes account,bytes32 genesisHash,uint64 nonce,uint64 expiresAt)";
+
+    fn encode_data(&self) -> Vec<u8> {
+        let mut encoded = Vec::with_capacity(4 * 32);
+        encoded.extend_from_slice(&encode_bytes(&self.account));
+        encoded.extend_from_slice(&self.genesis_hash);
+        encoded.extend_from_slice(&encode_uint(self.nonce));
+        encoded.extend_from_slice(&encode_uint(self.expires_at));
+        encoded
+    }
+}
+
+/// Digest of the [`Registration`] typed data.
+pub fn registration_digest<T: Encode, H: AsRef<[u8]>>(
+    account: T,
+    genesis_hash: H,
+    nonce: u64,
+    expires_at: u64,
+) -> Hash {
+    Registration::new(account, genesis_hash, nonce, expires_at).digest()
+}
+
+/// Digest of the [`Registration`] typed data signed by the contract wallet
+/// at `contract` on the chain with `chain_id`.
+pub fn contract_registration_digest<T: Encode, H: AsRef<[u8]>>(
+    account: T,
+    genesis_hash: H,
+    nonce: u64,
+    expires_at: u64,
+    chain_id: u64,
+    contract: [u8; 20],
+) -> Hash {
+    Registration::new(account, genesis_hash, nonce, expires_at).contract_digest(chain_id, contract)
+}
+
+pub fn encode_string(value: &str) -> Hash {
+    keccak256(value)
+}
+
+pub fn encode_bytes(value: &[u8]) -> Hash {
+    keccak256(value)
+}
+
+/// Encodes `bytes1` to `bytes32` values, i.e. right-pads them with zeros.
+pub fn encode_fixed_bytes(value: &[u8]) -> [u8; 32] {
+    debug_assert!(value.len() <= 32, "fixed bytes are at most 32 bytes");
+    let mut encoded = [0u8; 32];
+    let len = value.len().min(32);
+    encoded[..len].copy_from_slice(&value[..len]);
+    encoded
+}
+
+pub fn encode_uint<T: Into<u128>>(value: T) -> [u8; 32] {
+    let mut encoded = [0u8; 32];
+    encoded[16..].copy_from_slice(&value.into().to_be_bytes());
+    encoded
+}
+
+pub fn encode_address(address: [u8; 20]) -> [u8; 32] {
+    let mut encoded = [0u8; 32];
+    encoded[12..].copy_from_slice(&address);
+    encoded
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::identity::{EcdsaSignature, Identity, IdentityWithAuth};
+
+    // example from the EIP-712 specification
+    struct Person {
+        name: &'static str,
+        wallet: [u8; 20],
+    }
+
+    struct Mail {
+        from: Person,
+        to: Person,
+        contents: &'static str,
+    }
+
+    impl Eip712Struct for Person {
+        const TYPE: &'static str = "Person(string name,address wallet)";
+
+        fn encode_data(&self) -> Vec<u8> {
+            let mut encoded = encode_string(self.name).to_vec();
+            encoded.extend_from_slice(&encode_address(self.wallet));
+            encoded
+        }
+    }
+
+    impl Eip712Struct for Mail {
+        const TYPE: &'static str =
+            "Mail(Person from,Person to,string contents)Person(string name,address wallet)";
+
+        fn encode_data(&self) -> Vec<u8> {
+            let mut encoded = self.from.hash_struct().to_vec();
+            encoded.extend_from_slice(&self.to.hash_struct());
+            encoded.extend_from_slice(&encode_string(self.contents));
+            encoded
+        }
+    }
+
+    fn decode<const N: usize>(input: &str) -> [u8; N] {
+        let mut output = [0u8; N];
+        hex::decode_to_slice(input.trim_start_matches("0x"), &mut output).unwrap();
+        output
+    }
+
+    #[test]
+    fn specification_example() {
+        let cow = decode("0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826");
+        let domain = Domain {
+            chain_id: Some(1),
+            verifying_contract: Some(decode("0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC")),
+            ..Domain::new("Ether Mail", "1")
+        };
+        let mail = Mail {
+            from: Person {
+                name: "Cow",
+                wallet: cow,
+            },
+            to: Person {
+                name: "Bob",
+                wallet: decode("0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB"),
+            },
+            contents: "Hello, Bob!",
+        };
+
+        assert_eq!(
+            domain.type_string(),
+            "EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)"
+        );
+        assert_eq!(
+            domain.separator(),
+            decode::<32>("0xf2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f")
+        );
+        assert_eq!(
+            mail.hash_struct(),
+            decode::<32>("0xc52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e")
+        );
+        let digest = domain.typed_data_hash(&mail);
+        assert_eq!(
+            digest,
+            decode::<32>("0xbe609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2")
+        );
+
+        // signature of the specification with v = 28 normalized
+        let mut signature = [0u8; 65];
+        signature[..32].copy_from_slice(&decode::<32>(
+            "0x4355c47d63924e8a72e509b65029052eb6c299d53a04e167c5775fd466751c9d",
+        ));
+        signature[32..64].copy_from_slice(&decode::<32>(
+            "0x07299936d304c153f6443dfa05f40ff007d72911b6f72307f996231605b91562",
+        ));
+        signature[64] = 1;
+        let id_with_auth =
+            IdentityWithAuth::Eip712(Identity::Address20(cow), EcdsaSignature(signature));
+        assert!(id_with_auth.verify(digest));
+        assert!(!id_with_auth.verify(mail.hash_struct()));
+        assert!(!id_with_auth.verify(b"Hello, Bob!"));
+    }
+
+    #[test]
+    fn member_encoding() {
+        assert_eq!(encode_uint(1u64)[31], 1);
+        assert_eq!(encode_uint(u64::MAX)[..24], [0u8; 24]);
+        assert_eq!(encode_uint(u64::MAX)[24..], [0xff; 8]);
+        assert_eq!(encode_address([1u8; 20])[..12], [0u8; 12]);
+        assert_eq!(encode_fixed_bytes(&[1u8; 4])[..4], [1u8; 4]);
+        assert_eq!(encode_fixed_bytes(&[1u8; 4])[4..], [0u8; 28]);
+        assert_eq!(
+            Registration::domain().type_string(),
+            "EIP712Domain(string name,string version)"
+        );
+    }
+
+    #[test]
+    fn registration() {
+        let registration = Registration::new("account", [1u8; 32], 3, 100);
+        let other = Registration::new("account", [1u8; 32], 4, 100);
+        assert_eq!(registration.account, "account".encode());
+        assert_ne!(registration.digest(), other.digest());
+        assert_eq!(
+            registration.digest(),
+            registration_digest("account", [1u8; 32], 3, 100)
+        );
+
+        let json: serde_json::Value =
+            serde_json::from_str(&registration.typed_data_json()).unwrap();
+        assert_eq!(json["primaryType"], "GuildNetworkRegistration");
+        assert_eq!(json["domain"]["name"], REGISTRATION_DOMAIN_NAME);
+        assert_eq!(
+            json["message"]["genesisHash"],
+            format!("0x{}", "01".repeat(32))
+        );
+        assert_eq!(json["message"]["nonce"], "3");
+        assert_eq!(json["message"]["expiresAt"], "100");
+        let members = json["types"]["GuildNetworkRegistration"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|member| {
+                format!(
+                    "{} {}",
+                    member["type"].as_str().unwrap(),
+                    member["name"].as_str().unwrap()
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+        assert_eq!(
+            format!("GuildNetworkRegistration({members})"),
+            Registration::TYPE
+        );
+    }
+
+    #[test]
+    fn contract_registration() {
+        let contract = [7u8; 20];
+        let registration = Registration::new("account", [1u8; 32], 3, 100);
+        let digest = registration.contract_digest(100, contract);
+        assert_eq!(
+            Registration::contract_domain(100, contract).type_string(),
+            "EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)"
+        );
+        assert_ne!(digest, registration.digest());
+        assert_ne!(digest, registration.contract_digest(1, contract));
+        assert_ne!(digest, registration.contract_digest(100, [8u8; 20]));
+        assert_eq!(
+            digest,
+            contract_registration_digest("account", [1u8; 32], 3, 100, 100, contract)
+        );
+
+        let json: serde_json::Value =
+            serde_json::from_str(&registration.contract_typed_data_json(100, contract)).unwrap();
+        assert_eq!(json["domain"]["name"], REGISTRATION_DOMAIN_NAME);
+        assert_eq!(json["domain"]["chainId"], 100);
+        assert_eq!(
+            json["domain"]["verifyingContract"],
+            format!("0x{}", "07".repeat(20))
+        );
+        let members = json["types"]["EIP712Domain"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|member| {
+                format!(
+                    "{} {}",
+                    member["type"].as_str().unwrap(),
+                    member["name"].as_str().unwrap()
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+        assert_eq!(
+            format!("EIP712Domain({members})"),
+            Registration::contract_domain(100, contract).type_string()
+        );
+    }
+}
@@ -1,32 +1,102 @@
-use crate::hash::{Hash, Keccak256};
+use crate::hash::Hash;
 use crate::identity::Identity;
-use crate::{GuildName, RoleName};
-use parity_scale_codec::{Decode, Encode};
+use crate::{GuildName, RoleName, SpVec};
+use parity_scale_codec::{Decode, Encode, MaxEncodedLen};
 use scale_info::TypeInfo;
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, Encode, Decode, TypeInfo, Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(
+    Serialize,
+    Deserialize,
+    Encode,
+    Decode,
+    MaxEncodedLen,
+    TypeInfo,
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+)]
 pub struct Guild {
     pub name: GuildName,
     pub role: Option<RoleName>,
 }
 
-#[derive(Serialize, Deserialize, Encode, Decode, TypeInfo, Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(
+    Serialize,
+    Deserialize,
+    Encode,
+    Decode,
+    MaxEncodedLen,
+    TypeInfo,
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+)]
 pub enum Logic {
     And,
     Or,
 }
 
-#[derive(Serialize, Deserialize, Encode, Decode, TypeInfo, Clone, Copy, Debug, PartialEq, Eq)]
-pub enum Filter {
+/// Several parent roles and an optional allowlist (Merkle root and number of
+/// leaves) evaluated together. The parts are combined with `logic`, while the
+/// logic of [`Filter::Composite`] combines the result with the requirements.
+/// The container of the parent roles (`G`) is generic, so that the runtime
+/// can store them in a bounded vector.
+#[derive(
+    Serialize, Deserialize, Encode, Decode, MaxEncodedLen, TypeInfo, Clone, Debug, PartialEq, Eq,
+)]
+pub struct Composite<G = SpVec<Guild>> {
+    pub guilds: G,
+    pub allowlist: Option<(Hash, u32)>,
+    pub logic: Logic,
+}
+
+impl<G: AsRef<[Guild]>> Composite<G> {
+    pub fn len(&self) -> usize {
+        self.guilds.as_ref().len() + usize::from(self.allowlist.is_some())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[derive(
+    Serialize, Deserialize, Encode, Decode, MaxEncodedLen, TypeInfo, Clone, Debug, PartialEq, Eq,
+)]
+pub enum Filter<G = SpVec<Guild>> {
     Allowlist(Hash, Logic, u32),
     Guild(Guild, Logic),
+    Composite(Composite<G>, Logic),
 }
 
-impl Filter {
+impl<G> Filter<G> {
     pub fn allowlist(allowlist: &[Identity], logic: Logic) -> Self {
         let length = allowlist.len();
-        let root = crate::merkle::root::<Keccak256, _>(allowlist);
+        let root = crate::merkle::root(allowlist);
         Filter::Allowlist(root, logic, length as u32)
     }
+
+    /// Returns how the filter is combined with the requirements of the role.
+    pub fn logic(&self) -> Logic {
+        match self {
+            Self::Allowlist(_, logic, _) | Self::Guild(_, logic) | Self::Composite(_, logic) => {
+                *logic
+            }
+        }
+    }
+
+    /// Returns the Merkle root and the number of leaves of the allowlist the
+    /// filter contains, if any.
+    pub fn allowlist_root(&self) -> Option<(Hash, u32)> {
+        match self {
+            Self::Allowlist(root, _, n_leaves) => Some((*root, *n_leaves)),
+            Self::Composite(composite, _) => composite.allowlist,
+            Self::Guild(..) => None,
+        }
+    }
 }
@@ -18,12 +18,44 @@ pub struct Ed25519Signature(pub [u8; 64]);
 #[derive(Encode, Decode, TypeInfo, Eq, PartialEq, Clone, Copy, Debug)]
 pub struct Sr25519Signature(pub [u8; 64]);
 
+/// Signature of a contract wallet that is checked via the EIP-1271
+/// `isValidSignature` method of the contract on the given EVM chain.
+///
+/// The chain id and the contract address are part of the EIP-712 domain of
+/// the signed typed data, see
+/// [`Registration::contract_domain`](crate::eip712::Registration::contract_domain),
+/// so the signature is only valid on the given chain.
+///
+/// An all-zero signature is forwarded as empty bytes which, e.g. in case of
+/// a Gnosis Safe, means that the message was approved onchain via
+/// `signMessage`.
+#[derive(Encode, Decode, TypeInfo, Eq, PartialEq, Clone, Copy, Debug)]
+pub struct ContractSignature {
+    pub chain_id: u64,
+    pub signature: [u8; 65],
+}
+
+impl ContractSignature {
+    pub fn signature_bytes(&self) -> &[u8] {
+        if self.signature == [0u8; 65] {
+            &[]
+        } else {
+            &self.signature
+        }
+    }
+}
+
 #[derive(Encode, Decode, TypeInfo, Eq, PartialEq, Clone, Copy, Debug)]
 pub enum IdentityWithAuth {
     Ecdsa(Identity, EcdsaSignature),
     Ed25519(Identity, Ed25519Signature),
     Sr25519(Identity, Sr25519Signature),
     Other(Identity, [u8; 64]),
+    /// Ethereum address signing EIP-712 typed data
+    Eip712(Identity, EcdsaSignature),
+    /// Contract wallet signing the EIP-712 registration typed data,
+    /// verified by the oracle
+    Eip1271(Identity, ContractSignature),
 }
 
 impl IdentityWithAuth {
@@ -73,7 +105,19 @@ impl IdentityWithAuth {
                     .verify_simple(SR_SIGNING_CTX, msg.as_ref(), &sr_sig)
                     .is_ok()
             }
+            // EIP-712 typed data - the message is the typed data hash
+            Self::Eip712(Identity::Address20(address), sig) => {
+                let Ok(digest) = msg.as_ref().try_into() else {
+                    return false
+                };
+                let Some(recovered_pk) = recover_prehashed(digest, &sig.0) else {
+                    return false
+                };
+                &eth_address(&recovered_pk) == address
+            }
             Self::Other(Identity::Other(_), _) => true,
+            // NOTE contract signatures require an EVM call, so they cannot
+            // be verified here
             _ => false,
         }
     }
@@ -165,10 +209,11 @@ mod test {
     use sp_core::Pair as PairT;
 
     const TEST_ACCOUNT: &str = "test-account-0xabcde";
+    const TEST_GENESIS: [u8; 32] = [69u8; 32];
 
     #[test]
     fn test_eth_msg_hashing() {
-        let msg = verification_msg(TEST_ACCOUNT);
+        let msg = verification_msg(TEST_ACCOUNT, TEST_GENESIS, 0, 100);
         let eth_hashed_msg = eth_hash_message(&msg);
         assert_eq!(ethers::utils::hash_message(&msg).as_bytes(), eth_hashed_msg);
     }
@@ -195,7 +240,7 @@ mod test {
     #[tokio::test]
     async fn ethereum_ecdsa() {
         // check ethereum specific message hashing
-        let msg = verification_msg(TEST_ACCOUNT);
+        let msg = verification_msg(TEST_ACCOUNT, TEST_GENESIS, 0, 100);
         let eth_hashed_msg = eth_hash_message(&msg);
 
         // generate signers
@@ -234,7 +279,7 @@ mod test {
         let signer = sp_core::ecdsa::Pair::from_seed_slice(&seed).unwrap();
 
         // prehashed msg
-        let msg = verification_msg(TEST_ACCOUNT);
+        let msg = verification_msg(TEST_ACCOUNT, TEST_GENESIS, 0, 100);
         let mut prehashed_msg = [0u8; 32];
         let mut hasher = sha3::Sha3_256::new();
         hasher.update(&msg);
@@ -252,7 +297,7 @@ mod test {
 
     #[test]
     fn generic_edwards() {
-        let msg = verification_msg(TEST_ACCOUNT);
+        let msg = verification_msg(TEST_ACCOUNT, TEST_GENESIS, 0, 100);
         let seed = [2u8; 32];
         let signer = sp_core::ed25519::Pair::from_seed_slice(&seed).unwrap();
 
@@ -266,7 +311,7 @@ mod test {
 
     #[test]
     fn generic_ristretto() {
-        let msg = verification_msg(TEST_ACCOUNT);
+        let msg = verification_msg(TEST_ACCOUNT, TEST_GENESIS, 0, 100);
         let seed = [2u8; 32];
         let signer = sp_core::sr25519::Pair::from_seed_slice(&seed).unwrap();
 
@@ -278,6 +323,24 @@ mod test {
         assert!(!id_with_auth.verify(b"wrong msg"));
     }
 
+    #[test]
+    fn verification_msg_binding() {
+        let seed = [2u8; 32];
+        let signer = sp_core::sr25519::Pair::from_seed_slice(&seed).unwrap();
+        let msg = verification_msg(TEST_ACCOUNT, TEST_GENESIS, 0, 100);
+        let signature = Sr25519Signature(signer.sign(msg.as_ref()).0);
+        let address = Identity::Address32(signer.public().0);
+        let id_with_auth = IdentityWithAuth::Sr25519(address, signature);
+
+        assert!(id_with_auth.verify(&msg));
+        // different network
+        assert!(!id_with_auth.verify(verification_msg(TEST_ACCOUNT, [0u8; 32], 0, 100)));
+        // replayed signature
+        assert!(!id_with_auth.verify(verification_msg(TEST_ACCOUNT, TEST_GENESIS, 1, 100)));
+        // extended expiry
+        assert!(!id_with_auth.verify(verification_msg(TEST_ACCOUNT, TEST_GENESIS, 0, 101)));
+    }
+
     #[test]
     fn other_identities() {
         let id_with_auth = IdentityWithAuth::Other(Identity::Other([0u8; 64]), [0u8; 64]);
@@ -288,6 +351,21 @@ mod test {
         assert!(!id_with_auth.verify(b""));
     }
 
+    #[test]
+    fn contract_signatures() {
+        let mut contract_signature = ContractSignature {
+            chain_id: 100,
+            signature: [0u8; 65],
+        };
+        assert!(contract_signature.signature_bytes().is_empty());
+        let id_with_auth =
+            IdentityWithAuth::Eip1271(Identity::Address20([1u8; 20]), contract_signature);
+        assert!(!id_with_auth.verify([0u8; 32]));
+
+        contract_signature.signature[64] = 1;
+        assert_eq!(contract_signature.signature_bytes().len(), 65);
+    }
+
     #[test]
     fn invalid_crypto_signatures() {
         let address = Identity::Address20([0u8; 20]);
@@ -1,10 +1,13 @@
 mod auth;
+pub mod siwe;
 pub use auth::*;
 
-use crate::{Decode, Encode, TypeInfo};
+use crate::{Decode, Encode, MaxEncodedLen, TypeInfo};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-#[derive(Encode, Decode, TypeInfo, Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd)]
+#[derive(
+    Encode, Decode, MaxEncodedLen, TypeInfo, Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd,
+)]
 pub enum Identity {
     Address20([u8; 20]),
     Address32([u8; 32]),
@@ -28,6 +31,8 @@ impl From<IdentityWithAuth> for Identity {
             IdentityWithAuth::Ed25519(id, _) => id,
             IdentityWithAuth::Sr25519(id, _) => id,
             IdentityWithAuth::Other(id, _) => id,
+            IdentityWithAuth::Eip712(id, _) => id,
+            IdentityWithAuth::Eip1271(id, _) => id,
         }
     }
 }
@@ -39,6 +44,8 @@ impl From<&IdentityWithAuth> for Identity {
             IdentityWithAuth::Ed25519(id, _) => *id,
             IdentityWithAuth::Sr25519(id, _) => *id,
             IdentityWithAuth::Other(id, _) => *id,
+            IdentityWithAuth::Eip712(id, _) => *id,
+            IdentityWithAuth::Eip1271(id, _) => *id,
         }
     }
 }
@@ -91,6 +98,16 @@ mod test {
             let id_with_auth = IdentityWithAuth::Other($id, [0u8; 64]);
             assert_eq!($id, (&id_with_auth).into());
             assert_eq!($id, id_with_auth.into());
+            let id_with_auth = IdentityWithAuth::Eip712($id, EcdsaSignature([0u8; 65]));
+            assert_eq!($id, (&id_with_auth).into());
+            assert_eq!($id, id_with_auth.into());
+            let contract_signature = ContractSignature {
+                chain_id: 1,
+                signature: [0u8; 65],
+            };
+            let id_with_auth = IdentityWithAuth::Eip1271($id, contract_signature);
+            assert_eq!($id, (&id_with_auth).into());
+            assert_eq!($id, id_with_auth.into());
         };
     }
 
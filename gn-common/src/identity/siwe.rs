@@ -0,0 +1,663 @@
+//! Sign-In-With-Ethereum ([EIP-4361](https://eips.ethereum.org/EIPS/eip-4361))
+//! messages for registering EVM addresses.
+//!
+//! Wallets display these messages in a human readable form, while the
+//! registration specific data (the Guild Network account, the genesis hash,
+//! the registration nonce and the expiry block) is bound to the message via
+//! its `Nonce` field and a `urn:guild-network` resource.
+use crate::hash::keccak256;
+use crate::Encode;
+use core::fmt;
+use core::str::FromStr;
+use scale_info::prelude::format;
+use scale_info::prelude::string::String;
+use scale_info::prelude::vec::Vec;
+
+pub const SIWE_VERSION: &str = "1";
+pub const SIWE_STATEMENT: &str = "Register this address as an identity on Guild Network.";
+
+const PREAMBLE: &str = " wants you to sign in with your Ethereum account:";
+const URI_TAG: &str = "URI: ";
+const VERSION_TAG: &str = "Version: ";
+const CHAIN_ID_TAG: &str = "Chain ID: ";
+const NONCE_TAG: &str = "Nonce: ";
+const ISSUED_AT_TAG: &str = "Issued At: ";
+const EXPIRATION_TIME_TAG: &str = "Expiration Time: ";
+const NOT_BEFORE_TAG: &str = "Not Before: ";
+const REQUEST_ID_TAG: &str = "Request ID: ";
+const RESOURCES_TAG: &str = "Resources:";
+const RESOURCE_PREFIX: &str = "- ";
+const MIN_NONCE_LEN: usize = 8;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SiweMessage {
+    pub domain: String,
+    pub address: [u8; 20],
+    pub statement: Option<String>,
+    pub uri: String,
+    pub chain_id: u64,
+    pub nonce: String,
+    pub issued_at: String,
+    pub expiration_time: Option<String>,
+    pub not_before: Option<String>,
+    pub request_id: Option<String>,
+    pub resources: Vec<String>,
+}
+
+impl SiweMessage {
+    /// Creates a message without registration data, see
+    /// [`SiweMessage::bind_registration`].
+    pub fn new(
+        domain: String,
+        address: [u8; 20],
+        uri: String,
+        chain_id: u64,
+        issued_at: String,
+    ) -> Self {
+        Self {
+            domain,
+            address,
+            statement: Some(String::from(SIWE_STATEMENT)),
+            uri,
+            chain_id,
+            nonce: String::new(),
+            issued_at,
+            expiration_time: None,
+            not_before: None,
+            request_id: None,
+            resources: Vec::new(),
+        }
+    }
+
+    /// Binds the message to registering the address to `account` on the
+    /// network with the given genesis hash.
+    pub fn bind_registration<T: Encode, H: AsRef<[u8]>>(
+        mut self,
+        account: T,
+        genesis_hash: H,
+        nonce: u64,
+        expires_at: u64,
+    ) -> Self {
+        self.nonce = registration_nonce(nonce);
+        self.resources
+            .push(registration_resource(account, genesis_hash, expires_at));
+        self
+    }
+
+    /// Checks that the message was created via
+    /// [`SiweMessage::bind_registration`] with the same parameters.
+    pub fn verify_registration<T: Encode, H: AsRef<[u8]>>(
+        &self,
+        account: T,
+        genesis_hash: H,
+        nonce: u64,
+        expires_at: u64,
+    ) -> Result<(), &'static str> {
+        if self.nonce != registration_nonce(nonce) {
+            return Err("invalid nonce");
+        }
+        let resource = registration_resource(account, genesis_hash, expires_at);
+        if !self.resources.contains(&resource) {
+            return Err("missing registration resource");
+        }
+        Ok(())
+    }
+
+    /// Checks that the message was issued by `domain` for the chain with
+    /// `chain_id` and that it is valid at `now`, given in seconds since the
+    /// Unix epoch.
+    pub fn verify_validity(
+        &self,
+        domain: &str,
+        chain_id: u64,
+        now: u64,
+    ) -> Result<(), &'static str> {
+        if self.domain != domain {
+            return Err("invalid domain");
+        }
+        if self.chain_id != chain_id {
+            return Err("invalid chain id");
+        }
+        if let Some(expiration_time) = &self.expiration_time {
+            let expires_at = unix_timestamp(expiration_time).ok_or("invalid expiration time")?;
+            if expires_at <= now {
+                return Err("message expired");
+            }
+        }
+        if let Some(not_before) = &self.not_before {
+            let valid_from = unix_timestamp(not_before).ok_or("invalid not before")?;
+            if valid_from > now {
+                return Err("message not yet valid");
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for SiweMessage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{}{PREAMBLE}", self.domain)?;
+        writeln!(f, "{}", eip55_checksum(&self.address))?;
+        writeln!(f)?;
+        if let Some(statement) = &self.statement {
+            writeln!(f, "{statement}")?;
+        }
+        writeln!(f)?;
+        writeln!(f, "{URI_TAG}{}", self.uri)?;
+        writeln!(f, "{VERSION_TAG}{SIWE_VERSION}")?;
+        writeln!(f, "{CHAIN_ID_TAG}{}", self.chain_id)?;
+        writeln!(f, "{NONCE_TAG}{}", self.nonce)?;
+        write!(f, "{ISSUED_AT_TAG}{}", self.issued_at)?;
+        if let Some(expiration_time) = &self.expiration_time {
+            write!(f, "\n{EXPIRATION_TIME_TAG}{expiration_time}")?;
+        }
+        if let Some(not_before) = &self.not_before {
+            write!(f, "\n{NOT_BEFORE_TAG}{not_before}")?;
+        }
+        if let Some(request_id) = &self.request_id {
+            write!(f, "\n{REQUEST_ID_TAG}{request_id}")?;
+        }
+        if !self.resources.is_empty() {
+            write!(f, "\n{RESOURCES_TAG}")?;
+            for resource in &self.resources {
+                write!(f, "\n{RESOURCE_PREFIX}{resource}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for SiweMessage {
+    type Err = &'static str;
+
+    fn from_str(message: &str) -> Result<Self, Self::Err> {
+        let mut lines = message.split('\n').peekable();
+
+        let domain = lines
+            .next()
+            .and_then(|line| line.strip_suffix(PREAMBLE))
+            .ok_or("invalid preamble")?;
+        if domain.is_empty() || domain.contains(char::is_whitespace) {
+            return Err("invalid domain");
+        }
+
+        let address = lines.next().ok_or("missing address")?;
+        let address = parse_checksummed_address(address)?;
+
+        if lines.next() != Some("") {
+            return Err("missing empty line after address");
+        }
+        let statement = match lines.next() {
+            Some("") => None,
+            Some(statement) => {
+                if lines.next() != Some("") {
+                    return Err("missing empty line after statement");
+                }
+                Some(String::from(statement))
+            }
+            None => return Err("unexpected end of message"),
+        };
+
+        let uri = tagged(lines.next(), URI_TAG)?;
+        if !uri.contains(':') || uri.contains(char::is_whitespace) {
+            return Err("invalid uri");
+        }
+        if tagged(lines.next(), VERSION_TAG)? != SIWE_VERSION {
+            return Err("unsupported version");
+        }
+        let chain_id = tagged(lines.next(), CHAIN_ID_TAG)?
+            .parse::<u64>()
+            .map_err(|_| "invalid chain id")?;
+        let nonce = tagged(lines.next(), NONCE_TAG)?;
+        if nonce.len() < MIN_NONCE_LEN || !nonce.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err("invalid nonce");
+        }
+        let issued_at = tagged(lines.next(), ISSUED_AT_TAG)?;
+        if !is_rfc3339(issued_at) {
+            return Err("invalid issued at");
+        }
+
+        let expiration_time = optional_tagged(&mut lines, EXPIRATION_TIME_TAG);
+        if expiration_time.map_or(false, |time| !is_rfc3339(time)) {
+            return Err("invalid expiration time");
+        }
+        let not_before = optional_tagged(&mut lines, NOT_BEFORE_TAG);
+        if not_before.map_or(false, |time| !is_rfc3339(time)) {
+            return Err("invalid not before");
+        }
+        let request_id = optional_tagged(&mut lines, REQUEST_ID_TAG);
+
+        let mut resources = Vec::new();
+        if lines.peek() == Some(&RESOURCES_TAG) {
+            lines.next();
+            for line in lines.by_ref() {
+                let resource = line
+                    .strip_prefix(RESOURCE_PREFIX)
+                    .ok_or("invalid resource")?;
+                if !resource.contains(':') || resource.contains(char::is_whitespace) {
+                    return Err("invalid resource");
+                }
+                resources.push(String::from(resource));
+            }
+        }
+
+        if lines.next().is_some() {
+            return Err("unexpected trailing lines");
+        }
+
+        Ok(Self {
+            domain: String::from(domain),
+            address,
+            statement,
+            uri: String::from(uri),
+            chain_id,
+            nonce: String::from(nonce),
+            issued_at: String::from(issued_at),
+            expiration_time: expiration_time.map(String::from),
+            not_before: not_before.map(String::from),
+            request_id: request_id.map(String::from),
+            resources,
+        })
+    }
+}
+
+/// Returns the address in the mixed-case checksum encoding of
+/// [EIP-55](https://eips.ethereum.org/EIPS/eip-55).
+pub fn eip55_checksum(address: &[u8; 20]) -> String {
+    let lowercase = hex::encode(address);
+    let hash = keccak256(lowercase.as_bytes());
+    let checksummed = lowercase
+        .chars()
+        .enumerate()
+        .map(|(i, c)| {
+            let nibble = (hash[i / 2] >> (4 * (1 - i % 2))) & 0x0f;
+            if nibble >= 8 {
+                c.to_ascii_uppercase()
+            } else {
+                c
+            }
+        })
+        .collect::<String>();
+    format!("0x{checksummed}")
+}
+
+/// Parses a `0x` prefixed hex address regardless of its checksum.
+pub fn parse_address(address: &str) -> Result<[u8; 20], &'static str> {
+    let mut bytes = [0u8; 20];
+    let hex_address = address.strip_prefix("0x").ok_or("invalid address")?;
+    hex::decode_to_slice(hex_address, &mut bytes).map_err(|_| "invalid address")?;
+    Ok(bytes)
+}
+
+fn parse_checksummed_address(address: &str) -> Result<[u8; 20], &'static str> {
+    let bytes = parse_address(address)?;
+    if eip55_checksum(&bytes) != address {
+        return Err("invalid address checksum");
+    }
+    Ok(bytes)
+}
+
+fn registration_nonce(nonce: u64) -> String {
+    format!("{nonce:0width$}", width = MIN_NONCE_LEN)
+}
+
+fn registration_resource<T: Encode, H: AsRef<[u8]>>(
+    account: T,
+    genesis_hash: H,
+    expires_at: u64,
+) -> String {
+    format!(
+        "urn:guild-network:{}:{}:{}",
+        hex::encode(genesis_hash),
+        hex::encode(account.encode()),
+        expires_at
+    )
+}
+
+fn tagged<'a>(line: Option<&'a str>, tag: &str) -> Result<&'a str, &'static str> {
+    line.and_then(|line| line.strip_prefix(tag))
+        .filter(|value| !value.is_empty())
+        .ok_or("missing or invalid field")
+}
+
+fn optional_tagged<'a, I: Iterator<Item = &'a str>>(
+    lines: &mut core::iter::Peekable<I>,
+    tag: &str,
+) -> Option<&'a str> {
+    let value = (*lines.peek()?).strip_prefix(tag)?;
+    lines.next();
+    Some(value)
+}
+
+/// Checks whether `time` is an RFC 3339 date-time, e.g.
+/// `2023-03-01T12:00:00.000Z` or `2023-03-01T12:00:00+01:00`.
+fn is_rfc3339(time: &str) -> bool {
+    let bytes = time.as_bytes();
+    if bytes.len() < 20 {
+        return false;
+    }
+    let number = |range: core::ops::Range<usize>| -> Option<u32> {
+        let digits = &bytes[range];
+        if !digits.iter().all(u8::is_ascii_digit) {
+            return None;
+        }
+        Some(
+            digits
+                .iter()
+                .fold(0, |acc, digit| acc * 10 + u32::from(digit - b'0')),
+        )
+    };
+    let valid_date = number(0..4).is_some()
+        && bytes[4] == b'-'
+        && matches!(number(5..7), Some(1..=12))
+        && bytes[7] == b'-'
+        && matches!(number(8..10), Some(1..=31));
+    let valid_time = matches!(bytes[10], b'T' | b't')
+        && matches!(number(11..13), Some(0..=23))
+        && bytes[13] == b':'
+        && matches!(number(14..16), Some(0..=59))
+        && bytes[16] == b':'
+        && matches!(number(17..19), Some(0..=60));
+    if !(valid_date && valid_time) {
+        return false;
+    }
+
+    let mut offset = &bytes[19..];
+    if let Some(fraction) = offset.strip_prefix(b".") {
+        let n_digits = fraction.iter().take_while(|b| b.is_ascii_digit()).count();
+        if n_digits == 0 {
+            return false;
+        }
+        offset = &fraction[n_digits..];
+    }
+    match offset {
+        [b'Z' | b'z'] => true,
+        [b'+' | b'-', h1, h2, b':', m1, m2] => {
+            [h1, h2, m1, m2].iter().all(|b| b.is_ascii_digit())
+                && (h1 - b'0') * 10 + (h2 - b'0') <= 23
+                && (m1 - b'0') * 10 + (m2 - b'0') <= 59
+        }
+        _ => false,
+    }
+}
+
+/// Converts an RFC 3339 date-time into seconds since the Unix epoch.
+fn unix_timestamp(time: &str) -> Option<u64> {
+    if !is_rfc3339(time) {
+        return None;
+    }
+    let bytes = time.as_bytes();
+    let number = |range: core::ops::Range<usize>| -> i64 {
+        bytes[range]
+            .iter()
+            .fold(0, |acc, digit| acc * 10 + i64::from(digit - b'0'))
+    };
+    // NOTE days since the epoch are computed from the civil date, see
+    // http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+    let (month, day) = (number(5..7), number(8..10));
+    let year = number(0..4) - i64::from(month <= 2);
+    let era = year / 400;
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = era * 146_097 + day_of_era - 719_468;
+    let seconds = number(11..13) * 3600 + number(14..16) * 60 + number(17..19);
+
+    let offset = match bytes[bytes.len() - 1] {
+        b'Z' | b'z' => 0,
+        _ => {
+            let start = bytes.len() - 6;
+            let offset = number(start + 1..start + 3) * 3600 + number(start + 4..start + 6) * 60;
+            if bytes[start] == b'-' {
+                -offset
+            } else {
+                offset
+            }
+        }
+    };
+    u64::try_from(days * 86_400 + seconds - offset).ok()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::identity::{EcdsaSignature, Identity, IdentityWithAuth};
+    use ethers::core::k256::ecdsa::SigningKey;
+    use ethers::signers::{LocalWallet, Signer as EthSigner};
+
+    const TEST_ACCOUNT: &str = "test-account-0xabcde";
+    const TEST_GENESIS: [u8; 32] = [69u8; 32];
+
+    // example message from the EIP-4361 specification
+    const SPEC_MESSAGE: &str = "service.invalid wants you to sign in with your Ethereum account:
+0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2
+
+I accept the ServiceOrg Terms of Service: https://service.invalid/tos
+
+URI: https://service.invalid/login
+Version: 1
+Chain ID: 1
+Nonce: 32891756
+Issued At: 2021-09-30T16:25:24Z
+Resources:
+- ipfs://bafybeiemxf5abjwjbikoz4mc3a3dla6ual3jsgpdr4cjr3oz3evfyavhwq/
+- https://example.com/my-web2-claim.json";
+
+    fn test_message(address: [u8; 20]) -> SiweMessage {
+        SiweMessage::new(
+            "app.guild.xyz".into(),
+            address,
+            "https://app.guild.xyz/".into(),
+            1,
+            "2023-03-01T12:00:00.000Z".into(),
+        )
+        .bind_registration(TEST_ACCOUNT, TEST_GENESIS, 5, 100)
+    }
+
+    #[test]
+    fn eip55() {
+        // test vectors from the EIP-55 specification
+        for expected in [
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+            "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+            "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+        ] {
+            let address = parse_checksummed_address(expected).unwrap();
+            assert_eq!(eip55_checksum(&address), expected);
+        }
+        assert_eq!(
+            parse_checksummed_address("0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed"),
+            Err("invalid address checksum")
+        );
+    }
+
+    #[test]
+    fn spec_roundtrip() {
+        let message = SiweMessage::from_str(SPEC_MESSAGE).unwrap();
+        assert_eq!(message.domain, "service.invalid");
+        assert_eq!(
+            message.statement.as_deref(),
+            Some("I accept the ServiceOrg Terms of Service: https://service.invalid/tos")
+        );
+        assert_eq!(message.chain_id, 1);
+        assert_eq!(message.nonce, "32891756");
+        assert!(message.expiration_time.is_none());
+        assert_eq!(message.resources.len(), 2);
+        assert_eq!(message.to_string(), SPEC_MESSAGE);
+
+        // statements are optional
+        let message = SiweMessage {
+            statement: None,
+            expiration_time: Some("2021-10-30T16:25:24+02:00".into()),
+            request_id: Some("some-request".into()),
+            resources: Vec::new(),
+            ..message
+        };
+        let serialized = message.to_string();
+        assert!(serialized.contains("Cc2\n\n\nURI: "));
+        assert_eq!(SiweMessage::from_str(&serialized).unwrap(), message);
+    }
+
+    #[test]
+    fn invalid_messages() {
+        let message = test_message([1u8; 20]).to_string();
+        assert!(SiweMessage::from_str(&message).is_ok());
+
+        let test_data = [
+            (
+                message.replace(" wants you", " needs you"),
+                "invalid preamble",
+            ),
+            (
+                message.replace(&eip55_checksum(&[1u8; 20]), &hex::encode([1u8; 20])),
+                "invalid address",
+            ),
+            (
+                message.replace("Version: 1", "Version: 2"),
+                "unsupported version",
+            ),
+            (
+                message.replace("Chain ID: 1", "Chain ID: x"),
+                "invalid chain id",
+            ),
+            (
+                message.replace("Nonce: 00000005", "Nonce: 5"),
+                "invalid nonce",
+            ),
+            (
+                message.replace("2023-03-01T12:00:00.000Z", "2023-03-01 12:00:00"),
+                "invalid issued at",
+            ),
+            (
+                message.replace("URI: https://app.guild.xyz/", "URI: app"),
+                "invalid uri",
+            ),
+            (format!("{message}\n"), "invalid resource"),
+            (format!("{message}\nfoo"), "invalid resource"),
+        ];
+
+        for (invalid, expected_error) in test_data {
+            assert_eq!(SiweMessage::from_str(&invalid), Err(expected_error));
+        }
+    }
+
+    #[test]
+    fn rfc3339_timestamps() {
+        for valid in [
+            "2021-09-30T16:25:24Z",
+            "2021-09-30t16:25:24.123456z",
+            "2021-09-30T16:25:24+02:00",
+            "2021-09-30T16:25:24.1-11:30",
+        ] {
+            assert!(is_rfc3339(valid), "{valid}");
+        }
+        for invalid in [
+            "2021-09-30",
+            "2021-09-30T16:25:24",
+            "2021-13-30T16:25:24Z",
+            "2021-09-30T24:25:24Z",
+            "2021-09-30T16:25:24.Z",
+            "2021-09-30T16:25:24+0200",
+        ] {
+            assert!(!is_rfc3339(invalid), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn unix_timestamps() {
+        assert_eq!(unix_timestamp("1970-01-01T00:00:00Z"), Some(0));
+        assert_eq!(unix_timestamp("2021-09-30T16:25:24Z"), Some(1_633_019_124));
+        assert_eq!(
+            unix_timestamp("2021-09-30T18:25:24.5+02:00"),
+            Some(1_633_019_124)
+        );
+        assert_eq!(
+            unix_timestamp("2021-09-30T05:55:24-10:30"),
+            Some(1_633_019_124)
+        );
+        assert_eq!(unix_timestamp("2000-02-29T00:00:00z"), Some(951_782_400));
+        assert_eq!(unix_timestamp("1969-12-31T23:59:59Z"), None);
+        assert_eq!(unix_timestamp("2021-09-30T16:25:24"), None);
+    }
+
+    #[test]
+    fn message_validity() {
+        let now = 1_677_672_000; // 2023-03-01T12:00:00Z
+        let message = test_message([1u8; 20]);
+        assert!(message.verify_validity("app.guild.xyz", 1, now).is_ok());
+        assert_eq!(
+            message.verify_validity("evil.xyz", 1, now),
+            Err("invalid domain")
+        );
+        assert_eq!(
+            message.verify_validity("app.guild.xyz", 5, now),
+            Err("invalid chain id")
+        );
+
+        let message = SiweMessage {
+            expiration_time: Some("2023-03-01T12:10:00Z".into()),
+            not_before: Some("2023-03-01T11:50:00+00:00".into()),
+            ..message
+        };
+        assert!(message.verify_validity("app.guild.xyz", 1, now).is_ok());
+        assert_eq!(
+            message.verify_validity("app.guild.xyz", 1, now + 600),
+            Err("message expired")
+        );
+        assert_eq!(
+            message.verify_validity("app.guild.xyz", 1, now - 601),
+            Err("message not yet valid")
+        );
+    }
+
+    #[test]
+    fn registration_binding() {
+        let message = test_message([1u8; 20]);
+        assert_eq!(message.nonce, "00000005");
+        assert!(message
+            .verify_registration(TEST_ACCOUNT, TEST_GENESIS, 5, 100)
+            .is_ok());
+        assert_eq!(
+            message.verify_registration(TEST_ACCOUNT, TEST_GENESIS, 6, 100),
+            Err("invalid nonce")
+        );
+        assert_eq!(
+            message.verify_registration(TEST_ACCOUNT, [0u8; 32], 5, 100),
+            Err("missing registration resource")
+        );
+        assert_eq!(
+            message.verify_registration(TEST_ACCOUNT, TEST_GENESIS, 5, 101),
+            Err("missing registration resource")
+        );
+        assert_eq!(
+            message.verify_registration("other-account", TEST_GENESIS, 5, 100),
+            Err("missing registration resource")
+        );
+    }
+
+    #[tokio::test]
+    async fn signed_message() {
+        let signing_key = SigningKey::from_bytes(&[2u8; 32]).unwrap();
+        let eth_signer = LocalWallet::from(signing_key);
+        let address = eth_signer.address().to_fixed_bytes();
+
+        let message = test_message(address).to_string();
+        let signature = eth_signer.sign_message(&message).await.unwrap();
+        let id_with_auth =
+            IdentityWithAuth::from_evm(&hex::encode(address), &hex::encode(signature.to_vec()))
+                .unwrap();
+
+        let parsed = SiweMessage::from_str(&message).unwrap();
+        assert_eq!(
+            Identity::from(id_with_auth),
+            Identity::Address20(parsed.address)
+        );
+        assert!(id_with_auth.verify(&message));
+        assert!(!IdentityWithAuth::Ecdsa(
+            Identity::Address20([1u8; 20]),
+            EcdsaSignature(signature.to_vec().try_into().unwrap())
+        )
+        .verify(&message));
+    }
+}
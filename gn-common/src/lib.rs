@@ -3,6 +3,7 @@
 #![deny(clippy::dbg_macro)]
 #![deny(unused_crate_dependencies)]
 
+pub mod eip712;
 pub mod filter;
 pub mod hash;
 pub mod identity;
@@ -11,7 +12,7 @@ pub mod pad;
 pub mod utils;
 
 use parity_scale_codec::alloc::vec::Vec as SpVec;
-use parity_scale_codec::{Decode, Encode};
+use parity_scale_codec::{Decode, Encode, MaxEncodedLen};
 use scale_info::TypeInfo;
 use serde::{Deserialize, Serialize};
 
@@ -23,6 +24,12 @@ pub fn offchain_allowlist_key(key: &[u8]) -> SpVec<u8> {
     offchain_key
 }
 
+pub fn offchain_allowlist_chunk_key(key: &[u8], chunk_index: u32) -> SpVec<u8> {
+    let mut offchain_key = offchain_allowlist_key(key);
+    offchain_key.extend_from_slice(&chunk_index.to_le_bytes());
+    offchain_key
+}
+
 pub type GuildName = [u8; 32];
 pub type RoleName = [u8; 32];
 
@@ -42,24 +49,40 @@ pub enum RequestData<T> {
     Register {
         identity_with_auth: identity::IdentityWithAuth,
         index: u8,
+        nonce: u64,
+        expires_at: u64,
     },
     ReqCheck {
         account: T,
         guild_name: GuildName,
         role_name: RoleName,
     },
+    /// Requirement check of several roles at once, answered with one
+    /// result per role in the same order.
+    ReqCheckMany {
+        account: T,
+        roles: SpVec<(GuildName, RoleName)>,
+    },
 }
 
-#[derive(Serialize, Deserialize, Encode, Decode, TypeInfo, Clone, Debug, Eq, PartialEq)]
-pub struct Guild<T> {
+/// Guild data with the metadata (`M`) and the role list (`R`) containers
+/// being generic, so that the runtime can store them in bounded vectors.
+#[derive(
+    Serialize, Deserialize, Encode, Decode, MaxEncodedLen, TypeInfo, Clone, Debug, Eq, PartialEq,
+)]
+pub struct Guild<T, M = SerializedData, R = SpVec<RoleName>> {
     pub name: GuildName,
     pub owner: T,
-    pub metadata: SerializedData,
-    pub roles: SpVec<RoleName>,
+    pub metadata: M,
+    pub roles: R,
 }
 
-#[derive(Encode, Decode, TypeInfo, Clone, Debug, Eq, PartialEq)]
-pub struct Role {
-    pub filter: Option<filter::Filter>,
-    pub requirements: Option<SerializedRequirements>,
+/// Role data with the filter (`F`) and the serialized requirements (`Q`)
+/// being generic, so that the runtime can store them in bounded vectors.
+#[derive(
+    Serialize, Deserialize, Encode, Decode, MaxEncodedLen, TypeInfo, Clone, Debug, Eq, PartialEq,
+)]
+pub struct Role<F = filter::Filter, Q = SerializedRequirements> {
+    pub filter: Option<F>,
+    pub requirements: Option<Q>,
 }
@@ -1,42 +1,201 @@
-use crate::hash::{Hash, Keccak256};
+use crate::hash::{keccak256, Hash};
 use crate::identity::Identity;
 use crate::{Decode, Encode, SpVec, TypeInfo};
 use serde::{Deserialize, Serialize};
 
-pub use binary_merkle_tree::merkle_root as root;
-pub type Leaf<'a> = binary_merkle_tree::Leaf<'a, Hash>;
+/// Leaf of the tree, given either by its content or by its hash.
+pub enum Leaf<'a> {
+    Value(&'a [u8]),
+    Hash(Hash),
+}
+
+impl Leaf<'_> {
+    fn hash(&self) -> Hash {
+        match self {
+            Self::Value(content) => keccak256(content),
+            Self::Hash(hash) => *hash,
+        }
+    }
+}
+
+fn hash_pair(left: &Hash, right: &Hash) -> Hash {
+    let mut combined = [0u8; 64];
+    combined[..32].copy_from_slice(left);
+    combined[32..].copy_from_slice(right);
+    keccak256(combined)
+}
+
+// NOTE nodes are hashed in their order in the tree, while the last node of a
+// level with an odd number of nodes is promoted to the next level as is
+fn next_level(level: &[Hash]) -> SpVec<Hash> {
+    level
+        .chunks(2)
+        .map(|pair| match pair {
+            [left, right] => hash_pair(left, right),
+            single => single[0],
+        })
+        .collect()
+}
+
+fn leaves(allowlist: &[Identity]) -> SpVec<Hash> {
+    allowlist.iter().map(keccak256).collect()
+}
+
+/// Root of the tree spanned by the given leaves.
+pub fn root<L: AsRef<[u8]>>(leaves: &[L]) -> Hash {
+    let mut level = leaves.iter().map(keccak256).collect::<SpVec<_>>();
+    while level.len() > 1 {
+        level = next_level(&level);
+    }
+    level.first().copied().unwrap_or_default()
+}
 
 #[derive(Serialize, Deserialize, Encode, Decode, TypeInfo, Clone, Debug, Eq, PartialEq)]
 pub struct Proof {
     pub path: SpVec<Hash>,
+    pub leaf_index: u32,
     pub id_index: u8,
 }
 
 impl Proof {
     pub fn new(allowlist: &[Identity], leaf_index: usize, id_index: u8) -> Self {
-        let merkle_proof =
-            binary_merkle_tree::merkle_proof::<Keccak256, _, _>(allowlist, leaf_index);
         Self {
-            path: merkle_proof.proof,
+            path: Self::path(leaves(allowlist), leaf_index),
+            leaf_index: leaf_index as u32,
             id_index,
         }
     }
 
-    // NOTE leaf index does not play a role in verification
-    // only a bound check is performed to ensure the index is
-    // less than the number of leaves in the tree. Setting the
-    // index to 0 ensures that it will never be greater. If the
-    // number of leaves is 0, though, access will still be
-    // denied as the proof evaluates to false. However, empty
-    // allowlists are not allowed in the respective
-    // 'create_role' call
+    /// Proof of the chunk at `chunk_index`, i.e. of the root of the subtree
+    /// spanned by the identities in the chunk. `chunk_len` has to be a power
+    /// of two, so that every chunk is an aligned subtree of the allowlist.
+    pub fn chunk(allowlist: &[Identity], chunk_index: usize, chunk_len: usize) -> Self {
+        let mut level = leaves(allowlist);
+        for _ in 0..chunk_len.trailing_zeros() {
+            level = next_level(&level);
+        }
+        Self {
+            path: Self::path(level, chunk_index),
+            leaf_index: chunk_index as u32,
+            id_index: 0,
+        }
+    }
+
+    fn path(mut level: SpVec<Hash>, mut index: usize) -> SpVec<Hash> {
+        let mut path = SpVec::new();
+        while level.len() > 1 {
+            // promoted nodes have no sibling
+            if let Some(sibling) = level.get(index ^ 1) {
+                path.push(*sibling);
+            }
+            level = next_level(&level);
+            index /= 2;
+        }
+        path
+    }
+
+    /// Verifies the proof of a chunk created by [`Proof::chunk`], the index
+    /// of the chunk is given by `leaf_index`.
+    pub fn verify_chunk(
+        &self,
+        root: &Hash,
+        n_leaves: usize,
+        chunk_len: usize,
+        chunk: &[Identity],
+    ) -> bool {
+        if !chunk_len.is_power_of_two() {
+            return false;
+        }
+        // NOTE the number of chunks is the number of nodes on the level of
+        // the chunk roots
+        let mut n_chunks = n_leaves;
+        for _ in 0..chunk_len.trailing_zeros() {
+            n_chunks = (n_chunks + 1) / 2;
+        }
+        self.verify_node(root, n_chunks, crate::merkle::root(chunk))
+    }
+
     pub fn verify(&self, root: &Hash, n_leaves: usize, leaf: Leaf) -> bool {
-        binary_merkle_tree::verify_proof::<'_, Keccak256, _, _>(
-            root,
-            self.path.iter().copied(),
-            n_leaves,
-            0,
-            leaf,
-        )
+        self.verify_node(root, n_leaves, leaf.hash())
+    }
+
+    // NOTE the index determines the side of the sibling on every level, as
+    // well as the levels where the node is promoted without a sibling, thus
+    // the length of the path has to match the depth of the node exactly. If
+    // the number of nodes is 0 the index is always out of bounds, however,
+    // empty allowlists are not allowed in the respective 'create_role' call
+    fn verify_node(&self, root: &Hash, n_nodes: usize, node: Hash) -> bool {
+        let mut index = self.leaf_index as usize;
+        if index >= n_nodes {
+            return false;
+        }
+        let mut width = n_nodes;
+        let mut siblings = self.path.iter();
+        let mut computed = node;
+        while width > 1 {
+            if index % 2 == 1 {
+                let Some(sibling) = siblings.next() else { return false };
+                computed = hash_pair(sibling, &computed);
+            } else if index + 1 < width {
+                let Some(sibling) = siblings.next() else { return false };
+                computed = hash_pair(&computed, sibling);
+            }
+            index /= 2;
+            width = (width + 1) / 2;
+        }
+        siblings.next().is_none() && &computed == root
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn proofs_are_bound_to_their_index() {
+        for n_leaves in 1..=13u8 {
+            let allowlist = (0..n_leaves)
+                .map(|i| Identity::Address20([i; 20]))
+                .collect::<SpVec<_>>();
+            let root = root(&allowlist);
+            for (leaf_index, id) in allowlist.iter().enumerate() {
+                let leaf = || Leaf::Value(id.as_ref());
+                let proof = Proof::new(&allowlist, leaf_index, 0);
+                assert!(proof.verify(&root, allowlist.len(), leaf()));
+                for wrong_index in [leaf_index ^ 1, allowlist.len()] {
+                    let moved = Proof {
+                        leaf_index: wrong_index as u32,
+                        ..proof.clone()
+                    };
+                    assert!(!moved.verify(&root, allowlist.len(), leaf()));
+                }
+                let mut extended = proof.clone();
+                extended.path.push(root);
+                assert!(!extended.verify(&root, allowlist.len(), leaf()));
+            }
+        }
+    }
+
+    #[test]
+    fn chunk_proofs_work() {
+        let chunk_len = 4;
+        for n_leaves in 1..=13u8 {
+            let allowlist = (0..n_leaves)
+                .map(|i| Identity::Address20([i; 20]))
+                .collect::<SpVec<_>>();
+            let root = root(&allowlist);
+            for (chunk_index, chunk) in allowlist.chunks(chunk_len).enumerate() {
+                let proof = Proof::chunk(&allowlist, chunk_index, chunk_len);
+                assert!(proof.verify_chunk(&root, allowlist.len(), chunk_len, chunk));
+                let mut tampered = chunk.to_vec();
+                tampered[0] = Identity::Address20([100; 20]);
+                assert!(!proof.verify_chunk(&root, allowlist.len(), chunk_len, &tampered));
+                let moved = Proof {
+                    leaf_index: chunk_index as u32 ^ 1,
+                    ..proof.clone()
+                };
+                assert!(!moved.verify_chunk(&root, allowlist.len(), chunk_len, chunk));
+            }
+        }
     }
 }
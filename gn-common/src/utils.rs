@@ -2,10 +2,27 @@ use crate::Encode;
 use scale_info::prelude::format;
 use scale_info::prelude::string::String as SpString;
 
-pub fn verification_msg<T: Encode>(id: T) -> SpString {
+pub const VERIFICATION_MSG_VERSION: u8 = 1;
+
+/// Message that has to be signed by an identity in order to be registered
+/// to the given account.
+///
+/// The genesis hash binds the signature to a single network, the nonce
+/// (stored onchain for each account) makes it single-use and the signature
+/// is rejected after the `expires_at` block.
+pub fn verification_msg<T: Encode, H: AsRef<[u8]>>(
+    id: T,
+    genesis_hash: H,
+    nonce: u64,
+    expires_at: u64,
+) -> SpString {
     format!(
-        "Guild Network registration id: {}",
-        hex::encode(id.encode())
+        "Guild Network registration\nversion: {}\nid: {}\ngenesis: {}\nnonce: {}\nexpires at block: {}",
+        VERIFICATION_MSG_VERSION,
+        hex::encode(id.encode()),
+        hex::encode(genesis_hash),
+        nonce,
+        expires_at,
     )
 }
 
@@ -0,0 +1,231 @@
+//! Contract wallet signature checks via
+//! [EIP-1271](https://eips.ethereum.org/EIPS/eip-1271).
+use crate::EvmAddress;
+use ethers::providers::{Http, Middleware, Provider};
+use ethers::types::{Address, TransactionRequest};
+
+use std::future::Future;
+
+/// `bytes4(keccak256("isValidSignature(bytes32,bytes)"))`, which is also
+/// the value returned by the contract for valid signatures.
+pub const MAGIC_VALUE: [u8; 4] = [0x16, 0x26, 0xba, 0x7e];
+
+/// Checks the signature by calling the contract via the JSON-RPC endpoint
+/// of the chain it is deployed on.
+pub async fn is_valid_signature(
+    rpc_url: &str,
+    contract: &EvmAddress,
+    hash: [u8; 32],
+    signature: &[u8],
+) -> Result<bool, anyhow::Error> {
+    let provider = Provider::<Http>::try_from(rpc_url)
+        .map_err(|error| anyhow::anyhow!("invalid rpc url: {error}"))?;
+    is_valid_signature_with(
+        |calldata| eth_call(&provider, contract, calldata),
+        hash,
+        signature,
+    )
+    .await
+}
+
+/// Executes a read-only call on the contract in the latest block and
+/// returns the raw return data.
+async fn eth_call(
+    provider: &Provider<Http>,
+    contract: &EvmAddress,
+    calldata: Vec<u8>,
+) -> Result<Vec<u8>, anyhow::Error> {
+    let tx = TransactionRequest::new()
+        .to(Address::from(*contract))
+        .data(calldata);
+    let return_data = provider
+        .call(&tx.into(), None)
+        .await
+        .map_err(|error| anyhow::anyhow!("eth_call failed: {error}"))?;
+    Ok(return_data.to_vec())
+}
+
+/// Checks the signature with `call`, which executes a read-only call on the
+/// contract with the given calldata and returns the raw return data.
+pub async fn is_valid_signature_with<F, R>(
+    call: F,
+    hash: [u8; 32],
+    signature: &[u8],
+) -> Result<bool, anyhow::Error>
+where
+    F: FnOnce(Vec<u8>) -> R,
+    R: Future<Output = Result<Vec<u8>, anyhow::Error>>,
+{
+    let return_data = call(is_valid_signature_calldata(hash, signature)).await?;
+    // NOTE the returned bytes4 is left-aligned in a 32 byte word
+    Ok(return_data.len() >= 32 && return_data[..4] == MAGIC_VALUE)
+}
+
+/// ABI encoded `isValidSignature(bytes32,bytes)` call.
+fn is_valid_signature_calldata(hash: [u8; 32], signature: &[u8]) -> Vec<u8> {
+    let padded_len = (signature.len() + 31) / 32 * 32;
+    let mut calldata = Vec::with_capacity(4 + 3 * 32 + padded_len);
+    calldata.extend_from_slice(&MAGIC_VALUE);
+    calldata.extend_from_slice(&hash);
+    // offset of the dynamic `bytes` argument
+    calldata.extend_from_slice(&abi_word(64));
+    calldata.extend_from_slice(&abi_word(signature.len() as u64));
+    calldata.extend_from_slice(signature);
+    calldata.resize(4 + 3 * 32 + padded_len, 0);
+    calldata
+}
+
+fn abi_word(value: u64) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[24..].copy_from_slice(&value.to_be_bytes());
+    word
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const CONTRACT: EvmAddress = [7u8; 20];
+    const HASH: [u8; 32] = [9u8; 32];
+
+    // accepts the hash if the signature is empty or equals the hash twice
+    async fn test_call(contract: EvmAddress, calldata: Vec<u8>) -> Result<Vec<u8>, anyhow::Error> {
+        if contract != CONTRACT {
+            anyhow::bail!("execution reverted")
+        }
+        assert_eq!(calldata[..4], MAGIC_VALUE);
+        assert_eq!(calldata[68..100], abi_word(64));
+        let hash = &calldata[4..36];
+        let len = calldata[100..132][24..]
+            .iter()
+            .fold(0usize, |acc, byte| (acc << 8) | *byte as usize);
+        let signature = &calldata[132..132 + len];
+
+        let mut return_data = vec![0u8; 32];
+        if signature.is_empty() || signature == [hash, hash].concat() {
+            return_data[..4].copy_from_slice(&MAGIC_VALUE);
+        } else {
+            return_data[..4].copy_from_slice(&[0xff; 4]);
+        }
+        Ok(return_data)
+    }
+
+    #[test]
+    fn calldata_encoding() {
+        let calldata = is_valid_signature_calldata(HASH, &[]);
+        assert_eq!(calldata.len(), 4 + 3 * 32);
+        assert_eq!(calldata[100..], [0u8; 32]);
+
+        let calldata = is_valid_signature_calldata(HASH, &[1u8; 65]);
+        assert_eq!(calldata.len(), 4 + 3 * 32 + 96);
+        assert_eq!(calldata[4..36], HASH);
+        assert_eq!(calldata[131], 65);
+        assert_eq!(calldata[132..197], [1u8; 65]);
+        assert_eq!(calldata[197..], [0u8; 31]);
+    }
+
+    // minimal JSON-RPC endpoint answering 'eth_call' requests via 'test_call'
+    async fn spawn_rpc() -> String {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let rpc_url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            loop {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut received = Vec::new();
+                let body = loop {
+                    let mut buffer = [0u8; 1024];
+                    let n = stream.read(&mut buffer).await.unwrap();
+                    received.extend_from_slice(&buffer[..n]);
+                    let text = String::from_utf8_lossy(&received).to_string();
+                    let Some(header_len) = text.find("\r\n\r\n").map(|end| end + 4) else {
+                        continue
+                    };
+                    let content_len = text[..header_len]
+                        .lines()
+                        .find_map(|line| {
+                            let line = line.to_ascii_lowercase();
+                            let len = line.strip_prefix("content-length:")?;
+                            len.trim().parse::<usize>().ok()
+                        })
+                        .unwrap_or_default();
+                    if received.len() >= header_len + content_len {
+                        break received[header_len..header_len + content_len].to_vec();
+                    }
+                };
+
+                let request: serde_json::Value = serde_json::from_slice(&body).unwrap();
+                assert_eq!(request["method"], "eth_call");
+                let params = &request["params"][0];
+                let decode = |value: &serde_json::Value| {
+                    hex::decode(value.as_str().unwrap().trim_start_matches("0x")).unwrap()
+                };
+                let contract = decode(&params["to"]).try_into().unwrap();
+                let calldata = decode(params.get("data").unwrap_or(&params["input"]));
+                let response = match test_call(contract, calldata).await {
+                    Ok(return_data) => serde_json::json!({
+                        "jsonrpc": "2.0",
+                        "id": request["id"],
+                        "result": format!("0x{}", hex::encode(return_data)),
+                    }),
+                    Err(error) => serde_json::json!({
+                        "jsonrpc": "2.0",
+                        "id": request["id"],
+                        "error": { "code": 3, "message": error.to_string() },
+                    }),
+                }
+                .to_string();
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\n\
+                    Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    response.len(),
+                    response
+                );
+                stream.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+        rpc_url
+    }
+
+    #[tokio::test]
+    async fn is_valid_signature_via_eth_call() {
+        let rpc_url = spawn_rpc().await;
+        // the contract returns the magic value
+        let valid_signature = [HASH, HASH].concat();
+        assert!(
+            is_valid_signature(&rpc_url, &CONTRACT, HASH, &valid_signature)
+                .await
+                .unwrap()
+        );
+        // the contract returns another value
+        assert!(!is_valid_signature(&rpc_url, &CONTRACT, HASH, &[1u8; 65])
+            .await
+            .unwrap());
+        // the call reverts
+        assert!(is_valid_signature(&rpc_url, &[0u8; 20], HASH, &[])
+            .await
+            .is_err());
+        assert!(is_valid_signature("not a url", &CONTRACT, HASH, &[])
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_is_valid_signature() {
+        let call = |calldata| test_call(CONTRACT, calldata);
+        let valid_signature = [HASH, HASH].concat();
+        assert!(is_valid_signature_with(call, HASH, &valid_signature)
+            .await
+            .unwrap());
+        assert!(is_valid_signature_with(call, HASH, &[]).await.unwrap());
+        assert!(!is_valid_signature_with(call, HASH, &[1u8; 65])
+            .await
+            .unwrap());
+        assert!(
+            is_valid_signature_with(|calldata| test_call([0u8; 20], calldata), HASH, &[])
+                .await
+                .is_err()
+        );
+    }
+}
@@ -1,6 +1,7 @@
 use super::Requirement;
 use gn_common::identity::Identity;
 
+pub mod eip1271;
 mod evm;
 
 impl Requirement {
@@ -7,6 +7,8 @@ pub mod balance;
 pub mod chains;
 #[cfg(feature = "check")]
 mod check;
+#[cfg(feature = "check")]
+pub use check::eip1271;
 
 use balance::Balance;
 use chains::EvmChain;
@@ -65,5 +67,7 @@ impl RequirementsWithLogic {
 #[cfg(test)]
 mod test {
     use async_trait as _;
+    use hex as _;
+    use serde_json as _;
     use tokio as _;
 }
@@ -5,6 +5,8 @@
 
 #![warn(missing_docs)]
 
+mod guild;
+
 use std::sync::Arc;
 
 use gn_runtime::{opaque::Block, AccountId, Balance, Index};
@@ -37,8 +39,10 @@ where
     C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Index>,
     C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
     C::Api: BlockBuilder<Block>,
+    C::Api: gn_runtime::guild_api::GuildApi<Block, AccountId>,
     P: TransactionPool + 'static,
 {
+    use guild::{Guild, GuildApiServer};
     use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
     use substrate_frame_rpc_system::{System, SystemApiServer};
 
@@ -50,12 +54,8 @@ where
     } = deps;
 
     module.merge(System::new(client.clone(), pool, deny_unsafe).into_rpc())?;
-    module.merge(TransactionPayment::new(client).into_rpc())?;
-
-    // Extend this RPC with a custom API by using the following syntax.
-    // `YourRpcStruct` should have a reference to a client, which is needed
-    // to call into the runtime.
-    // `module.merge(YourRpcTrait::into_rpc(YourRpcStruct::new(ReferenceToClient, ...)))?;`
+    module.merge(TransactionPayment::new(client.clone()).into_rpc())?;
+    module.merge(Guild::new(client).into_rpc())?;
 
     Ok(module)
 }
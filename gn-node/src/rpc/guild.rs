@@ -0,0 +1,193 @@
+//! RPC methods reading the guild state through the guild runtime API.
+
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use gn_common::identity::Identity;
+use gn_common::{Guild as GuildData, GuildName, Role, RoleName};
+use gn_runtime::{guild_api::GuildApi as GuildRuntimeApi, AccountId};
+use jsonrpsee::{
+    core::{Error as JsonRpseeError, RpcResult},
+    proc_macros::rpc,
+    types::error::{CallError, ErrorObject},
+};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+
+/// Error code of failed runtime API calls.
+const RUNTIME_ERROR: i32 = 1;
+/// Upper bound of the page size of `guild_members`.
+const MAX_PAGE_SIZE: u32 = 1000;
+
+/// Guild RPC methods. Every method takes an optional block hash and falls
+/// back to the best block if it's missing.
+#[rpc(server, namespace = "guild")]
+pub trait GuildApi<BlockHash> {
+    /// Returns the guild with the given name.
+    #[method(name = "guild")]
+    fn guild(
+        &self,
+        guild_name: GuildName,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Option<GuildData<AccountId>>>;
+
+    /// Returns the names of the roles of a guild.
+    #[method(name = "roles")]
+    fn roles(&self, guild_name: GuildName, at: Option<BlockHash>) -> RpcResult<Vec<RoleName>>;
+
+    /// Returns the filter and the requirements of a role.
+    #[method(name = "role")]
+    fn role(
+        &self,
+        guild_name: GuildName,
+        role_name: RoleName,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Option<Role>>;
+
+    /// Checks whether the account is a member of the role.
+    #[method(name = "isMember")]
+    fn is_member(
+        &self,
+        guild_name: GuildName,
+        role_name: RoleName,
+        account: AccountId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<bool>;
+
+    /// Returns at most `page_size` members of a role, starting after the
+    /// `start` account.
+    #[method(name = "members")]
+    fn members(
+        &self,
+        guild_name: GuildName,
+        role_name: RoleName,
+        start: Option<AccountId>,
+        page_size: u32,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<AccountId>>;
+
+    /// Returns the registered identities of an account with their indices.
+    #[method(name = "identities")]
+    fn identities(
+        &self,
+        account: AccountId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<(u8, Identity)>>;
+}
+
+/// Implements the guild RPC methods.
+pub struct Guild<C, Block> {
+    client: Arc<C>,
+    _marker: PhantomData<Block>,
+}
+
+impl<C, Block> Guild<C, Block> {
+    /// Creates a new instance of the guild RPC handler.
+    pub fn new(client: Arc<C>) -> Self {
+        Self {
+            client,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<C, Block> Guild<C, Block>
+where
+    Block: BlockT,
+    C: HeaderBackend<Block>,
+{
+    fn at_or_best(&self, at: Option<Block::Hash>) -> Block::Hash {
+        at.unwrap_or_else(|| self.client.info().best_hash)
+    }
+}
+
+impl<C, Block> GuildApiServer<<Block as BlockT>::Hash> for Guild<C, Block>
+where
+    Block: BlockT,
+    C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+    C::Api: GuildRuntimeApi<Block, AccountId>,
+{
+    fn guild(
+        &self,
+        guild_name: GuildName,
+        at: Option<Block::Hash>,
+    ) -> RpcResult<Option<GuildData<AccountId>>> {
+        self.client
+            .runtime_api()
+            .guild(self.at_or_best(at), guild_name)
+            .map_err(runtime_error)
+    }
+
+    fn roles(&self, guild_name: GuildName, at: Option<Block::Hash>) -> RpcResult<Vec<RoleName>> {
+        self.client
+            .runtime_api()
+            .roles(self.at_or_best(at), guild_name)
+            .map_err(runtime_error)
+    }
+
+    fn role(
+        &self,
+        guild_name: GuildName,
+        role_name: RoleName,
+        at: Option<Block::Hash>,
+    ) -> RpcResult<Option<Role>> {
+        self.client
+            .runtime_api()
+            .role(self.at_or_best(at), guild_name, role_name)
+            .map_err(runtime_error)
+    }
+
+    fn is_member(
+        &self,
+        guild_name: GuildName,
+        role_name: RoleName,
+        account: AccountId,
+        at: Option<Block::Hash>,
+    ) -> RpcResult<bool> {
+        self.client
+            .runtime_api()
+            .is_member(self.at_or_best(at), guild_name, role_name, account)
+            .map_err(runtime_error)
+    }
+
+    fn members(
+        &self,
+        guild_name: GuildName,
+        role_name: RoleName,
+        start: Option<AccountId>,
+        page_size: u32,
+        at: Option<Block::Hash>,
+    ) -> RpcResult<Vec<AccountId>> {
+        self.client
+            .runtime_api()
+            .members(
+                self.at_or_best(at),
+                guild_name,
+                role_name,
+                start,
+                page_size.min(MAX_PAGE_SIZE),
+            )
+            .map_err(runtime_error)
+    }
+
+    fn identities(
+        &self,
+        account: AccountId,
+        at: Option<Block::Hash>,
+    ) -> RpcResult<Vec<(u8, Identity)>> {
+        self.client
+            .runtime_api()
+            .identities(self.at_or_best(at), account)
+            .map_err(runtime_error)
+    }
+}
+
+fn runtime_error(error: sp_api::ApiError) -> JsonRpseeError {
+    CallError::Custom(ErrorObject::owned(
+        RUNTIME_ERROR,
+        "Unable to query the guild state.",
+        Some(error.to_string()),
+    ))
+    .into()
+}
@@ -2,23 +2,31 @@ use super::*;
 use crate::Pallet as Guild;
 
 use frame_benchmarking::{account, benchmarks, whitelisted_caller};
-use frame_support::traits::Get;
+use frame_support::sp_runtime::traits::{Bounded, One, UniqueSaturatedInto, Zero};
+use frame_support::traits::{Currency, Get, Hooks};
+use frame_support::BoundedVec;
 use frame_system::RawOrigin;
-use gn_common::filter::{Guild as GuildFilter, Logic as FilterLogic};
+use gn_common::filter::{
+    Composite as CompositeFilter, Filter, Guild as GuildFilter, Logic as FilterLogic,
+};
+use gn_common::identity::siwe::SiweMessage;
 use gn_common::identity::*;
 use gn_common::merkle::Proof as MerkleProof;
-use sp_std::vec;
+use sp_std::{vec, vec::Vec};
 
 const ACCOUNT: &str = "account";
+const BANNED: &str = "banned";
 const SEED: u32 = 999;
+const MAX_MEMBERS: u32 = 1000;
 
 benchmarks! {
     register {
         let caller: T::AccountId = whitelisted_caller();
+        fund_account::<T>(&caller);
         let (identity, signature) = id_with_auth::<T>(&caller);
         let identity_with_auth = IdentityWithAuth::Ecdsa(identity, signature);
         let index = 1;
-    }: _(RawOrigin::Signed(caller.clone()), identity_with_auth, index)
+    }: _(RawOrigin::Signed(caller.clone()), identity_with_auth, index, expires_at::<T>())
     verify {
         assert_eq!(Guild::<T>::user_data(caller, index), Some(identity));
     }
@@ -26,6 +34,7 @@ benchmarks! {
         let n in 0 .. <T as Config>::MaxSerializedLen::get();
 
         let caller: T::AccountId = whitelisted_caller();
+        fund_account::<T>(&caller);
         let guild_name = [0u8; 32];
         let metadata = vec![0u8; n as usize];
     }: _(RawOrigin::Signed(caller), guild_name, metadata)
@@ -113,12 +122,14 @@ benchmarks! {
 
         // identity
         let caller: T::AccountId = whitelisted_caller();
+        fund_account::<T>(&caller);
         let (identity, signature) = id_with_auth::<T>(&caller);
         let identity_with_auth = IdentityWithAuth::Ecdsa(identity, signature);
         Guild::<T>::register(
             RawOrigin::Signed(caller.clone()).into(),
             identity_with_auth,
             0,
+            expires_at::<T>(),
         ).unwrap();
 
         // guild
@@ -137,6 +148,27 @@ benchmarks! {
             None,
         ).unwrap();
 
+        // NOTE the membership expires, and every block its expiry can be
+        // postponed to is full, except for the last one
+        let validity = <T as frame_system::Config>::BlockNumber::from(100u32);
+        Guild::<T>::set_role_validity(
+            RawOrigin::Signed(caller.clone()).into(),
+            guild_name,
+            role_name,
+            Some(validity),
+        ).unwrap();
+        let granted_at = frame_system::Pallet::<T>::block_number();
+        let expiries: BoundedVec<_, T::MaxExpiriesPerBlock> =
+            (0..<T as Config>::MaxExpiriesPerBlock::get())
+                .map(|i| (guild_name, role_name, account(ACCOUNT, i, SEED), granted_at))
+                .collect::<Vec<_>>()
+                .try_into()
+                .unwrap();
+        for delay in 0..<T as Config>::MaxScheduleDelay::get() {
+            let block = granted_at + validity + <T as frame_system::Config>::BlockNumber::from(delay);
+            MembershipExpiries::<T>::insert(block, expiries.clone());
+        }
+
         // proof to the last element
         let proof = MerkleProof::new(&allowlist, n - 1, 0);
 
@@ -149,6 +181,7 @@ benchmarks! {
 
     leave {
         let caller: T::AccountId = whitelisted_caller();
+        fund_account::<T>(&caller);
         let (identity, signature) = id_with_auth::<T>(&caller);
         let identity_with_auth = IdentityWithAuth::Ecdsa(identity, signature);
 
@@ -156,6 +189,7 @@ benchmarks! {
             RawOrigin::Signed(caller.clone()).into(),
             identity_with_auth,
             0,
+            expires_at::<T>(),
         ).unwrap();
 
         let guild_name = [0u8; 32];
@@ -185,6 +219,7 @@ benchmarks! {
         let s = <T as Config>::MaxSerializedLen::get() as usize;
 
         let caller: T::AccountId = whitelisted_caller();
+        fund_account::<T>(&caller);
         let keeper: T::AccountId = account(ACCOUNT, 123, SEED);
         let operator: T::AccountId = account(ACCOUNT, 222, SEED);
         let (identity, signature) = id_with_auth::<T>(&caller);
@@ -201,6 +236,7 @@ benchmarks! {
             RawOrigin::Signed(caller.clone()).into(),
             identity_with_auth,
             0,
+            expires_at::<T>(),
         ).unwrap();
 
         let guild_name = [0u8; 32];
@@ -238,10 +274,615 @@ benchmarks! {
         assert!(Guild::<T>::member(role_id, caller).is_some());
     }
 
+    transfer_guild_ownership {
+        let caller: T::AccountId = whitelisted_caller();
+        let new_owner: T::AccountId = account(ACCOUNT, 123, SEED);
+        let guild_name = [0u8; 32];
+        init_guild::<T>(&caller, guild_name);
+    }: _(RawOrigin::Signed(caller), guild_name, new_owner.clone())
+    verify {
+        let guild_id = Guild::<T>::guild_id(guild_name).unwrap();
+        assert_eq!(Guild::<T>::pending_owner(guild_id), Some(new_owner));
+    }
+
+    accept_guild_ownership {
+        let caller: T::AccountId = whitelisted_caller();
+        let new_owner: T::AccountId = account(ACCOUNT, 123, SEED);
+        let guild_name = [0u8; 32];
+        init_guild::<T>(&caller, guild_name);
+        Guild::<T>::transfer_guild_ownership(
+            RawOrigin::Signed(caller).into(),
+            guild_name,
+            new_owner.clone(),
+        ).unwrap();
+    }: _(RawOrigin::Signed(new_owner.clone()), guild_name)
+    verify {
+        let guild_id = Guild::<T>::guild_id(guild_name).unwrap();
+        assert_eq!(Guild::<T>::guild(guild_id).unwrap().owner, new_owner);
+        assert!(Guild::<T>::pending_owner(guild_id).is_none());
+    }
+
+    delete_role {
+        let n in 0 .. MAX_MEMBERS;
+
+        let caller: T::AccountId = whitelisted_caller();
+        let guild_name = [0u8; 32];
+        let role_name = [0u8; 32];
+        init_guild::<T>(&caller, guild_name);
+        let role_id = init_role_with_members::<T>(&caller, guild_name, role_name, n);
+    }: _(RawOrigin::Signed(caller), guild_name, role_name, n)
+    verify {
+        let guild_id = Guild::<T>::guild_id(guild_name).unwrap();
+        assert!(Guild::<T>::role_id(guild_id, role_name).is_none());
+        assert!(Guild::<T>::role(role_id).is_none());
+    }
+
+    delete_guild {
+        let r in 0 .. <T as Config>::MaxRolesPerGuild::get();
+        let n in 0 .. MAX_MEMBERS;
+
+        let caller: T::AccountId = whitelisted_caller();
+        let guild_name = [0u8; 32];
+        init_guild::<T>(&caller, guild_name);
+        // distribute members evenly among the roles
+        for i in 0..r {
+            let role_name = [i as u8; 32];
+            let members = n / r + u32::from(i < n % r);
+            init_role_with_members::<T>(&caller, guild_name, role_name, members);
+        }
+    }: _(RawOrigin::Signed(caller), guild_name, n)
+    verify {
+        assert!(Guild::<T>::guild_id(guild_name).is_none());
+    }
+    update_role_requirements {
+        let r in 0 .. <T as Config>::MaxReqsPerRole::get();
+        let s in 0 .. <T as Config>::MaxSerializedLen::get();
+
+        let caller: T::AccountId = whitelisted_caller();
+        let guild_name = [0u8; 32];
+        let role_name = [0u8; 32];
+        init_guild::<T>(&caller, guild_name);
+        Guild::<T>::create_free_role(
+            RawOrigin::Signed(caller.clone()).into(),
+            guild_name,
+            role_name,
+        ).unwrap();
+
+        let logic = vec![100u8; s as usize];
+        let req = vec![200u8; s as usize];
+        let serialized_requirements = (vec![req; r as usize], logic);
+    }: _(RawOrigin::Signed(caller), guild_name, role_name, Some(serialized_requirements))
+    verify {
+        let guild_id = Guild::<T>::guild_id(guild_name).unwrap();
+        let role_id = Guild::<T>::role_id(guild_id, role_name).unwrap();
+        assert_eq!(Guild::<T>::role_version(role_id), 1);
+    }
+
+    update_role_filter {
+        let n in 1 .. <T as Config>::MaxCompositeFilterLen::get();
+
+        let caller: T::AccountId = whitelisted_caller();
+        let guild_name = [0u8; 32];
+        let role_name = [0u8; 32];
+        init_guild::<T>(&caller, guild_name);
+        // NOTE the role is filtered by the largest possible allowlist, which
+        // is cleared from the offchain storage
+        let max_leaves = <T as Config>::MaxAllowlistLen::get()
+            .saturating_mul(<T as Config>::MaxAllowlistChunks::get());
+        Guild::<T>::create_role_with_allowlist_root(
+            RawOrigin::Signed(caller.clone()).into(),
+            guild_name,
+            role_name,
+            Default::default(),
+            max_leaves,
+            FilterLogic::And,
+            None,
+        ).unwrap();
+        let guilds = (1..=n)
+            .map(|i| {
+                let parent_role_name = [i as u8; 32];
+                init_role_with_members::<T>(&caller, guild_name, parent_role_name, 0);
+                GuildFilter {
+                    name: guild_name,
+                    role: Some(parent_role_name),
+                }
+            })
+            .collect::<Vec<_>>();
+        let filter = Filter::Composite(
+            CompositeFilter {
+                guilds,
+                allowlist: None,
+                logic: FilterLogic::Or,
+            },
+            FilterLogic::And,
+        );
+    }: _(RawOrigin::Signed(caller), guild_name, role_name, Some(filter))
+    verify {
+        let guild_id = Guild::<T>::guild_id(guild_name).unwrap();
+        let role_id = Guild::<T>::role_id(guild_id, role_name).unwrap();
+        assert_eq!(Guild::<T>::role_version(role_id), 1);
+    }
+    add_admin {
+        let n = <T as Config>::MaxAdminsPerGuild::get();
+
+        let caller: T::AccountId = whitelisted_caller();
+        let guild_name = [0u8; 32];
+        init_guild::<T>(&caller, guild_name);
+        init_admins::<T>(&caller, guild_name, n - 1);
+        let admin: T::AccountId = account(ACCOUNT, n - 1, SEED);
+    }: _(RawOrigin::Signed(caller), guild_name, admin.clone())
+    verify {
+        let guild_id = Guild::<T>::guild_id(guild_name).unwrap();
+        assert!(Guild::<T>::admins(guild_id).contains(&admin));
+    }
+
+    remove_admin {
+        let n = <T as Config>::MaxAdminsPerGuild::get();
+
+        let caller: T::AccountId = whitelisted_caller();
+        let guild_name = [0u8; 32];
+        init_guild::<T>(&caller, guild_name);
+        init_admins::<T>(&caller, guild_name, n);
+        let admin: T::AccountId = account(ACCOUNT, n - 1, SEED);
+    }: _(RawOrigin::Signed(caller), guild_name, admin.clone())
+    verify {
+        let guild_id = Guild::<T>::guild_id(guild_name).unwrap();
+        assert!(!Guild::<T>::admins(guild_id).contains(&admin));
+    }
+    update_guild_metadata {
+        let n in 0 .. <T as Config>::MaxSerializedLen::get();
+
+        let caller: T::AccountId = whitelisted_caller();
+        let guild_name = [0u8; 32];
+        init_guild::<T>(&caller, guild_name);
+        let metadata = vec![1u8; n as usize];
+    }: _(RawOrigin::Signed(caller), guild_name, metadata.clone())
+    verify {
+        let guild_id = Guild::<T>::guild_id(guild_name).unwrap();
+        assert_eq!(Guild::<T>::guild(guild_id).unwrap().metadata.to_vec(), metadata);
+    }
+    unregister {
+        let r in 0 .. <T as Config>::MaxRolesPerAccount::get();
+
+        let caller: T::AccountId = whitelisted_caller();
+        fund_account::<T>(&caller);
+        let (identity, signature) = id_with_auth::<T>(&caller);
+        let identity_with_auth = IdentityWithAuth::Ecdsa(identity, signature);
+        let index = 1;
+        Guild::<T>::register(
+            RawOrigin::Signed(caller.clone()).into(),
+            identity_with_auth,
+            index,
+            expires_at::<T>(),
+        ).unwrap();
+
+        // NOTE every membership has been obtained via the allowlist, so all
+        // of them are revoked
+        let roles_per_guild = <T as Config>::MaxRolesPerGuild::get();
+        for i in 0..r {
+            let guild_name = [(i / roles_per_guild) as u8; 32];
+            let role_name = [(i % roles_per_guild) as u8; 32];
+            if i % roles_per_guild == 0 {
+                init_guild::<T>(&caller, guild_name);
+            }
+            Guild::<T>::create_role_with_allowlist(
+                RawOrigin::Signed(caller.clone()).into(),
+                guild_name,
+                role_name,
+                vec![identity],
+                FilterLogic::And,
+                None,
+            ).unwrap();
+            Guild::<T>::join(
+                RawOrigin::Signed(caller.clone()).into(),
+                guild_name,
+                role_name,
+                Some(MerkleProof::new(&[identity], 0, index)),
+            ).unwrap();
+        }
+    }: _(RawOrigin::Signed(caller.clone()), index)
+    verify {
+        assert!(Guild::<T>::user_data(&caller, index).is_none());
+        assert!(Guild::<T>::account_roles(caller).is_empty());
+    }
+
+
+    set_role_validity {
+        let caller: T::AccountId = whitelisted_caller();
+        let guild_name = [0u8; 32];
+        let role_name = [0u8; 32];
+        init_guild::<T>(&caller, guild_name);
+        Guild::<T>::create_free_role(
+            RawOrigin::Signed(caller.clone()).into(),
+            guild_name,
+            role_name,
+        ).unwrap();
+        let validity = <T as frame_system::Config>::BlockNumber::from(100u32);
+    }: _(RawOrigin::Signed(caller), guild_name, role_name, Some(validity))
+    verify {
+        let guild_id = Guild::<T>::guild_id(guild_name).unwrap();
+        let role_id = Guild::<T>::role_id(guild_id, role_name).unwrap();
+        assert_eq!(Guild::<T>::role_validity(role_id), Some(validity));
+    }
+
+    expire_memberships {
+        let n in 0 .. <T as Config>::MaxExpiriesPerBlock::get();
+
+        let caller: T::AccountId = whitelisted_caller();
+        let guild_name = [0u8; 32];
+        let role_name = [0u8; 32];
+        init_guild::<T>(&caller, guild_name);
+        let role_id = init_role_with_members::<T>(&caller, guild_name, role_name, n);
+
+        let granted_at = frame_system::Pallet::<T>::block_number();
+        let expiry = granted_at + <T as frame_system::Config>::BlockNumber::from(100u32);
+        let expiries: BoundedVec<_, T::MaxExpiriesPerBlock> = (0..n)
+            .map(|i| (guild_name, role_name, account(ACCOUNT, i, SEED), granted_at))
+            .collect::<Vec<_>>()
+            .try_into()
+            .unwrap();
+        MembershipExpiries::<T>::insert(expiry, expiries);
+    }: {
+        Guild::<T>::on_initialize(expiry);
+    }
+    verify {
+        for i in 0..n {
+            let member: T::AccountId = account(ACCOUNT, i, SEED);
+            assert!(Guild::<T>::member(role_id, member).is_none());
+        }
+    }
+
+
+    reverify_members {
+        let n in 0 .. <T as Config>::MaxReverificationsPerBlock::get();
+
+        let r = <T as Config>::MaxReqsPerRole::get() as usize;
+        let s = <T as Config>::MaxSerializedLen::get() as usize;
+
+        let caller: T::AccountId = whitelisted_caller();
+        let operator: T::AccountId = account(ACCOUNT, 222, SEED);
+        pallet_oracle::Pallet::<T>::register_operator(
+            RawOrigin::Root.into(),
+            operator.clone()
+        ).unwrap();
+        pallet_oracle::Pallet::<T>::activate_operator(
+            RawOrigin::Signed(operator).into(),
+        ).unwrap();
+
+        let guild_name = [0u8; 32];
+        let role_name = [0u8; 32];
+        init_guild::<T>(&caller, guild_name);
+
+        let logic = vec![100u8; s];
+        let req = vec![200u8; s];
+        let serialized_requirements = (vec![req; r], logic);
+        Guild::<T>::create_unfiltered_role(
+            RawOrigin::Signed(caller).into(),
+            guild_name,
+            role_name,
+            serialized_requirements,
+        ).unwrap();
+        let guild_id = Guild::<T>::guild_id(guild_name).unwrap();
+        let role_id = Guild::<T>::role_id(guild_id, role_name).unwrap();
+
+        fund_account::<T>(&Guild::<T>::account_id());
+        let granted_at = frame_system::Pallet::<T>::block_number();
+        let now = granted_at + <T as Config>::ReverificationPeriod::get();
+        let checks: BoundedVec<_, T::MaxReverificationsPerBlock> = (0..n)
+            .map(|i| {
+                let member: T::AccountId = account(ACCOUNT, i, SEED);
+                Members::<T>::insert(role_id, &member, granted_at);
+                ScheduledReverifications::<T>::insert((guild_name, role_name), &member, now);
+                (guild_name, role_name, member, granted_at)
+            })
+            .collect::<Vec<_>>()
+            .try_into()
+            .unwrap();
+        ReverificationQueue::<T>::insert(now, checks);
+    }: {
+        Guild::<T>::on_initialize(now);
+    }
+    verify {
+        let next_check = now + <T as Config>::ReverificationPeriod::get();
+        assert!(Guild::<T>::reverification_queue(now).is_empty());
+        assert_eq!(Guild::<T>::reverification_queue(next_check).len(), n as usize);
+    }
+
+
+    kick_member {
+        let n in 0 .. <T as Config>::MaxSerializedLen::get();
+
+        let caller: T::AccountId = whitelisted_caller();
+        let guild_name = [0u8; 32];
+        let role_name = [0u8; 32];
+        init_guild::<T>(&caller, guild_name);
+        let role_id = init_role_with_members::<T>(&caller, guild_name, role_name, 1);
+        let member: T::AccountId = account(ACCOUNT, 0, SEED);
+        let reason = vec![1u8; n as usize];
+    }: _(RawOrigin::Signed(caller), guild_name, role_name, member.clone(), reason)
+    verify {
+        assert!(Guild::<T>::member(role_id, member).is_none());
+    }
+
+    ban_account {
+        let r in 0 .. <T as Config>::MaxRolesPerGuild::get();
+        let n in 0 .. <T as Config>::MaxSerializedLen::get();
+
+        let caller: T::AccountId = whitelisted_caller();
+        let guild_name = [0u8; 32];
+        init_guild::<T>(&caller, guild_name);
+        let role_ids = (0..r)
+            .map(|i| init_role_with_members::<T>(&caller, guild_name, [i as u8; 32], 1))
+            .collect::<Vec<_>>();
+        init_bans::<T>(guild_name, <T as Config>::MaxBansPerGuild::get() - 1);
+        let member: T::AccountId = account(ACCOUNT, 0, SEED);
+        let reason = vec![1u8; n as usize];
+    }: _(RawOrigin::Signed(caller), guild_name, member.clone(), reason)
+    verify {
+        let guild_id = Guild::<T>::guild_id(guild_name).unwrap();
+        assert!(Guild::<T>::bans(guild_id).contains(&member));
+        for role_id in role_ids {
+            assert!(Guild::<T>::member(role_id, &member).is_none());
+        }
+    }
+
+    unban_account {
+        let n = <T as Config>::MaxBansPerGuild::get();
+
+        let caller: T::AccountId = whitelisted_caller();
+        let guild_name = [0u8; 32];
+        init_guild::<T>(&caller, guild_name);
+        init_bans::<T>(guild_name, n);
+        let banned: T::AccountId = account(BANNED, n - 1, SEED);
+    }: _(RawOrigin::Signed(caller), guild_name, banned.clone())
+    verify {
+        let guild_id = Guild::<T>::guild_id(guild_name).unwrap();
+        assert!(!Guild::<T>::bans(guild_id).contains(&banned));
+    }
+
+
+    update_allowlist {
+        let n in 1 .. <T as Config>::MaxAllowlistLen::get();
+        let m in 0 .. MAX_MEMBERS;
+
+        let caller: T::AccountId = whitelisted_caller();
+        let guild_name = [0u8; 32];
+        let role_name = [0u8; 32];
+        init_guild::<T>(&caller, guild_name);
+        let role_id = init_allowlist_role::<T>(&caller, guild_name, role_name, 1);
+        // every member is stripped because none of them is on the new list
+        for i in 0..m {
+            let member: T::AccountId = account(ACCOUNT, i, SEED);
+            UserData::<T>::insert(&member, 0, Identity::Other([1u8; 64]));
+            Members::<T>::insert(role_id, member, frame_system::Pallet::<T>::block_number());
+        }
+        let allowlist = vec![Identity::Other([0u8; 64]); n as usize];
+    }: _(RawOrigin::Signed(caller), guild_name, role_name, allowlist.clone(), Some(m))
+    verify {
+        assert_eq!(
+            Guild::<T>::role(role_id).unwrap().filter,
+            Some(Filter::allowlist(&allowlist, FilterLogic::And))
+        );
+        assert_eq!(Members::<T>::iter_key_prefix(role_id).count(), 0);
+    }
+
+    extend_allowlist {
+        let n in 2 .. <T as Config>::MaxAllowlistLen::get();
+
+        let caller: T::AccountId = whitelisted_caller();
+        let guild_name = [0u8; 32];
+        let role_name = [0u8; 32];
+        init_guild::<T>(&caller, guild_name);
+        let role_id = init_allowlist_role::<T>(&caller, guild_name, role_name, n - 1);
+        let allowlist = vec![Identity::Other([0u8; 64]); n as usize - 1];
+        let additions = vec![Identity::Other([1u8; 64])];
+    }: _(RawOrigin::Signed(caller), guild_name, role_name, allowlist.clone(), additions.clone())
+    verify {
+        let extended = [allowlist, additions].concat();
+        assert_eq!(
+            Guild::<T>::role(role_id).unwrap().filter,
+            Some(Filter::allowlist(&extended, FilterLogic::And))
+        );
+    }
+
+
+    create_role_with_allowlist_root {
+        let r in 0 .. <T as Config>::MaxReqsPerRole::get();
+        let s in 0 .. <T as Config>::MaxSerializedLen::get();
+
+        let caller: T::AccountId = whitelisted_caller();
+        let guild_name = [0u8; 32];
+        let role_name = [0u8; 32];
+        init_guild::<T>(&caller, guild_name);
+
+        let n_leaves = <T as Config>::MaxAllowlistLen::get()
+            * <T as Config>::MaxAllowlistChunks::get();
+        let logic = vec![100u8; s as usize];
+        let req = vec![200u8; s as usize];
+        let serialized_requirements = (vec![req; r as usize], logic);
+    }: _(RawOrigin::Signed(caller), guild_name, role_name, [1u8; 32], n_leaves, FilterLogic::And, Some(serialized_requirements))
+    verify {
+        let guild_id = Guild::<T>::guild_id(guild_name).unwrap();
+        assert!(Guild::<T>::role_id(guild_id, role_name).is_some());
+    }
+
+    upload_allowlist_chunk {
+        let n in 1 .. <T as Config>::MaxAllowlistLen::get();
+
+        let caller: T::AccountId = whitelisted_caller();
+        let guild_name = [0u8; 32];
+        let role_name = [0u8; 32];
+        init_guild::<T>(&caller, guild_name);
+        let chunk = vec![Identity::Other([0u8; 64]); n as usize];
+        let root = match Filter::<Vec<GuildFilter>>::allowlist(&chunk, FilterLogic::And) {
+            Filter::Allowlist(root, _, _) => root,
+            _ => unreachable!(),
+        };
+        Guild::<T>::create_role_with_allowlist_root(
+            RawOrigin::Signed(caller.clone()).into(),
+            guild_name,
+            role_name,
+            root,
+            n,
+            FilterLogic::And,
+            None,
+        ).unwrap();
+        let proof = MerkleProof::chunk(&chunk, 0, <T as Config>::MaxAllowlistLen::get() as usize);
+    }: _(RawOrigin::Signed(caller), guild_name, role_name, 0, chunk, proof)
+    verify {
+        let guild_id = Guild::<T>::guild_id(guild_name).unwrap();
+        let role_id = Guild::<T>::role_id(guild_id, role_name).unwrap();
+        assert_eq!(
+            Guild::<T>::role(role_id).unwrap().filter,
+            Some(Filter::Allowlist(root, FilterLogic::And, n))
+        );
+    }
+
+
+    create_role_with_composite_filter {
+        let n in 1 .. <T as Config>::MaxCompositeFilterLen::get();
+        let r in 0 .. <T as Config>::MaxReqsPerRole::get();
+        let s in 0 .. <T as Config>::MaxSerializedLen::get();
+
+        let caller: T::AccountId = whitelisted_caller();
+        let guild_name = [0u8; 32];
+        let role_name = [0u8; 32];
+        init_guild::<T>(&caller, guild_name);
+        let guilds = (1..=n)
+            .map(|i| {
+                let parent_role_name = [i as u8; 32];
+                init_role_with_members::<T>(&caller, guild_name, parent_role_name, 0);
+                GuildFilter {
+                    name: guild_name,
+                    role: Some(parent_role_name),
+                }
+            })
+            .collect::<Vec<_>>();
+        let filter = CompositeFilter {
+            guilds,
+            allowlist: None,
+            logic: FilterLogic::Or,
+        };
+
+        let logic = vec![100u8; s as usize];
+        let req = vec![200u8; s as usize];
+        let serialized_requirements = (vec![req; r as usize], logic);
+    }: _(RawOrigin::Signed(caller), guild_name, role_name, filter, FilterLogic::And, Some(serialized_requirements))
+    verify {
+        let guild_id = Guild::<T>::guild_id(guild_name).unwrap();
+        assert!(Guild::<T>::role_id(guild_id, role_name).is_some());
+    }
+
+
+    revoke_child_roles {
+        let n in 0 .. <T as Config>::MaxChildChecksPerBlock::get();
+
+        let caller: T::AccountId = whitelisted_caller();
+        let guild_name = [0u8; 32];
+        let parent_role_name = [0u8; 32];
+        let role_name = [1u8; 32];
+        init_guild::<T>(&caller, guild_name);
+        init_role_with_members::<T>(&caller, guild_name, parent_role_name, 0);
+        let parent = GuildFilter {
+            name: guild_name,
+            role: Some(parent_role_name),
+        };
+        Guild::<T>::create_child_role(
+            RawOrigin::Signed(caller).into(),
+            guild_name,
+            role_name,
+            parent,
+            FilterLogic::And,
+            None,
+        )
+        .unwrap();
+        let guild_id = Guild::<T>::guild_id(guild_name).unwrap();
+        let role_id = Guild::<T>::role_id(guild_id, role_name).unwrap();
+        let now = frame_system::Pallet::<T>::block_number();
+        for i in 0..n {
+            let member: T::AccountId = account(ACCOUNT, i, SEED);
+            Members::<T>::insert(role_id, &member, now);
+            Guild::<T>::queue_child_role_check((guild_name, role_name, member));
+        }
+    }: {
+        Guild::<T>::on_initialize(now);
+    }
+    verify {
+        for i in 0..n {
+            let member: T::AccountId = account(ACCOUNT, i, SEED);
+            assert!(Guild::<T>::member(role_id, member).is_none());
+        }
+        assert_eq!(Guild::<T>::pending_child_role_checks(), 0);
+    }
+
+
+    join_many {
+        let n in 1 .. <T as Config>::MaxJoinBatchLen::get();
+
+        let caller: T::AccountId = whitelisted_caller();
+        fund_account::<T>(&caller);
+        let (identity, signature) = id_with_auth::<T>(&caller);
+        let identity_with_auth = IdentityWithAuth::Ecdsa(identity, signature);
+        Guild::<T>::register(
+            RawOrigin::Signed(caller.clone()).into(),
+            identity_with_auth,
+            0,
+            expires_at::<T>(),
+        ).unwrap();
+
+        // NOTE every role is created in a separate guild, so the batch is
+        // not limited by the number of roles per guild
+        let role_name = [0u8; 32];
+        let mut roles = Vec::new();
+        for i in 0..n {
+            let guild_name = [i as u8; 32];
+            init_guild::<T>(&caller, guild_name);
+            Guild::<T>::create_free_role(
+                RawOrigin::Signed(caller.clone()).into(),
+                guild_name,
+                role_name,
+            ).unwrap();
+            roles.push((guild_name, role_name, None));
+        }
+        let roles: BoundedVec<_, T::MaxJoinBatchLen> = roles.try_into().unwrap();
+    }: _(RawOrigin::Signed(caller.clone()), roles)
+    verify {
+        assert_eq!(Guild::<T>::account_roles(caller).len() as u32, n);
+    }
+
+    register_siwe {
+        let caller: T::AccountId = whitelisted_caller();
+        fund_account::<T>(&caller);
+        let (identity, message, signature) = siwe_id_with_auth::<T>(&caller);
+        let index = 1;
+    }: _(RawOrigin::Signed(caller.clone()), message, signature, index, expires_at::<T>())
+    verify {
+        assert_eq!(Guild::<T>::user_data(caller, index), Some(identity));
+    }
+
+    prune_free_calls {
+        let n in 0 .. <T as Config>::MaxFreeCallPrunesPerBlock::get();
+
+        let window = <T as Config>::FreeCallWindow::get();
+        let expired = Guild::<T>::free_call_window(window) - One::one();
+        for i in 0..n {
+            let member: T::AccountId = account(ACCOUNT, i, SEED);
+            FreeCalls::<T>::insert(expired, member, 1);
+        }
+        OldestFreeCallWindow::<T>::put(expired);
+    }: {
+        Guild::<T>::on_initialize(window);
+    }
+    verify {
+        assert!(!FreeCalls::<T>::contains_prefix(expired));
+        assert_eq!(Guild::<T>::oldest_free_call_window(), Some(expired + One::one()));
+    }
+
     impl_benchmark_test_suite!(Guild, crate::mock::new_test_ext(), crate::mock::TestRuntime, extra = false);
 }
 
 fn init_guild<T: Config>(caller: &T::AccountId, guild_name: [u8; 32]) {
+    fund_account::<T>(caller);
     frame_system::Pallet::<T>::set_block_number(<T as frame_system::Config>::BlockNumber::from(
         1u32,
     ));
@@ -254,7 +895,116 @@ fn init_guild<T: Config>(caller: &T::AccountId, guild_name: [u8; 32]) {
     .unwrap();
 }
 
+fn init_role_with_members<T: Config>(
+    caller: &T::AccountId,
+    guild_name: [u8; 32],
+    role_name: [u8; 32],
+    n: u32,
+) -> T::Hash {
+    Guild::<T>::create_free_role(
+        RawOrigin::Signed(caller.clone()).into(),
+        guild_name,
+        role_name,
+    )
+    .unwrap();
+    let guild_id = Guild::<T>::guild_id(guild_name).unwrap();
+    let role_id = Guild::<T>::role_id(guild_id, role_name).unwrap();
+    for i in 0..n {
+        let member: T::AccountId = account(ACCOUNT, i, SEED);
+        Members::<T>::insert(role_id, member, frame_system::Pallet::<T>::block_number());
+    }
+    role_id
+}
+
+fn init_allowlist_role<T: Config>(
+    caller: &T::AccountId,
+    guild_name: [u8; 32],
+    role_name: [u8; 32],
+    n: u32,
+) -> T::Hash {
+    Guild::<T>::create_role_with_allowlist(
+        RawOrigin::Signed(caller.clone()).into(),
+        guild_name,
+        role_name,
+        vec![Identity::Other([0u8; 64]); n as usize],
+        FilterLogic::And,
+        None,
+    )
+    .unwrap();
+    let guild_id = Guild::<T>::guild_id(guild_name).unwrap();
+    Guild::<T>::role_id(guild_id, role_name).unwrap()
+}
+
+fn init_admins<T: Config>(caller: &T::AccountId, guild_name: [u8; 32], n: u32) {
+    for i in 0..n {
+        let admin: T::AccountId = account(ACCOUNT, i, SEED);
+        Guild::<T>::add_admin(RawOrigin::Signed(caller.clone()).into(), guild_name, admin).unwrap();
+    }
+}
+
+fn init_bans<T: Config>(guild_name: [u8; 32], n: u32) {
+    let guild_id = Guild::<T>::guild_id(guild_name).unwrap();
+    let bans: BoundedVec<T::AccountId, T::MaxBansPerGuild> = (0..n)
+        .map(|i| account(BANNED, i, SEED))
+        .collect::<Vec<_>>()
+        .try_into()
+        .unwrap();
+    Bans::<T>::insert(guild_id, bans);
+}
+
+fn fund_account<T: Config>(account: &T::AccountId) {
+    let balance = BalanceOf::<T>::max_value() / 2u32.into();
+    <T as pallet_oracle::Config>::Currency::make_free_balance_be(account, balance);
+}
+
+fn expires_at<T: Config>() -> T::BlockNumber {
+    T::BlockNumber::max_value()
+}
+
 fn id_with_auth<T: Config>(caller: &T::AccountId) -> (Identity, EcdsaSignature) {
     let seed = [2u8; 32];
-    gn_common::identity::test_ecdsa_id_with_auth(seed, gn_common::utils::verification_msg(caller))
+    let genesis_hash = frame_system::Pallet::<T>::block_hash(T::BlockNumber::zero());
+    let msg = gn_common::utils::verification_msg(
+        caller,
+        genesis_hash,
+        Guild::<T>::registration_nonce(caller),
+        expires_at::<T>().unique_saturated_into(),
+    );
+    gn_common::identity::test_ecdsa_id_with_auth(seed, msg)
+}
+
+fn siwe_id_with_auth<T: Config>(
+    caller: &T::AccountId,
+) -> (
+    Identity,
+    BoundedVec<u8, <T as Config>::MaxSiweMessageLen>,
+    EcdsaSignature,
+) {
+    let seed = [2u8; 32];
+    let (identity, _) = gn_common::identity::test_ecdsa_id_with_auth(seed, b"");
+    let Identity::Address20(address) = identity else {
+        unreachable!()
+    };
+    let genesis_hash = frame_system::Pallet::<T>::block_hash(T::BlockNumber::zero());
+    // NOTE the optional timestamps are set, so that they are parsed too
+    let siwe = SiweMessage {
+        expiration_time: Some("2100-01-01T00:00:00.000Z".into()),
+        not_before: Some("1970-01-01T00:00:00.000Z".into()),
+        ..SiweMessage::new(
+            <T as Config>::SiweDomain::get().into(),
+            address,
+            "https://app.guild.xyz/".into(),
+            <T as Config>::SiweChainId::get(),
+            "2023-03-01T12:00:00.000Z".into(),
+        )
+    }
+    .bind_registration(
+        caller,
+        genesis_hash,
+        Guild::<T>::registration_nonce(caller),
+        expires_at::<T>().unique_saturated_into(),
+    );
+    let message = scale_info::prelude::format!("{siwe}").into_bytes();
+    let (_, signature) = gn_common::identity::test_ecdsa_id_with_auth(seed, &message);
+    (identity, message.try_into().unwrap(), signature)
 }
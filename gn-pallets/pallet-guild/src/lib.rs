@@ -7,35 +7,72 @@ pub use pallet::*;
 
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmark;
+pub mod migration;
 #[cfg(test)]
 mod mock;
 #[cfg(test)]
 mod test;
 pub mod weights;
 
+pub const LOG_TARGET: &str = "runtime::guild";
+
 #[frame_support::pallet]
 pub mod pallet {
     use super::weights::WeightInfo;
     use frame_support::traits::Randomness;
     use frame_support::{
-        dispatch::DispatchResult, pallet_prelude::*, sp_runtime::traits::UniqueSaturatedFrom,
-        traits::Currency, StorageDoubleMap as StorageDoubleMapT,
+        dispatch::DispatchResult,
+        pallet_prelude::*,
+        sp_runtime::traits::{
+            AccountIdConversion, One, Saturating, UniqueSaturatedFrom, UniqueSaturatedInto, Zero,
+        },
+        traits::{Currency, ReservableCurrency, UnixTime},
+        BoundedBTreeSet, PalletId, StorageDoubleMap as StorageDoubleMapT,
     };
     use frame_system::pallet_prelude::*;
     use gn_common::filter::{Filter, Logic as FilterLogic};
-    use gn_common::identity::{Identity, IdentityWithAuth};
+    use gn_common::identity::{siwe::SiweMessage, EcdsaSignature, Identity, IdentityWithAuth};
     use gn_common::merkle::{Leaf as MerkleLeaf, Proof as MerkleProof};
     use gn_common::{
         Guild, GuildName, Request, RequestData, RequestIdentifier, Role, RoleName, SerializedData,
         SerializedRequirements,
     };
     use pallet_oracle::{CallbackWithParameter, Config as OracleConfig, OracleAnswer};
+    use scale_info::prelude::string::String as SpString;
     use sp_std::vec::Vec as SpVec;
 
-    type BalanceOf<T> = <<T as OracleConfig>::Currency as Currency<
+    pub type BalanceOf<T> = <<T as OracleConfig>::Currency as Currency<
         <T as frame_system::Config>::AccountId,
     >>::Balance;
 
+    /// Guild name, role name, member and the block the membership was granted
+    pub type ScheduledMembership<T> = (
+        GuildName,
+        RoleName,
+        <T as frame_system::Config>::AccountId,
+        <T as frame_system::Config>::BlockNumber,
+    );
+
+    // Guild and role data as stored onchain, i.e. with their vectors bounded
+    // by the limits of the pallet
+    pub type BoundedSerializedData<T> = BoundedVec<u8, <T as Config>::MaxSerializedLen>;
+    pub type BoundedRequirements<T> = (
+        BoundedVec<BoundedSerializedData<T>, <T as Config>::MaxReqsPerRole>,
+        BoundedSerializedData<T>,
+    );
+    pub type BoundedParents<T> =
+        BoundedVec<gn_common::filter::Guild, <T as Config>::MaxCompositeFilterLen>;
+    pub type BoundedComposite<T> = gn_common::filter::Composite<BoundedParents<T>>;
+    pub type BoundedFilter<T> = Filter<BoundedParents<T>>;
+    pub type GuildOf<T> = Guild<
+        <T as frame_system::Config>::AccountId,
+        BoundedSerializedData<T>,
+        BoundedVec<RoleName, <T as Config>::MaxRolesPerGuild>,
+    >;
+    pub type RoleOf<T> = Role<BoundedFilter<T>, BoundedRequirements<T>>;
+
+    pub(crate) const STORAGE_VERSION: StorageVersion = StorageVersion::new(5);
+
     #[pallet::storage]
     #[pallet::getter(fn nonce)]
     pub type Nonce<T: Config> = StorageValue<_, u64, ValueQuery>;
@@ -61,14 +98,49 @@ pub mod pallet {
         OptionQuery,
     >;
 
+    // NOTE inverse of 'RoleIdMap', so that role ids (e.g. the ones in
+    // 'AccountRoles') can be resolved without iterating the role ids
+    #[pallet::storage]
+    #[pallet::getter(fn role_name_of)]
+    pub type RoleNameMap<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::Hash, (GuildName, RoleName), OptionQuery>;
+
     #[pallet::storage]
     #[pallet::getter(fn guild)]
-    pub type Guilds<T: Config> =
-        StorageMap<_, Blake2_128Concat, T::Hash, Guild<T::AccountId>, OptionQuery>;
+    pub type Guilds<T: Config> = StorageMap<_, Blake2_128Concat, T::Hash, GuildOf<T>, OptionQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn admins)]
+    pub type Admins<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::Hash,
+        BoundedVec<T::AccountId, T::MaxAdminsPerGuild>,
+        ValueQuery,
+    >;
+
+    #[pallet::storage]
+    #[pallet::getter(fn bans)]
+    pub type Bans<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::Hash,
+        BoundedVec<T::AccountId, T::MaxBansPerGuild>,
+        ValueQuery,
+    >;
+
+    #[pallet::storage]
+    #[pallet::getter(fn pending_owner)]
+    pub type PendingOwners<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::Hash, T::AccountId, OptionQuery>;
 
     #[pallet::storage]
     #[pallet::getter(fn role)]
-    pub type Roles<T: Config> = StorageMap<_, Blake2_128Concat, T::Hash, Role, OptionQuery>;
+    pub type Roles<T: Config> = StorageMap<_, Blake2_128Concat, T::Hash, RoleOf<T>, OptionQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn role_version)]
+    pub type RoleVersions<T: Config> = StorageMap<_, Blake2_128Concat, T::Hash, u32, ValueQuery>;
 
     #[pallet::storage]
     #[pallet::getter(fn member)]
@@ -78,7 +150,139 @@ pub mod pallet {
         T::Hash,
         Blake2_128Concat,
         T::AccountId,
-        bool,
+        T::BlockNumber, // block of the membership grant
+        OptionQuery,
+    >;
+
+    #[pallet::storage]
+    #[pallet::getter(fn registration_nonce)]
+    pub type RegistrationNonces<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, u64, ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn account_roles)]
+    pub type AccountRoles<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        BoundedBTreeSet<T::Hash, T::MaxRolesPerAccount>, // Role ids
+        ValueQuery,
+    >;
+
+    #[pallet::storage]
+    #[pallet::getter(fn member_count)]
+    pub type MemberCount<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::Hash, // Guild or role id
+        u32,
+        ValueQuery,
+    >;
+
+    #[pallet::storage]
+    #[pallet::getter(fn role_validity)]
+    pub type RoleValidity<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::Hash, T::BlockNumber, OptionQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn membership_expiries)]
+    pub type MembershipExpiries<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::BlockNumber,
+        BoundedVec<ScheduledMembership<T>, T::MaxExpiriesPerBlock>,
+        ValueQuery,
+    >;
+
+    #[pallet::storage]
+    #[pallet::getter(fn reverification_queue)]
+    pub type ReverificationQueue<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::BlockNumber,
+        BoundedVec<ScheduledMembership<T>, T::MaxReverificationsPerBlock>,
+        ValueQuery,
+    >;
+
+    // NOTE the block of the pending re-verification of a membership, so that
+    // renewed memberships don't occupy more than one slot in the queue
+    #[pallet::storage]
+    #[pallet::getter(fn scheduled_reverification)]
+    pub type ScheduledReverifications<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        (GuildName, RoleName),
+        Blake2_128Concat,
+        T::AccountId,
+        T::BlockNumber,
+        OptionQuery,
+    >;
+
+    // NOTE feeless calls are counted per rate limiting window, so that the
+    // counters of expired windows can be pruned as a whole
+    #[pallet::storage]
+    #[pallet::getter(fn free_calls)]
+    pub type FreeCalls<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        T::BlockNumber, // Window index
+        Blake2_128Concat,
+        T::AccountId,
+        u32,
+        ValueQuery,
+    >;
+
+    #[pallet::storage]
+    #[pallet::getter(fn oldest_free_call_window)]
+    pub type OldestFreeCallWindow<T: Config> = StorageValue<_, T::BlockNumber, OptionQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn child_roles)]
+    pub type ChildRoles<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::Hash, // Parent role id or guild id
+        BoundedVec<(GuildName, RoleName), T::MaxChildrenPerRole>,
+        ValueQuery,
+    >;
+
+    // NOTE the checks are queued by revocations, so their number is only
+    // bounded by the number of memberships. They are stored one by one and
+    // processed in FIFO order, so that a batch never touches the whole queue
+    #[pallet::storage]
+    #[pallet::getter(fn child_role_check)]
+    pub type ChildRoleChecks<T: Config> = StorageMap<
+        _,
+        Twox64Concat,
+        u64, // Queue index
+        (GuildName, RoleName, T::AccountId),
+        OptionQuery,
+    >;
+
+    // NOTE index of the first pending check and of the next free slot
+    #[pallet::storage]
+    #[pallet::getter(fn child_role_check_range)]
+    pub type ChildRoleCheckRange<T: Config> = StorageValue<_, (u64, u64), ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn deposit)]
+    pub type Deposits<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::Hash, // Guild or role id
+        (T::AccountId, BalanceOf<T>),
+        OptionQuery,
+    >;
+
+    #[pallet::storage]
+    #[pallet::getter(fn identity_deposit)]
+    pub type IdentityDeposits<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        u8,
+        BalanceOf<T>,
         OptionQuery,
     >;
 
@@ -94,59 +298,166 @@ pub mod pallet {
         OptionQuery,
     >;
 
+    #[pallet::storage]
+    #[pallet::getter(fn identity_owner)]
+    pub type IdentityOwners<T: Config> =
+        StorageMap<_, Blake2_128Concat, Identity, T::AccountId, OptionQuery>;
+
     #[pallet::config]
     pub trait Config: OracleConfig<Callback = Call<Self>> + frame_system::Config {
+        #[pallet::constant]
+        type AllowlistByteDeposit: Get<BalanceOf<Self>>;
+        #[pallet::constant]
+        type FreeCallWindow: Get<Self::BlockNumber>;
+        #[pallet::constant]
+        type FreeCallsPerWindow: Get<u32>;
+        #[pallet::constant]
+        type GuildDeposit: Get<BalanceOf<Self>>;
+        #[pallet::constant]
+        type IdentityDeposit: Get<BalanceOf<Self>>;
+        #[pallet::constant]
+        type MaxAdminsPerGuild: Get<u32>;
+        #[pallet::constant]
+        type MaxAllowlistChunks: Get<u32>;
         #[pallet::constant]
         type MaxAllowlistLen: Get<u32>;
         #[pallet::constant]
+        type MaxBansPerGuild: Get<u32>;
+        #[pallet::constant]
+        type MaxChildChecksPerBlock: Get<u32>;
+        #[pallet::constant]
+        type MaxChildrenPerRole: Get<u32>;
+        #[pallet::constant]
+        type MaxCompositeFilterLen: Get<u32>;
+        #[pallet::constant]
+        type MaxExpiriesPerBlock: Get<u32>;
+        #[pallet::constant]
+        type MaxFreeCallPrunesPerBlock: Get<u32>;
+        #[pallet::constant]
+        type MaxJoinBatchLen: Get<u32>;
+        #[pallet::constant]
+        type MaxRolesPerAccount: Get<u32>;
+        #[pallet::constant]
         type MaxRolesPerGuild: Get<u32>;
         #[pallet::constant]
         type MaxReqsPerRole: Get<u32>;
         #[pallet::constant]
+        type MaxReverificationsPerBlock: Get<u32>;
+        #[pallet::constant]
+        type MaxScheduleDelay: Get<u32>;
+        #[pallet::constant]
         type MaxSerializedLen: Get<u32>;
         #[pallet::constant]
+        type MaxSiweMessageLen: Get<u32>;
+        #[pallet::constant]
+        type PalletId: Get<PalletId>;
+        #[pallet::constant]
+        type ReverificationPeriod: Get<Self::BlockNumber>;
+        #[pallet::constant]
+        type RoleDeposit: Get<BalanceOf<Self>>;
+        #[pallet::constant]
+        type SiweChainId: Get<u64>;
+        #[pallet::constant]
         type MaxIdentities: Get<u8>;
         type MyRandomness: Randomness<Self::Hash, Self::BlockNumber>;
         type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+        /// Domain that SIWE messages have to be issued by.
+        type SiweDomain: Get<&'static str>;
+        type UnixTime: UnixTime;
         type WeightInfo: WeightInfo;
     }
 
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
+        AccountBanned(T::AccountId, GuildName, SerializedData),
+        AccountUnbanned(T::AccountId, GuildName),
+        AdminAdded(T::AccountId, GuildName),
+        AdminRemoved(T::AccountId, GuildName),
         AllowlistWritten(SpVec<u8>),
+        ExpiryNotScheduled(T::AccountId, GuildName, RoleName, DispatchError),
         GuildCreated(T::AccountId, GuildName),
+        GuildDeleted(T::AccountId, GuildName),
+        GuildMetadataUpdated(T::AccountId, GuildName),
+        GuildOwnershipOffered(T::AccountId, T::AccountId, GuildName),
+        GuildOwnershipTransferred(T::AccountId, T::AccountId, GuildName),
+        IdDeregistered(T::AccountId, u8),
         IdRegistered(T::AccountId, u8),
+        MemberKicked(T::AccountId, GuildName, RoleName, SerializedData),
+        ReverificationFailed(T::AccountId, GuildName, RoleName, DispatchError),
         RoleCreated(T::AccountId, GuildName, RoleName),
+        RoleDeleted(T::AccountId, GuildName, RoleName),
         RoleAssigned(T::AccountId, GuildName, RoleName),
         RoleStripped(T::AccountId, GuildName, RoleName),
+        RoleUpdated(T::AccountId, GuildName, RoleName, u32),
     }
 
     #[pallet::error]
     pub enum Error<T> {
         AccessDenied,
+        AccountAlreadyBanned,
+        AccountBanned,
+        AccountNotBanned,
+        AdminAlreadyExists,
+        AdminDoesNotExist,
+        AllowlistDoesNotExist,
+        AllowlistMismatch,
+        CannotBanGuildOwner,
         GuildAlreadyExists,
         GuildDoesNotExist,
         RoleAlreadyExists,
         RoleDoesNotExist,
         InvalidAllowlistLen,
+        InvalidCompositeFilterLen,
         InvalidOracleAnswer,
         InvalidOracleRequest,
+        InvalidRegistrationNonce,
+        InvalidSiweMessage,
+        InvalidValidityPeriod,
         UserNotRegistered,
         IdNotRegistered,
+        IdentityAlreadyClaimed,
         CodecError,
+        MaxAdminsPerGuildExceeded,
+        MaxBansPerGuildExceeded,
+        MaxChildrenPerRoleExceeded,
+        MaxExpiriesPerBlockExceeded,
         MaxIdentitiesExceeded,
+        MaxMembersExceeded,
+        MemberDoesNotExist,
+        MaxRolesPerAccountExceeded,
         MaxRolesPerGuildExceeded,
         MaxReqsPerRoleExceeded,
+        MaxReverificationsPerBlockExceeded,
         MaxSerializedLenExceeded,
         MissingAllowlistProof,
+        NoPendingOwnershipTransfer,
+        VerificationMsgExpired,
     }
 
     #[pallet::pallet]
-    #[pallet::without_storage_info]
+    #[pallet::storage_version(STORAGE_VERSION)]
     #[pallet::generate_store(pub(super) trait Store)]
     pub struct Pallet<T>(_);
 
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_initialize(n: T::BlockNumber) -> Weight {
+            Self::expire_memberships(n)
+                .saturating_add(Self::revoke_child_roles())
+                .saturating_add(Self::prune_free_calls(n))
+                .saturating_add(Self::reverify_members(n))
+        }
+
+        fn integrity_test() {
+            // NOTE uploaded allowlist chunks are verified as subtrees
+            assert!(
+                T::MaxAllowlistLen::get().is_power_of_two(),
+                "MaxAllowlistLen has to be a power of two"
+            );
+        }
+    }
+
     #[pallet::call]
     impl<T: Config> Pallet<T> {
         #[pallet::call_index(0)]
@@ -155,6 +466,7 @@ pub mod pallet {
             origin: OriginFor<T>,
             identity_with_auth: IdentityWithAuth,
             index: u8,
+            expires_at: T::BlockNumber,
         ) -> DispatchResult {
             let signer = ensure_signed(origin.clone())?;
 
@@ -162,12 +474,24 @@ pub mod pallet {
                 index < T::MaxIdentities::get(),
                 Error::<T>::MaxIdentitiesExceeded
             );
+            ensure!(
+                frame_system::Pallet::<T>::block_number() <= expires_at,
+                Error::<T>::VerificationMsgExpired
+            );
+            Self::ensure_claimable(&signer, index, &Identity::from(identity_with_auth))?;
 
+            let nonce = Self::registration_nonce(&signer);
+            let expires_at = expires_at.unique_saturated_into();
             match identity_with_auth {
-                IdentityWithAuth::Other(Identity::Other(_), _) => {
+                IdentityWithAuth::Other(Identity::Other(_), _)
+                | IdentityWithAuth::Eip1271(Identity::Address20(_), _) => {
+                    // NOTE the signature is checked by the oracle, so
+                    // the nonce is only consumed in the callback
                     let data = RequestData::Register {
                         identity_with_auth,
                         index,
+                        nonce,
+                        expires_at,
                     };
                     let request = Request::<T::AccountId> {
                         requester: signer,
@@ -186,14 +510,13 @@ pub mod pallet {
                         fee,
                     )?;
                 }
+                IdentityWithAuth::Eip712(..) => {
+                    let digest = Self::registration_digest(&signer, nonce, expires_at);
+                    Self::register_onchain(signer, index, identity_with_auth, digest)?;
+                }
                 id_with_auth => {
-                    let msg = gn_common::utils::verification_msg(&signer);
-                    if id_with_auth.verify(msg) {
-                        UserData::<T>::insert(&signer, index, Identity::from(identity_with_auth));
-                        Self::deposit_event(Event::IdRegistered(signer, index));
-                    } else {
-                        return Err(Error::<T>::AccessDenied.into());
-                    }
+                    let msg = Self::verification_msg(&signer, nonce, expires_at);
+                    Self::register_onchain(signer, index, id_with_auth, msg)?;
                 }
             }
 
@@ -209,78 +532,15 @@ pub mod pallet {
             proof: Option<MerkleProof>,
         ) -> DispatchResult {
             let signer = ensure_signed(origin.clone())?;
-            let role_id = Self::checked_role_id(&signer, &guild_name, &role_name)?;
-            // should not throw an error because we already checked that
-            // 'role_id' exists
-            let role_data = Roles::<T>::get(role_id).ok_or(Error::<T>::RoleDoesNotExist)?;
-            // check the onchain filter first
-            let (onchain_access, logic) = match role_data.filter {
-                Some(Filter::Guild(filter, logic)) => {
-                    let access = Self::check_parent_role(&signer, &filter);
-                    (access, logic)
-                }
-                Some(Filter::Allowlist(root, logic, n_leaves)) => {
-                    let Some(proof) = proof else {
-                        return Err(Error::<T>::MissingAllowlistProof.into())
-                    };
-                    let id = Self::user_data(&signer, proof.id_index)
-                        .ok_or(Error::<T>::IdNotRegistered)?;
-                    let leaf = MerkleLeaf::Value(id.as_ref());
-                    let access = proof.verify(&root, n_leaves as usize, leaf);
-                    (access, logic)
-                }
-                None => (true, FilterLogic::And),
-            };
-
-            match (onchain_access, logic, role_data.requirements.is_some()) {
-                // access is granted without the need for an oracle check if
-                // T || T
-                // T || F
-                // T && F
-                (true, FilterLogic::Or, _) | (true, _, false) => {
-                    Members::<T>::insert(role_id, &signer, true);
-                    Self::deposit_event(Event::RoleAssigned(signer, guild_name, role_name));
-                    Ok(())
-                }
-                // access is denied without the need of an oracle check if
-                // F && T
-                // F && F
-                // F || F
-                (false, FilterLogic::And, _) | (false, FilterLogic::Or, false) => {
-                    Err(Error::<T>::AccessDenied.into())
-                }
-                // else we need external oracle checks
-                // T && T
-                // F || T
-                (true, FilterLogic::And, true) | (false, FilterLogic::Or, true) => {
-                    let data = RequestData::ReqCheck {
-                        account: signer.clone(),
-                        guild_name,
-                        role_name,
-                    };
-                    let request = Request {
-                        requester: signer,
-                        data,
-                    };
-                    let call: <T as OracleConfig>::Callback = Call::callback {
-                        result: SpVec::new(),
-                    };
-                    let fee = BalanceOf::<T>::unique_saturated_from(
-                        <T as OracleConfig>::MinimumFee::get(),
-                    );
-
-                    if role_data.requirements.is_some() {
-                        <pallet_oracle::Pallet<T>>::initiate_request(
-                            origin,
-                            call,
-                            request.encode(),
-                            fee,
-                        )?;
-                    }
-
-                    Ok(())
-                }
+            if Self::join_onchain(&signer, guild_name, role_name, proof)? {
+                let data = RequestData::ReqCheck {
+                    account: signer.clone(),
+                    guild_name,
+                    role_name,
+                };
+                Self::initiate_req_check(origin, signer, data)?;
             }
+            Ok(())
         }
 
         #[pallet::call_index(2)]
@@ -291,8 +551,10 @@ pub mod pallet {
             role_name: RoleName,
         ) -> DispatchResult {
             let signer = ensure_signed(origin.clone())?;
-            let role_id = Self::checked_role_id(&signer, &guild_name, &role_name)?;
-            Members::<T>::remove(role_id, &signer);
+            // NOTE members should be able to leave even if they have
+            // deregistered all of their identities
+            let role_id = Self::existing_role_id(&guild_name, &role_name)?;
+            Self::revoke_membership(&guild_name, role_id, &signer);
             Self::deposit_event(Event::RoleStripped(signer, guild_name, role_name));
             Ok(())
         }
@@ -307,23 +569,30 @@ pub mod pallet {
         ) -> DispatchResult {
             let requester = ensure_signed(origin.clone())?;
 
-            let role_id = Self::checked_role_id(&account, &guild_name, &role_name)?;
+            let role_id = Self::existing_role_id(&guild_name, &role_name)?;
             // self checking is not allowed, users should just call 'leave'
             ensure!(account != requester, DispatchError::BadOrigin);
             // checked account must be a joined member, otherwise the oracle
             // could unknowingly add the user without checking on-chain filters
             // in the callback
-            ensure!(
-                Self::member(role_id, &account).is_some(),
-                Error::<T>::InvalidOracleRequest
-            );
+            //
+            // NOTE members are not required to be registered, because they
+            // might have deregistered the identities their roles depend on
+            if Self::member(role_id, &account).is_none() {
+                ensure!(
+                    <UserData<T>>::contains_prefix(&account),
+                    Error::<T>::UserNotRegistered
+                );
+                return Err(Error::<T>::InvalidOracleRequest.into());
+            }
             let role_data = Self::role(role_id).ok_or(Error::<T>::RoleDoesNotExist)?;
             match role_data.filter {
                 // NOTE if there is a filter with OR logic
                 // then it doesn't make sense to call an oracle check
                 // because the filter is definitely satisfied
                 Some(Filter::Guild(_, FilterLogic::Or))
-                | Some(Filter::Allowlist(_, FilterLogic::Or, _)) => {
+                | Some(Filter::Allowlist(_, FilterLogic::Or, _))
+                | Some(Filter::Composite(_, FilterLogic::Or)) => {
                     return Err(Error::<T>::InvalidOracleRequest.into())
                 }
                 // NOTE it makes sense to perform an oracle check
@@ -365,19 +634,19 @@ pub mod pallet {
                 Error::<T>::GuildAlreadyExists
             );
 
-            ensure!(
-                metadata.len() <= T::MaxSerializedLen::get() as usize,
-                Error::<T>::MaxSerializedLenExceeded
-            );
+            let metadata: BoundedSerializedData<T> = metadata
+                .try_into()
+                .map_err(|_| Error::<T>::MaxSerializedLenExceeded)?;
 
             let guild_id = Self::get_random_uuid();
+            Self::reserve_deposit(&signer, guild_id, T::GuildDeposit::get())?;
             GuildIdMap::<T>::insert(guild_name, guild_id);
 
             let guild = Guild {
                 name: guild_name,
                 owner: signer.clone(),
                 metadata,
-                roles: SpVec::new(),
+                roles: BoundedVec::default(),
             };
 
             Guilds::<T>::insert(guild_id, guild);
@@ -393,7 +662,14 @@ pub mod pallet {
             guild_name: GuildName,
             role_name: RoleName,
         ) -> DispatchResult {
-            Self::create_role(origin, guild_name, role_name, None, None)?;
+            Self::create_role(
+                origin,
+                guild_name,
+                role_name,
+                None,
+                None,
+                Self::role_deposit(0),
+            )?;
             Ok(())
         }
 
@@ -419,11 +695,19 @@ pub mod pallet {
                 Error::<T>::InvalidAllowlistLen
             );
             let filter = Filter::allowlist(&allowlist, filter_logic);
-            let role_id =
-                Self::create_role(origin, guild_name, role_name, Some(filter), requirements)?;
+            let encoded_allowlist = allowlist.encode();
+            let deposit = Self::role_deposit(encoded_allowlist.len());
+            let role_id = Self::create_role(
+                origin,
+                guild_name,
+                role_name,
+                Some(filter),
+                requirements,
+                deposit,
+            )?;
 
             let offchain_key = gn_common::offchain_allowlist_key(role_id.as_ref());
-            sp_io::offchain_index::set(&offchain_key, &allowlist.encode());
+            sp_io::offchain_index::set(&offchain_key, &encoded_allowlist);
             Self::deposit_event(Event::AllowlistWritten(offchain_key));
             Ok(())
         }
@@ -444,15 +728,16 @@ pub mod pallet {
             filter_logic: gn_common::filter::Logic,
             requirements: Option<SerializedRequirements>,
         ) -> DispatchResult {
-            let guild_id = Self::guild_id(filter.name).ok_or(Error::<T>::GuildDoesNotExist)?;
-            if let Some(parent_role_name) = filter.role {
-                ensure!(
-                    RoleIdMap::<T>::contains_key(guild_id, parent_role_name),
-                    Error::<T>::RoleDoesNotExist
-                );
-            }
+            Self::validate_parent_filter(&filter)?;
             let filter = Filter::Guild(filter, filter_logic);
-            Self::create_role(origin, guild_name, role_name, Some(filter), requirements)?;
+            Self::create_role(
+                origin,
+                guild_name,
+                role_name,
+                Some(filter),
+                requirements,
+                Self::role_deposit(0),
+            )?;
             Ok(())
         }
 
@@ -470,7 +755,14 @@ pub mod pallet {
             role_name: RoleName,
             requirements: SerializedRequirements,
         ) -> DispatchResult {
-            Self::create_role(origin, guild_name, role_name, None, Some(requirements))?;
+            Self::create_role(
+                origin,
+                guild_name,
+                role_name,
+                None,
+                Some(requirements),
+                Self::role_deposit(0),
+            )?;
             Ok(())
         }
 
@@ -486,30 +778,48 @@ pub mod pallet {
             let answer =
                 OracleAnswer::decode(&mut result.as_slice()).map_err(|_| Error::<T>::CodecError)?;
 
-            ensure!(answer.result.len() == 1, Error::<T>::InvalidOracleAnswer);
-
-            let access = answer.result[0] == 1;
-
             let request = Request::<T::AccountId>::decode(&mut answer.data.as_slice())
                 .map_err(|_| Error::<T>::CodecError)?;
 
+            // NOTE batched requirement checks are answered with one result
+            // per role
+            let expected_len = match &request.data {
+                RequestData::ReqCheckMany { roles, .. } => roles.len(),
+                _ => 1,
+            };
+            ensure!(
+                answer.result.len() == expected_len,
+                Error::<T>::InvalidOracleAnswer
+            );
+
+            let access = answer.result.first() == Some(&1);
+
             match request.data {
                 RequestData::ReqCheck {
                     account,
                     guild_name,
                     role_name,
                 } => {
-                    let role_id = Self::checked_role_id(&account, &guild_name, &role_name)?;
+                    let role_id = Self::existing_role_id(&guild_name, &role_name)?;
+                    let or_filter =
+                        Self::role(role_id).map_or(false, |role| Self::has_or_filter(&role));
                     match (access, Members::<T>::contains_key(role_id, &account)) {
                         (true, false) => {
-                            Members::<T>::insert(role_id, &account, true);
+                            // NOTE the account might have been banned while
+                            // the oracle request was pending
+                            Self::ensure_not_banned(&guild_name, &account)?;
+                            Self::grant_membership(role_id, guild_name, role_name, &account)?;
                             Self::deposit_event(Event::RoleAssigned(
                                 account, guild_name, role_name,
                             ));
                         }
+                        // NOTE members of roles with an OR filter might have
+                        // joined via the filter alone, so a failed check
+                        // doesn't prove that they aren't entitled to the role
+                        (false, true) if or_filter => {}
                         (false, true) => {
                             // TODO send locked rewards to requester
-                            Members::<T>::remove(role_id, &account);
+                            Self::revoke_membership(&guild_name, role_id, &account);
                             Self::deposit_event(Event::RoleStripped(
                                 account, guild_name, role_name,
                             ));
@@ -518,98 +828,1149 @@ pub mod pallet {
                         (true, true) => {} // nothing happens, requirements are still satisfied
                     }
                 }
+                RequestData::ReqCheckMany { account, roles } => {
+                    ensure!(answer.result.contains(&1), Error::<T>::AccessDenied);
+                    for ((guild_name, role_name), access) in roles.into_iter().zip(answer.result) {
+                        // NOTE roles might have been deleted and the account
+                        // might have been banned while the oracle request was
+                        // pending, these roles are skipped
+                        let Ok(role_id) = Self::existing_role_id(&guild_name, &role_name) else {
+                            continue;
+                        };
+                        if access != 1
+                            || Members::<T>::contains_key(role_id, &account)
+                            || Self::ensure_not_banned(&guild_name, &account).is_err()
+                        {
+                            continue;
+                        }
+                        Self::grant_membership(role_id, guild_name, role_name, &account)?;
+                        Self::deposit_event(Event::RoleAssigned(
+                            account.clone(),
+                            guild_name,
+                            role_name,
+                        ));
+                    }
+                }
                 RequestData::Register {
                     identity_with_auth,
                     index,
+                    nonce,
+                    ..
                 } => {
                     ensure!(access, Error::<T>::AccessDenied);
+                    // NOTE the signed message is single-use, so it is
+                    // rejected if another identity was registered with the
+                    // same nonce while the oracle request was pending
+                    ensure!(
+                        nonce == Self::registration_nonce(&request.requester),
+                        Error::<T>::InvalidRegistrationNonce
+                    );
                     ensure!(
                         index < T::MaxIdentities::get(),
                         Error::<T>::MaxIdentitiesExceeded
                     );
-                    UserData::<T>::insert(
-                        &request.requester,
-                        index,
-                        Identity::from(identity_with_auth),
-                    );
+                    // NOTE the identity might have been claimed by someone
+                    // else while the oracle request was pending
+                    let identity = Identity::from(identity_with_auth);
+                    Self::ensure_claimable(&request.requester, index, &identity)?;
+                    Self::store_identity(&request.requester, index, identity)?;
+                    RegistrationNonces::<T>::insert(&request.requester, nonce.saturating_add(1));
                     Self::deposit_event(Event::IdRegistered(request.requester, index));
                 }
             }
 
             Ok(())
         }
-    }
-
-    impl<T: Config> Pallet<T> {
-        fn checked_role_id(
-            account: &T::AccountId,
-            guild_name: &GuildName,
-            role_name: &RoleName,
-        ) -> Result<T::Hash, DispatchError> {
-            let guild_id = Self::guild_id(guild_name).ok_or(Error::<T>::GuildDoesNotExist)?;
-            let role_id = Self::role_id(guild_id, role_name).ok_or(Error::<T>::RoleDoesNotExist)?;
-
-            // check the requester is registered
-            ensure!(
-                <UserData<T>>::contains_prefix(account),
-                Error::<T>::UserNotRegistered
-            );
 
-            Ok(role_id)
+        #[pallet::call_index(10)]
+        #[pallet::weight((<T as Config>::WeightInfo::transfer_guild_ownership(), Pays::No))]
+        pub fn transfer_guild_ownership(
+            origin: OriginFor<T>,
+            guild_name: GuildName,
+            new_owner: T::AccountId,
+        ) -> DispatchResult {
+            let signer = ensure_signed(origin)?;
+            let guild_id = Self::owned_guild_id(&signer, &guild_name)?;
+            // NOTE a pending transfer is simply overwritten, thus the owner
+            // can always redirect an offer that hasn't been accepted yet
+            PendingOwners::<T>::insert(guild_id, &new_owner);
+            Self::deposit_event(Event::GuildOwnershipOffered(signer, new_owner, guild_name));
+            Ok(())
         }
 
-        fn get_and_increment_nonce() -> SerializedData {
-            let nonce = Nonce::<T>::get();
-            Nonce::<T>::put(nonce.wrapping_add(1));
-            nonce.encode()
-        }
+        #[pallet::call_index(11)]
+        #[pallet::weight((<T as Config>::WeightInfo::accept_guild_ownership(), Pays::No))]
+        pub fn accept_guild_ownership(
+            origin: OriginFor<T>,
+            guild_name: GuildName,
+        ) -> DispatchResult {
+            let signer = ensure_signed(origin)?;
+            let guild_id = Self::guild_id(guild_name).ok_or(Error::<T>::GuildDoesNotExist)?;
+            let pending_owner =
+                Self::pending_owner(guild_id).ok_or(Error::<T>::NoPendingOwnershipTransfer)?;
+            ensure!(pending_owner == signer, DispatchError::BadOrigin);
 
-        fn get_random_uuid() -> T::Hash {
-            let nonce = Self::get_and_increment_nonce();
-            let (random_value, _) = T::MyRandomness::random(&nonce);
-            random_value
+            let old_owner = Guilds::<T>::try_mutate(guild_id, |maybe_guild| {
+                let guild = maybe_guild.as_mut().ok_or(Error::<T>::GuildDoesNotExist)?;
+                Ok::<_, DispatchError>(sp_std::mem::replace(&mut guild.owner, signer.clone()))
+            })?;
+            PendingOwners::<T>::remove(guild_id);
+
+            Self::deposit_event(Event::GuildOwnershipTransferred(
+                old_owner, signer, guild_name,
+            ));
+            Ok(())
         }
 
-        fn create_role(
+        #[pallet::call_index(12)]
+        #[pallet::weight((<T as Config>::WeightInfo::delete_role(*max_members), Pays::No))]
+        pub fn delete_role(
             origin: OriginFor<T>,
             guild_name: GuildName,
             role_name: RoleName,
-            filter: Option<Filter>,
-            requirements: Option<SerializedRequirements>,
-        ) -> Result<T::Hash, DispatchError> {
+            max_members: u32,
+        ) -> DispatchResult {
             let signer = ensure_signed(origin)?;
-            let guild_id = Self::guild_id(guild_name).ok_or(Error::<T>::GuildDoesNotExist)?;
-            ensure!(
-                !RoleIdMap::<T>::contains_key(guild_id, role_name),
-                Error::<T>::RoleAlreadyExists
+            let guild_id = Self::managed_guild_id(&signer, &guild_name)?;
+            Self::remove_role(guild_id, &guild_name, &role_name, max_members)?;
+            Guilds::<T>::try_mutate(guild_id, |maybe_guild| {
+                let guild = maybe_guild.as_mut().ok_or(Error::<T>::GuildDoesNotExist)?;
+                guild.roles.retain(|name| name != &role_name);
+                Ok::<_, DispatchError>(())
+            })?;
+            Self::deposit_event(Event::RoleDeleted(signer, guild_name, role_name));
+            Ok(())
+        }
+
+        #[pallet::call_index(13)]
+        #[pallet::weight((
+            <T as Config>::WeightInfo::delete_guild(T::MaxRolesPerGuild::get(), *max_members),
+            Pays::No
+        ))]
+        pub fn delete_guild(
+            origin: OriginFor<T>,
+            guild_name: GuildName,
+            max_members: u32,
+        ) -> DispatchResult {
+            let signer = ensure_signed(origin)?;
+            let guild_id = Self::owned_guild_id(&signer, &guild_name)?;
+            let guild = Guilds::<T>::take(guild_id).ok_or(Error::<T>::GuildDoesNotExist)?;
+            // NOTE 'max_members' bounds the number of removed members
+            // across all roles of the guild
+            let mut members_left = max_members;
+            for role_name in guild.roles.iter() {
+                let removed = Self::remove_role(guild_id, &guild_name, role_name, members_left)?;
+                members_left = members_left.saturating_sub(removed);
+            }
+            GuildIdMap::<T>::remove(guild_name);
+            ChildRoles::<T>::remove(guild_id);
+            MemberCount::<T>::remove(guild_id);
+            Self::refund_deposit(guild_id);
+            Admins::<T>::remove(guild_id);
+            Bans::<T>::remove(guild_id);
+            PendingOwners::<T>::remove(guild_id);
+            Self::deposit_event(Event::GuildDeleted(signer, guild_name));
+            Ok(())
+        }
+
+        #[pallet::call_index(14)]
+        #[pallet::weight((
+            <T as Config>::WeightInfo::update_role_requirements(
+                requirements.as_ref().map(|reqs| reqs.0.len() as u32).unwrap_or_default(),
+                T::MaxSerializedLen::get()
+            ),
+            Pays::No
+        ))]
+        pub fn update_role_requirements(
+            origin: OriginFor<T>,
+            guild_name: GuildName,
+            role_name: RoleName,
+            requirements: Option<SerializedRequirements>,
+        ) -> DispatchResult {
+            let signer = ensure_signed(origin)?;
+            let guild_id = Self::managed_guild_id(&signer, &guild_name)?;
+            let role_id = Self::role_id(guild_id, role_name).ok_or(Error::<T>::RoleDoesNotExist)?;
+            let requirements = Self::bounded_requirements(requirements)?;
+            Roles::<T>::try_mutate(role_id, |maybe_role| {
+                let role = maybe_role.as_mut().ok_or(Error::<T>::RoleDoesNotExist)?;
+                role.requirements = requirements;
+                Ok::<_, DispatchError>(())
+            })?;
+            let version = Self::bump_role_version(role_id);
+            Self::deposit_event(Event::RoleUpdated(signer, guild_name, role_name, version));
+            Ok(())
+        }
+
+        #[pallet::call_index(15)]
+        #[pallet::weight((
+            <T as Config>::WeightInfo::update_role_filter(
+                match filter.as_ref() {
+                    Some(Filter::Composite(composite, _)) => composite.guilds.len() as u32,
+                    _ => 0,
+                }
+            ),
+            Pays::No
+        ))]
+        pub fn update_role_filter(
+            origin: OriginFor<T>,
+            guild_name: GuildName,
+            role_name: RoleName,
+            filter: Option<Filter>,
+        ) -> DispatchResult {
+            let signer = ensure_signed(origin)?;
+            let guild_id = Self::managed_guild_id(&signer, &guild_name)?;
+            let role_id = Self::role_id(guild_id, role_name).ok_or(Error::<T>::RoleDoesNotExist)?;
+            let new_filter = filter.map(Self::bounded_filter).transpose()?;
+            let old_filter = Roles::<T>::try_mutate(role_id, |maybe_role| {
+                let role = maybe_role.as_mut().ok_or(Error::<T>::RoleDoesNotExist)?;
+                Ok::<_, DispatchError>(sp_std::mem::replace(&mut role.filter, new_filter.clone()))
+            })?;
+            // NOTE the role is moved between the child role lists of its old
+            // and new parents
+            if let Some(filter) = old_filter.as_ref() {
+                Self::remove_child_role(filter, &guild_name, &role_name);
+            }
+            if let Some(filter) = new_filter.as_ref() {
+                Self::add_child_role(filter, &guild_name, &role_name)?;
+            }
+            // NOTE allowlists are stored offchain, so they need to be
+            // cleaned up when the role is no longer filtered by them, while
+            // the deposit follows the size of the new allowlist
+            let old_allowlist = old_filter.and_then(|filter| filter.allowlist_root());
+            let new_allowlist = new_filter
+                .as_ref()
+                .and_then(|filter| filter.allowlist_root());
+            if old_allowlist != new_allowlist {
+                if let Some((_, n_leaves)) = old_allowlist {
+                    Self::clear_offchain_allowlist(role_id, n_leaves);
+                }
+                let deposit = Self::filter_deposit(new_filter.as_ref());
+                Self::adjust_deposit(&signer, role_id, deposit)?;
+            }
+            let version = Self::bump_role_version(role_id);
+            Self::deposit_event(Event::RoleUpdated(signer, guild_name, role_name, version));
+            Ok(())
+        }
+
+        #[pallet::call_index(16)]
+        #[pallet::weight((<T as Config>::WeightInfo::add_admin(), Pays::No))]
+        pub fn add_admin(
+            origin: OriginFor<T>,
+            guild_name: GuildName,
+            admin: T::AccountId,
+        ) -> DispatchResult {
+            let signer = ensure_signed(origin)?;
+            let guild_id = Self::owned_guild_id(&signer, &guild_name)?;
+            Admins::<T>::try_mutate(guild_id, |admins| {
+                ensure!(!admins.contains(&admin), Error::<T>::AdminAlreadyExists);
+                admins
+                    .try_push(admin.clone())
+                    .map_err(|_| Error::<T>::MaxAdminsPerGuildExceeded)?;
+                Ok::<_, DispatchError>(())
+            })?;
+            Self::deposit_event(Event::AdminAdded(admin, guild_name));
+            Ok(())
+        }
+
+        #[pallet::call_index(17)]
+        #[pallet::weight((<T as Config>::WeightInfo::remove_admin(), Pays::No))]
+        pub fn remove_admin(
+            origin: OriginFor<T>,
+            guild_name: GuildName,
+            admin: T::AccountId,
+        ) -> DispatchResult {
+            let signer = ensure_signed(origin)?;
+            let guild_id = Self::owned_guild_id(&signer, &guild_name)?;
+            Admins::<T>::try_mutate(guild_id, |admins| {
+                let index = admins
+                    .iter()
+                    .position(|account| account == &admin)
+                    .ok_or(Error::<T>::AdminDoesNotExist)?;
+                admins.swap_remove(index);
+                Ok::<_, DispatchError>(())
+            })?;
+            Self::deposit_event(Event::AdminRemoved(admin, guild_name));
+            Ok(())
+        }
+
+        #[pallet::call_index(18)]
+        #[pallet::weight((
+            <T as Config>::WeightInfo::update_guild_metadata(metadata.len() as u32),
+            Pays::No
+        ))]
+        pub fn update_guild_metadata(
+            origin: OriginFor<T>,
+            guild_name: GuildName,
+            metadata: SerializedData,
+        ) -> DispatchResult {
+            let signer = ensure_signed(origin)?;
+            let metadata: BoundedSerializedData<T> = metadata
+                .try_into()
+                .map_err(|_| Error::<T>::MaxSerializedLenExceeded)?;
+            let guild_id = Self::owned_guild_id(&signer, &guild_name)?;
+            Guilds::<T>::try_mutate(guild_id, |maybe_guild| {
+                let guild = maybe_guild.as_mut().ok_or(Error::<T>::GuildDoesNotExist)?;
+                guild.metadata = metadata;
+                Ok::<_, DispatchError>(())
+            })?;
+            Self::deposit_event(Event::GuildMetadataUpdated(signer, guild_name));
+            Ok(())
+        }
+
+        #[pallet::call_index(19)]
+        #[pallet::weight((
+            <T as Config>::WeightInfo::unregister(T::MaxRolesPerAccount::get()),
+            Pays::No
+        ))]
+        pub fn unregister(origin: OriginFor<T>, index: u8) -> DispatchResult {
+            let signer = ensure_signed(origin)?;
+            let identity =
+                UserData::<T>::take(&signer, index).ok_or(Error::<T>::IdNotRegistered)?;
+            IdentityOwners::<T>::remove(identity);
+            if let Some(deposit) = IdentityDeposits::<T>::take(&signer, index) {
+                <T as OracleConfig>::Currency::unreserve(&signer, deposit);
+            }
+            // NOTE the identity used for joining is not stored, so every
+            // membership that might have been obtained via the removed
+            // identity is revoked or checked again
+            Self::recheck_memberships(&signer);
+            Self::deposit_event(Event::IdDeregistered(signer, index));
+            Ok(())
+        }
+
+        #[pallet::call_index(20)]
+        #[pallet::weight((<T as Config>::WeightInfo::set_role_validity(), Pays::No))]
+        pub fn set_role_validity(
+            origin: OriginFor<T>,
+            guild_name: GuildName,
+            role_name: RoleName,
+            validity: Option<T::BlockNumber>,
+        ) -> DispatchResult {
+            let signer = ensure_signed(origin)?;
+            let guild_id = Self::managed_guild_id(&signer, &guild_name)?;
+            let role_id = Self::role_id(guild_id, role_name).ok_or(Error::<T>::RoleDoesNotExist)?;
+            // NOTE the validity period only applies to memberships granted
+            // after the update, already existing memberships are unaffected
+            if let Some(period) = validity {
+                ensure!(!period.is_zero(), Error::<T>::InvalidValidityPeriod);
+                RoleValidity::<T>::insert(role_id, period);
+            } else {
+                RoleValidity::<T>::remove(role_id);
+            }
+            let version = Self::bump_role_version(role_id);
+            Self::deposit_event(Event::RoleUpdated(signer, guild_name, role_name, version));
+            Ok(())
+        }
+
+        #[pallet::call_index(21)]
+        #[pallet::weight((<T as Config>::WeightInfo::kick_member(reason.len() as u32), Pays::No))]
+        pub fn kick_member(
+            origin: OriginFor<T>,
+            guild_name: GuildName,
+            role_name: RoleName,
+            account: T::AccountId,
+            reason: SerializedData,
+        ) -> DispatchResult {
+            let signer = ensure_signed(origin)?;
+            let guild_id = Self::managed_guild_id(&signer, &guild_name)?;
+            let role_id = Self::role_id(guild_id, role_name).ok_or(Error::<T>::RoleDoesNotExist)?;
+            ensure!(
+                reason.len() <= T::MaxSerializedLen::get() as usize,
+                Error::<T>::MaxSerializedLenExceeded
+            );
+            ensure!(
+                Members::<T>::contains_key(role_id, &account),
+                Error::<T>::MemberDoesNotExist
+            );
+            Self::revoke_membership(&guild_name, role_id, &account);
+            Self::deposit_event(Event::MemberKicked(account, guild_name, role_name, reason));
+            Ok(())
+        }
+
+        #[pallet::call_index(22)]
+        #[pallet::weight((
+            <T as Config>::WeightInfo::ban_account(
+                T::MaxRolesPerGuild::get(),
+                reason.len() as u32
+            ),
+            Pays::No
+        ))]
+        pub fn ban_account(
+            origin: OriginFor<T>,
+            guild_name: GuildName,
+            account: T::AccountId,
+            reason: SerializedData,
+        ) -> DispatchResult {
+            let signer = ensure_signed(origin)?;
+            let guild_id = Self::managed_guild_id(&signer, &guild_name)?;
+            let guild = Self::guild(guild_id).ok_or(Error::<T>::GuildDoesNotExist)?;
+            ensure!(account != guild.owner, Error::<T>::CannotBanGuildOwner);
+            ensure!(
+                reason.len() <= T::MaxSerializedLen::get() as usize,
+                Error::<T>::MaxSerializedLenExceeded
+            );
+            Bans::<T>::try_mutate(guild_id, |bans| {
+                ensure!(!bans.contains(&account), Error::<T>::AccountAlreadyBanned);
+                bans.try_push(account.clone())
+                    .map_err(|_| Error::<T>::MaxBansPerGuildExceeded)?;
+                Ok::<_, DispatchError>(())
+            })?;
+            // NOTE banned accounts are kicked from every role of the guild
+            for role_name in guild.roles {
+                let Some(role_id) = Self::role_id(guild_id, role_name) else { continue };
+                if Self::revoke_membership(&guild_name, role_id, &account) {
+                    Self::deposit_event(Event::MemberKicked(
+                        account.clone(),
+                        guild_name,
+                        role_name,
+                        reason.clone(),
+                    ));
+                }
+            }
+            Self::deposit_event(Event::AccountBanned(account, guild_name, reason));
+            Ok(())
+        }
+
+        #[pallet::call_index(23)]
+        #[pallet::weight((<T as Config>::WeightInfo::unban_account(), Pays::No))]
+        pub fn unban_account(
+            origin: OriginFor<T>,
+            guild_name: GuildName,
+            account: T::AccountId,
+        ) -> DispatchResult {
+            let signer = ensure_signed(origin)?;
+            let guild_id = Self::managed_guild_id(&signer, &guild_name)?;
+            Bans::<T>::try_mutate(guild_id, |bans| {
+                let index = bans
+                    .iter()
+                    .position(|banned| banned == &account)
+                    .ok_or(Error::<T>::AccountNotBanned)?;
+                bans.swap_remove(index);
+                Ok::<_, DispatchError>(())
+            })?;
+            Self::deposit_event(Event::AccountUnbanned(account, guild_name));
+            Ok(())
+        }
+        #[pallet::call_index(24)]
+        #[pallet::weight((
+            <T as Config>::WeightInfo::update_allowlist(
+                allowlist.len() as u32,
+                max_members.unwrap_or_default()
+            ),
+            Pays::No
+        ))]
+        pub fn update_allowlist(
+            origin: OriginFor<T>,
+            guild_name: GuildName,
+            role_name: RoleName,
+            allowlist: SpVec<Identity>,
+            max_members: Option<u32>,
+        ) -> DispatchResult {
+            let signer = ensure_signed(origin)?;
+            ensure!(
+                !allowlist.is_empty() && allowlist.len() <= T::MaxAllowlistLen::get() as usize,
+                Error::<T>::InvalidAllowlistLen
+            );
+            let guild_id = Self::managed_guild_id(&signer, &guild_name)?;
+            let role_id = Self::role_id(guild_id, role_name).ok_or(Error::<T>::RoleDoesNotExist)?;
+            let Some(Filter::Allowlist(_, logic, _)) = Self::role(role_id).and_then(|role| role.filter) else {
+                return Err(Error::<T>::AllowlistDoesNotExist.into())
+            };
+
+            // NOTE stripping is optional, because members have to be
+            // iterated, which is bounded by 'max_members'
+            if let Some(max_members) = max_members {
+                let members = Members::<T>::iter_key_prefix(role_id)
+                    .take(max_members.saturating_add(1) as usize)
+                    .collect::<SpVec<_>>();
+                ensure!(
+                    members.len() <= max_members as usize,
+                    Error::<T>::MaxMembersExceeded
+                );
+                let mut sorted_allowlist = allowlist.clone();
+                sorted_allowlist.sort();
+                for account in members {
+                    let allowed = UserData::<T>::iter_prefix_values(&account)
+                        .any(|id| sorted_allowlist.binary_search(&id).is_ok());
+                    if !allowed {
+                        Self::revoke_membership(&guild_name, role_id, &account);
+                        Self::deposit_event(Event::RoleStripped(account, guild_name, role_name));
+                    }
+                }
+            }
+
+            Self::write_allowlist(&signer, role_id, &allowlist, logic)?;
+            let version = Self::bump_role_version(role_id);
+            Self::deposit_event(Event::RoleUpdated(signer, guild_name, role_name, version));
+            Ok(())
+        }
+
+        #[pallet::call_index(25)]
+        #[pallet::weight((
+            <T as Config>::WeightInfo::extend_allowlist(
+                allowlist.len().saturating_add(additions.len()) as u32
+            ),
+            Pays::No
+        ))]
+        pub fn extend_allowlist(
+            origin: OriginFor<T>,
+            guild_name: GuildName,
+            role_name: RoleName,
+            mut allowlist: SpVec<Identity>,
+            mut additions: SpVec<Identity>,
+        ) -> DispatchResult {
+            let signer = ensure_signed(origin)?;
+            ensure!(
+                !additions.is_empty()
+                    && allowlist.len().saturating_add(additions.len())
+                        <= T::MaxAllowlistLen::get() as usize,
+                Error::<T>::InvalidAllowlistLen
+            );
+            let guild_id = Self::managed_guild_id(&signer, &guild_name)?;
+            let role_id = Self::role_id(guild_id, role_name).ok_or(Error::<T>::RoleDoesNotExist)?;
+            let Some(current @ Filter::Allowlist(_, logic, _)) = Self::role(role_id).and_then(|role| role.filter) else {
+                return Err(Error::<T>::AllowlistDoesNotExist.into())
+            };
+            // NOTE allowlists are only stored offchain, so the current list
+            // has to be provided and checked against the onchain root
+            ensure!(
+                Filter::allowlist(&allowlist, logic) == current,
+                Error::<T>::AllowlistMismatch
+            );
+            allowlist.append(&mut additions);
+
+            Self::write_allowlist(&signer, role_id, &allowlist, logic)?;
+            let version = Self::bump_role_version(role_id);
+            Self::deposit_event(Event::RoleUpdated(signer, guild_name, role_name, version));
+            Ok(())
+        }
+        #[pallet::call_index(26)]
+        #[pallet::weight((
+            <T as Config>::WeightInfo::create_role_with_allowlist_root(
+                T::MaxReqsPerRole::get(),
+                T::MaxSerializedLen::get()
+            ),
+            Pays::No
+        ))]
+        pub fn create_role_with_allowlist_root(
+            origin: OriginFor<T>,
+            guild_name: GuildName,
+            role_name: RoleName,
+            root: gn_common::hash::Hash,
+            n_leaves: u32,
+            filter_logic: gn_common::filter::Logic,
+            requirements: Option<SerializedRequirements>,
+        ) -> DispatchResult {
+            // NOTE the list itself is published offchain or uploaded in
+            // chunks later, so the deposit covers the largest possible list
+            let filter = Self::bounded_filter(Filter::Allowlist(root, filter_logic, n_leaves))?;
+            let deposit = Self::filter_deposit(Some(&filter));
+            Self::create_role(
+                origin,
+                guild_name,
+                role_name,
+                Some(filter),
+                requirements,
+                deposit,
+            )?;
+            Ok(())
+        }
+
+        #[pallet::call_index(27)]
+        #[pallet::weight((
+            <T as Config>::WeightInfo::upload_allowlist_chunk(chunk.len() as u32),
+            Pays::No
+        ))]
+        pub fn upload_allowlist_chunk(
+            origin: OriginFor<T>,
+            guild_name: GuildName,
+            role_name: RoleName,
+            chunk_index: u32,
+            chunk: SpVec<Identity>,
+            proof: MerkleProof,
+        ) -> DispatchResult {
+            let signer = ensure_signed(origin)?;
+            let guild_id = Self::managed_guild_id(&signer, &guild_name)?;
+            let role_id = Self::role_id(guild_id, role_name).ok_or(Error::<T>::RoleDoesNotExist)?;
+            let Some((root, n_leaves)) = Self::role(role_id)
+                .and_then(|role| role.filter)
+                .and_then(|filter| filter.allowlist_root()) else {
+                return Err(Error::<T>::AllowlistDoesNotExist.into())
+            };
+            // NOTE every chunk but the last one is exactly 'MaxAllowlistLen'
+            // long, so leaf indices are derived from the chunk index. Since
+            // the chunk length is a power of two, every chunk spans a subtree
+            // of the allowlist and the root of that subtree is proven instead
+            // of the individual identities, at the position of the chunk
+            let chunk_len = T::MaxAllowlistLen::get();
+            let start = chunk_index.saturating_mul(chunk_len);
+            ensure!(
+                start < n_leaves && chunk.len() as u32 == chunk_len.min(n_leaves - start),
+                Error::<T>::InvalidAllowlistLen
+            );
+            ensure!(
+                proof.leaf_index == chunk_index
+                    && proof.verify_chunk(&root, n_leaves as usize, chunk_len as usize, &chunk),
+                Error::<T>::AllowlistMismatch
+            );
+            let chunk_key = gn_common::offchain_allowlist_chunk_key(role_id.as_ref(), chunk_index);
+            sp_io::offchain_index::set(&chunk_key, &chunk.encode());
+            Self::deposit_event(Event::AllowlistWritten(chunk_key));
+            Ok(())
+        }
+        #[pallet::call_index(28)]
+        #[pallet::weight((
+            <T as Config>::WeightInfo::create_role_with_composite_filter(
+                filter.guilds.len() as u32,
+                T::MaxReqsPerRole::get(),
+                T::MaxSerializedLen::get()
+            ),
+            Pays::No
+        ))]
+        pub fn create_role_with_composite_filter(
+            origin: OriginFor<T>,
+            guild_name: GuildName,
+            role_name: RoleName,
+            filter: gn_common::filter::Composite,
+            filter_logic: gn_common::filter::Logic,
+            requirements: Option<SerializedRequirements>,
+        ) -> DispatchResult {
+            let filter = Self::bounded_filter(Filter::Composite(filter, filter_logic))?;
+            // NOTE the allowlist is uploaded in chunks, just like for roles
+            // created with 'create_role_with_allowlist_root'
+            let deposit = Self::filter_deposit(Some(&filter));
+            Self::create_role(
+                origin,
+                guild_name,
+                role_name,
+                Some(filter),
+                requirements,
+                deposit,
+            )?;
+            Ok(())
+        }
+
+        #[pallet::call_index(29)]
+        #[pallet::weight((
+            <T as Config>::WeightInfo::join_many(roles.len() as u32),
+            Pays::No
+        ))]
+        pub fn join_many(
+            origin: OriginFor<T>,
+            roles: BoundedVec<(GuildName, RoleName, Option<MerkleProof>), T::MaxJoinBatchLen>,
+        ) -> DispatchResult {
+            let signer = ensure_signed(origin.clone())?;
+            // NOTE roles passing their onchain filters are joined right away,
+            // the rest are checked by a single oracle request
+            let mut checked_roles = SpVec::new();
+            for (guild_name, role_name, proof) in roles {
+                if Self::join_onchain(&signer, guild_name, role_name, proof)? {
+                    checked_roles.push((guild_name, role_name));
+                }
+            }
+            if !checked_roles.is_empty() {
+                let data = RequestData::ReqCheckMany {
+                    account: signer.clone(),
+                    roles: checked_roles,
+                };
+                Self::initiate_req_check(origin, signer, data)?;
+            }
+            Ok(())
+        }
+
+        #[pallet::call_index(30)]
+        #[pallet::weight((<T as Config>::WeightInfo::register_siwe(), Pays::No))]
+        pub fn register_siwe(
+            origin: OriginFor<T>,
+            message: BoundedVec<u8, T::MaxSiweMessageLen>,
+            signature: EcdsaSignature,
+            index: u8,
+            expires_at: T::BlockNumber,
+        ) -> DispatchResult {
+            let signer = ensure_signed(origin)?;
+
+            ensure!(
+                index < T::MaxIdentities::get(),
+                Error::<T>::MaxIdentitiesExceeded
+            );
+            ensure!(
+                frame_system::Pallet::<T>::block_number() <= expires_at,
+                Error::<T>::VerificationMsgExpired
+            );
+
+            let Some(siwe) = core::str::from_utf8(&message)
+                .ok()
+                .and_then(|msg| msg.parse::<SiweMessage>().ok()) else {
+                return Err(Error::<T>::InvalidSiweMessage.into())
+            };
+            ensure!(
+                siwe.verify_validity(
+                    T::SiweDomain::get(),
+                    T::SiweChainId::get(),
+                    T::UnixTime::now().as_secs()
+                )
+                .is_ok(),
+                Error::<T>::InvalidSiweMessage
+            );
+            let genesis_hash = frame_system::Pallet::<T>::block_hash(T::BlockNumber::zero());
+            let nonce = Self::registration_nonce(&signer);
+            ensure!(
+                siwe.verify_registration(
+                    &signer,
+                    genesis_hash,
+                    nonce,
+                    expires_at.unique_saturated_into()
+                )
+                .is_ok(),
+                Error::<T>::InvalidSiweMessage
+            );
+
+            let id_with_auth =
+                IdentityWithAuth::Ecdsa(Identity::Address20(siwe.address), signature);
+            Self::ensure_claimable(&signer, index, &Identity::from(id_with_auth))?;
+            Self::register_onchain(signer, index, id_with_auth, message.as_slice())
+        }
+    }
+
+    // NOTE these queries back the guild runtime API, so they return the
+    // unbounded types of `gn_common`
+    impl<T: Config> Pallet<T> {
+        pub fn guild_by_name(guild_name: &GuildName) -> Option<Guild<T::AccountId>> {
+            let guild = Self::guild(Self::guild_id(guild_name)?)?;
+            Some(Guild {
+                name: guild.name,
+                owner: guild.owner,
+                metadata: guild.metadata.into_inner(),
+                roles: guild.roles.into_inner(),
+            })
+        }
+
+        pub fn role_names(guild_name: &GuildName) -> SpVec<RoleName> {
+            Self::guild_by_name(guild_name)
+                .map(|guild| guild.roles)
+                .unwrap_or_default()
+        }
+
+        pub fn role_by_name(guild_name: &GuildName, role_name: &RoleName) -> Option<Role> {
+            let role = Self::role(Self::existing_role_id(guild_name, role_name).ok()?)?;
+            let filter = role.filter.map(|filter| match filter {
+                Filter::Allowlist(root, logic, n_leaves) => {
+                    Filter::Allowlist(root, logic, n_leaves)
+                }
+                Filter::Guild(parent, logic) => Filter::Guild(parent, logic),
+                Filter::Composite(composite, logic) => Filter::Composite(
+                    gn_common::filter::Composite {
+                        guilds: composite.guilds.into_inner(),
+                        allowlist: composite.allowlist,
+                        logic: composite.logic,
+                    },
+                    logic,
+                ),
+            });
+            let requirements = role.requirements.map(|(requirements, logic)| {
+                (
+                    requirements
+                        .into_iter()
+                        .map(BoundedVec::into_inner)
+                        .collect(),
+                    logic.into_inner(),
+                )
+            });
+            Some(Role {
+                filter,
+                requirements,
+            })
+        }
+
+        pub fn is_member_of(
+            guild_name: &GuildName,
+            role_name: &RoleName,
+            account: &T::AccountId,
+        ) -> bool {
+            Self::existing_role_id(guild_name, role_name)
+                .map(|role_id| Members::<T>::contains_key(role_id, account))
+                .unwrap_or_default()
+        }
+
+        /// Returns at most `limit` members of a role, starting after the
+        /// `start` account. Members are ordered by their storage keys, so
+        /// the last account of a page is the start of the next one.
+        pub fn members_page(
+            guild_name: &GuildName,
+            role_name: &RoleName,
+            start: Option<T::AccountId>,
+            limit: u32,
+        ) -> SpVec<T::AccountId> {
+            let Ok(role_id) = Self::existing_role_id(guild_name, role_name) else {
+                return SpVec::new();
+            };
+            let iter = if let Some(account) = start {
+                Members::<T>::iter_key_prefix_from(
+                    role_id,
+                    Members::<T>::hashed_key_for(role_id, account),
+                )
+            } else {
+                Members::<T>::iter_key_prefix(role_id)
+            };
+            iter.take(limit as usize).collect()
+        }
+
+        /// Returns the identities of an account with their indices in
+        /// ascending order.
+        pub fn identities_of(account: &T::AccountId) -> SpVec<(u8, Identity)> {
+            let mut identities = UserData::<T>::iter_prefix(account).collect::<SpVec<_>>();
+            identities.sort_by_key(|(index, _)| *index);
+            identities
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// Checks the onchain filter of a role and grants the membership if
+        /// the requirements don't have to be checked. Returns whether an
+        /// oracle check of the requirements is needed.
+        fn join_onchain(
+            signer: &T::AccountId,
+            guild_name: GuildName,
+            role_name: RoleName,
+            proof: Option<MerkleProof>,
+        ) -> Result<bool, DispatchError> {
+            let role_id = Self::checked_role_id(signer, &guild_name, &role_name)?;
+            Self::ensure_not_banned(&guild_name, signer)?;
+            // should not throw an error because we already checked that
+            // 'role_id' exists
+            let role_data = Roles::<T>::get(role_id).ok_or(Error::<T>::RoleDoesNotExist)?;
+            // check the onchain filter first
+            let (onchain_access, logic) = match role_data.filter {
+                Some(Filter::Guild(filter, logic)) => {
+                    let access = Self::check_parent_role(signer, &filter);
+                    (access, logic)
+                }
+                Some(Filter::Allowlist(root, logic, n_leaves)) => {
+                    let access = Self::check_allowlist(signer, proof, &root, n_leaves)?;
+                    (access, logic)
+                }
+                Some(Filter::Composite(composite, logic)) => {
+                    let access = Self::check_composite_filter(signer, &composite, proof)?;
+                    (access, logic)
+                }
+                None => (true, FilterLogic::And),
+            };
+
+            match (onchain_access, logic, role_data.requirements.is_some()) {
+                // access is granted without the need for an oracle check if
+                // T || T
+                // T || F
+                // T && F
+                (true, FilterLogic::Or, _) | (true, _, false) => {
+                    Self::grant_membership(role_id, guild_name, role_name, signer)?;
+                    Self::deposit_event(Event::RoleAssigned(signer.clone(), guild_name, role_name));
+                    Ok(false)
+                }
+                // access is denied without the need of an oracle check if
+                // F && T
+                // F && F
+                // F || F
+                (false, FilterLogic::And, _) | (false, FilterLogic::Or, false) => {
+                    Err(Error::<T>::AccessDenied.into())
+                }
+                // else we need external oracle checks
+                // T && T
+                // F || T
+                (true, FilterLogic::And, true) | (false, FilterLogic::Or, true) => Ok(true),
+            }
+        }
+
+        fn initiate_req_check(
+            origin: OriginFor<T>,
+            requester: T::AccountId,
+            data: RequestData<T::AccountId>,
+        ) -> DispatchResult {
+            let request = Request { requester, data };
+            let call: <T as OracleConfig>::Callback = Call::callback {
+                result: SpVec::new(),
+            };
+            let fee = BalanceOf::<T>::unique_saturated_from(<T as OracleConfig>::MinimumFee::get());
+            <pallet_oracle::Pallet<T>>::initiate_request(origin, call, request.encode(), fee)
+        }
+
+        fn checked_role_id(
+            account: &T::AccountId,
+            guild_name: &GuildName,
+            role_name: &RoleName,
+        ) -> Result<T::Hash, DispatchError> {
+            let role_id = Self::existing_role_id(guild_name, role_name)?;
+
+            // check the requester is registered
+            ensure!(
+                <UserData<T>>::contains_prefix(account),
+                Error::<T>::UserNotRegistered
+            );
+
+            Ok(role_id)
+        }
+
+        fn ensure_claimable(
+            account: &T::AccountId,
+            index: u8,
+            identity: &Identity,
+        ) -> DispatchResult {
+            if let Some(owner) = Self::identity_owner(identity) {
+                // re-registering the same identity under the same index is
+                // allowed, everything else would result in duplicate claims
+                ensure!(
+                    &owner == account && Self::user_data(account, index).as_ref() == Some(identity),
+                    Error::<T>::IdentityAlreadyClaimed
+                );
+            }
+            Ok(())
+        }
+
+        fn register_onchain<M: AsRef<[u8]>>(
+            signer: T::AccountId,
+            index: u8,
+            id_with_auth: IdentityWithAuth,
+            msg: M,
+        ) -> DispatchResult {
+            ensure!(id_with_auth.verify(msg), Error::<T>::AccessDenied);
+            let nonce = Self::registration_nonce(&signer);
+            Self::store_identity(&signer, index, Identity::from(id_with_auth))?;
+            RegistrationNonces::<T>::insert(&signer, nonce.saturating_add(1));
+            Self::deposit_event(Event::IdRegistered(signer, index));
+            Ok(())
+        }
+
+        fn verification_msg(account: &T::AccountId, nonce: u64, expires_at: u64) -> SpString {
+            let genesis_hash = frame_system::Pallet::<T>::block_hash(T::BlockNumber::zero());
+            gn_common::utils::verification_msg(account, genesis_hash, nonce, expires_at)
+        }
+
+        fn registration_digest(account: &T::AccountId, nonce: u64, expires_at: u64) -> [u8; 32] {
+            let genesis_hash = frame_system::Pallet::<T>::block_hash(T::BlockNumber::zero());
+            gn_common::eip712::registration_digest(account, genesis_hash, nonce, expires_at)
+        }
+
+        fn store_identity(account: &T::AccountId, index: u8, identity: Identity) -> DispatchResult {
+            if let Some(previous) = UserData::<T>::get(account, index) {
+                IdentityOwners::<T>::remove(previous);
+            } else {
+                // NOTE overwriting an identity doesn't require a new deposit
+                let deposit = T::IdentityDeposit::get();
+                <T as OracleConfig>::Currency::reserve(account, deposit)?;
+                IdentityDeposits::<T>::insert(account, index, deposit);
+            }
+            IdentityOwners::<T>::insert(identity, account);
+            UserData::<T>::insert(account, index, identity);
+            Ok(())
+        }
+
+        fn reserve_deposit(
+            depositor: &T::AccountId,
+            id: T::Hash,
+            deposit: BalanceOf<T>,
+        ) -> DispatchResult {
+            <T as OracleConfig>::Currency::reserve(depositor, deposit)?;
+            Deposits::<T>::insert(id, (depositor.clone(), deposit));
+            Ok(())
+        }
+
+        fn refund_deposit(id: T::Hash) {
+            if let Some((depositor, deposit)) = Deposits::<T>::take(id) {
+                <T as OracleConfig>::Currency::unreserve(&depositor, deposit);
+            }
+        }
+
+        /// Changes the amount reserved for a guild or role, the difference
+        /// is settled with the original depositor. The given account is only
+        /// charged if no deposit has been reserved yet.
+        fn adjust_deposit(
+            account: &T::AccountId,
+            id: T::Hash,
+            deposit: BalanceOf<T>,
+        ) -> DispatchResult {
+            let Some((depositor, reserved)) = Self::deposit(id) else {
+                return Self::reserve_deposit(account, id, deposit)
+            };
+            if deposit > reserved {
+                <T as OracleConfig>::Currency::reserve(&depositor, deposit - reserved)?;
+            } else {
+                <T as OracleConfig>::Currency::unreserve(&depositor, reserved - deposit);
+            }
+            Deposits::<T>::insert(id, (depositor, deposit));
+            Ok(())
+        }
+
+        /// Replaces the allowlist filter of a role and the offchain list,
+        /// while the deposit of the role's depositor is adjusted to the size
+        /// of the new list.
+        fn write_allowlist(
+            signer: &T::AccountId,
+            role_id: T::Hash,
+            allowlist: &[Identity],
+            logic: gn_common::filter::Logic,
+        ) -> DispatchResult {
+            let encoded_allowlist = allowlist.encode();
+            Self::adjust_deposit(signer, role_id, Self::role_deposit(encoded_allowlist.len()))?;
+            let old_filter = Roles::<T>::try_mutate(role_id, |maybe_role| {
+                let role = maybe_role.as_mut().ok_or(Error::<T>::RoleDoesNotExist)?;
+                let new_filter = Some(Filter::allowlist(allowlist, logic));
+                Ok::<_, DispatchError>(sp_std::mem::replace(&mut role.filter, new_filter))
+            })?;
+            // NOTE the previous list might have been uploaded in chunks
+            if let Some((_, n_leaves)) = old_filter.and_then(|filter| filter.allowlist_root()) {
+                Self::clear_offchain_allowlist(role_id, n_leaves);
+            }
+            let offchain_key = gn_common::offchain_allowlist_key(role_id.as_ref());
+            sp_io::offchain_index::set(&offchain_key, &encoded_allowlist);
+            Self::deposit_event(Event::AllowlistWritten(offchain_key));
+            Ok(())
+        }
+
+        /// Clears both the offchain allowlist written in a single piece and
+        /// the chunks that might have been uploaded for it.
+        fn clear_offchain_allowlist(role_id: T::Hash, n_leaves: u32) {
+            sp_io::offchain_index::clear(&gn_common::offchain_allowlist_key(role_id.as_ref()));
+            let chunk_len = T::MaxAllowlistLen::get().max(1);
+            let n_chunks = n_leaves.saturating_add(chunk_len - 1) / chunk_len;
+            for chunk_index in 0..n_chunks {
+                let chunk_key =
+                    gn_common::offchain_allowlist_chunk_key(role_id.as_ref(), chunk_index);
+                sp_io::offchain_index::clear(&chunk_key);
+            }
+        }
+
+        /// Deposit of a role filtered by an allowlist that is published
+        /// offchain or uploaded in chunks, which covers the largest
+        /// possible list with the given number of leaves.
+        fn filter_deposit(filter: Option<&BoundedFilter<T>>) -> BalanceOf<T> {
+            let n_leaves = filter
+                .and_then(|filter| filter.allowlist_root())
+                .map(|(_, n_leaves)| n_leaves)
+                .unwrap_or_default();
+            let max_identity_len = Identity::Other([0u8; 64]).encoded_size();
+            Self::role_deposit(max_identity_len.saturating_mul(n_leaves as usize))
+        }
+
+        fn role_deposit(allowlist_bytes: usize) -> BalanceOf<T> {
+            let per_byte = T::AllowlistByteDeposit::get();
+            T::RoleDeposit::get()
+                .saturating_add(per_byte.saturating_mul((allowlist_bytes as u32).into()))
+        }
+
+        pub(crate) fn existing_role_id(
+            guild_name: &GuildName,
+            role_name: &RoleName,
+        ) -> Result<T::Hash, DispatchError> {
+            let guild_id = Self::guild_id(guild_name).ok_or(Error::<T>::GuildDoesNotExist)?;
+            Self::role_id(guild_id, role_name).ok_or_else(|| Error::<T>::RoleDoesNotExist.into())
+        }
+
+        fn owned_guild_id(
+            signer: &T::AccountId,
+            guild_name: &GuildName,
+        ) -> Result<T::Hash, DispatchError> {
+            let guild_id = Self::guild_id(guild_name).ok_or(Error::<T>::GuildDoesNotExist)?;
+            let guild = Self::guild(guild_id).ok_or(Error::<T>::GuildDoesNotExist)?;
+            ensure!(&guild.owner == signer, DispatchError::BadOrigin);
+            Ok(guild_id)
+        }
+
+        /// Returns the guild's id if the signer is either the owner or one
+        /// of the admins of the guild.
+        fn managed_guild_id(
+            signer: &T::AccountId,
+            guild_name: &GuildName,
+        ) -> Result<T::Hash, DispatchError> {
+            let guild_id = Self::guild_id(guild_name).ok_or(Error::<T>::GuildDoesNotExist)?;
+            let guild = Self::guild(guild_id).ok_or(Error::<T>::GuildDoesNotExist)?;
+            ensure!(
+                Self::is_manager(guild_id, &guild, signer),
+                DispatchError::BadOrigin
             );
+            Ok(guild_id)
+        }
 
-            if let Some((reqs, logic)) = requirements.as_ref() {
-                ensure!(
-                    reqs.len() <= T::MaxReqsPerRole::get() as usize,
-                    Error::<T>::MaxReqsPerRoleExceeded
-                );
-                ensure!(
-                    logic.len() <= T::MaxSerializedLen::get() as usize,
-                    Error::<T>::MaxSerializedLenExceeded
-                );
-                for req in reqs {
-                    ensure!(
-                        req.len() <= T::MaxSerializedLen::get() as usize,
-                        Error::<T>::MaxSerializedLenExceeded
-                    );
-                }
+        fn is_manager(guild_id: T::Hash, guild: &GuildOf<T>, account: &T::AccountId) -> bool {
+            &guild.owner == account || Self::admins(guild_id).contains(account)
+        }
+
+        /// Removes every storage entry related to a role, apart from its
+        /// name in the guild's role list. Fails if the role has more than
+        /// `max_members` members, so the removal is always weighed properly.
+        fn remove_role(
+            guild_id: T::Hash,
+            guild_name: &GuildName,
+            role_name: &RoleName,
+            max_members: u32,
+        ) -> Result<u32, DispatchError> {
+            let role_id =
+                RoleIdMap::<T>::take(guild_id, role_name).ok_or(Error::<T>::RoleDoesNotExist)?;
+            let removed_members = Members::<T>::iter_key_prefix(role_id)
+                .take((max_members as usize).saturating_add(1))
+                .collect::<SpVec<_>>();
+            ensure!(
+                removed_members.len() <= max_members as usize,
+                Error::<T>::MaxMembersExceeded
+            );
+            // NOTE memberships are revoked one by one, so that the child
+            // roles of the removed role (and of the guild, for accounts that
+            // are not members of any other role) are checked again
+            for account in removed_members.iter() {
+                Self::revoke_membership(guild_name, role_id, account);
+            }
+            MemberCount::<T>::remove(role_id);
+            RoleNameMap::<T>::remove(role_id);
+            RoleVersions::<T>::remove(role_id);
+            RoleValidity::<T>::remove(role_id);
+            ChildRoles::<T>::remove(role_id);
+            Self::refund_deposit(role_id);
+            let removed_filter = Roles::<T>::take(role_id).and_then(|role| role.filter);
+            if let Some(filter) = removed_filter.as_ref() {
+                Self::remove_child_role(filter, guild_name, role_name);
             }
+            if let Some((_, n_leaves)) = removed_filter.and_then(|filter| filter.allowlist_root()) {
+                Self::clear_offchain_allowlist(role_id, n_leaves);
+            }
+            Ok(removed_members.len() as u32)
+        }
+
+        fn get_and_increment_nonce() -> SerializedData {
+            let nonce = Nonce::<T>::get();
+            Nonce::<T>::put(nonce.wrapping_add(1));
+            nonce.encode()
+        }
+
+        fn get_random_uuid() -> T::Hash {
+            let nonce = Self::get_and_increment_nonce();
+            let (random_value, _) = T::MyRandomness::random(&nonce);
+            random_value
+        }
+
+        fn create_role(
+            origin: OriginFor<T>,
+            guild_name: GuildName,
+            role_name: RoleName,
+            filter: Option<BoundedFilter<T>>,
+            requirements: Option<SerializedRequirements>,
+            deposit: BalanceOf<T>,
+        ) -> Result<T::Hash, DispatchError> {
+            let signer = ensure_signed(origin)?;
+            let guild_id = Self::guild_id(guild_name).ok_or(Error::<T>::GuildDoesNotExist)?;
+            ensure!(
+                !RoleIdMap::<T>::contains_key(guild_id, role_name),
+                Error::<T>::RoleAlreadyExists
+            );
+
+            let requirements = Self::bounded_requirements(requirements)?;
 
             Guilds::<T>::try_mutate(guild_id, |maybe_guild| {
                 if let Some(guild) = maybe_guild {
-                    if guild.owner != signer {
+                    if !Self::is_manager(guild_id, guild, &signer) {
                         Err(DispatchError::BadOrigin)
-                    } else if guild.roles.len() == T::MaxRolesPerGuild::get() as usize {
-                        Err(Error::<T>::MaxRolesPerGuildExceeded.into())
                     } else {
-                        guild.roles.push(role_name);
-                        Ok(())
+                        guild
+                            .roles
+                            .try_push(role_name)
+                            .map_err(|_| Error::<T>::MaxRolesPerGuildExceeded.into())
                     }
                 } else {
                     // shouldn't occur because we already
@@ -619,8 +1980,13 @@ pub mod pallet {
                 }
             })?;
 
+            if let Some(filter) = filter.as_ref() {
+                Self::add_child_role(filter, &guild_name, &role_name)?;
+            }
             let role_id = Self::get_random_uuid();
+            Self::reserve_deposit(&signer, role_id, deposit)?;
             RoleIdMap::<T>::insert(guild_id, role_name, role_id);
+            RoleNameMap::<T>::insert(role_id, (guild_name, role_name));
             Roles::<T>::insert(
                 role_id,
                 Role {
@@ -632,6 +1998,382 @@ pub mod pallet {
             Ok(role_id)
         }
 
+        fn bounded_requirements(
+            requirements: Option<SerializedRequirements>,
+        ) -> Result<Option<BoundedRequirements<T>>, DispatchError> {
+            let Some((reqs, logic)) = requirements else { return Ok(None) };
+            let reqs: BoundedVec<SerializedData, T::MaxReqsPerRole> = reqs
+                .try_into()
+                .map_err(|_| Error::<T>::MaxReqsPerRoleExceeded)?;
+            let logic: BoundedSerializedData<T> = logic
+                .try_into()
+                .map_err(|_| Error::<T>::MaxSerializedLenExceeded)?;
+            let reqs = reqs
+                .into_iter()
+                .map(BoundedSerializedData::<T>::try_from)
+                .collect::<Result<SpVec<_>, _>>()
+                .map_err(|_| Error::<T>::MaxSerializedLenExceeded)?;
+            // NOTE the number of requirements has already been bounded above
+            Ok(Some((BoundedVec::truncate_from(reqs), logic)))
+        }
+
+        // Strip memberships that expire in this block
+        fn expire_memberships(now: T::BlockNumber) -> Weight {
+            // NOTE the number of expiries per block is bounded by
+            // 'MaxExpiriesPerBlock' when they are scheduled
+            let expiries = MembershipExpiries::<T>::take(now);
+            let n_expiries = expiries.len() as u32;
+            for (guild_name, role_name, account, granted_at) in expiries {
+                // the role might have been deleted since
+                let Ok(role_id) = Self::existing_role_id(&guild_name, &role_name) else {
+                    continue
+                };
+                // the membership might have been revoked or renewed since
+                if Self::member(role_id, &account) == Some(granted_at) {
+                    Self::revoke_membership(&guild_name, role_id, &account);
+                    Self::deposit_event(Event::RoleStripped(account, guild_name, role_name));
+                }
+            }
+            <T as Config>::WeightInfo::expire_memberships(n_expiries)
+        }
+
+        // Send oracle requests re-checking the requirements of members that
+        // are scheduled for this block
+        fn reverify_members(now: T::BlockNumber) -> Weight {
+            // NOTE the number of checks per block is bounded by
+            // 'MaxReverificationsPerBlock' when they are scheduled
+            let checks = ReverificationQueue::<T>::take(now);
+            let n_checks = checks.len() as u32;
+            let payer = Self::account_id();
+            let next_check = now.saturating_add(T::ReverificationPeriod::get());
+            let fee = BalanceOf::<T>::unique_saturated_from(<T as OracleConfig>::MinimumFee::get());
+            for (guild_name, role_name, account, granted_at) in checks {
+                ScheduledReverifications::<T>::remove((guild_name, role_name), &account);
+                // the role might have been deleted since
+                let Ok(role_id) = Self::existing_role_id(&guild_name, &role_name) else {
+                    continue
+                };
+                // the membership might have been revoked or renewed since
+                if Self::member(role_id, &account) != Some(granted_at) {
+                    continue;
+                }
+                // requirements or the filter might have been updated since
+                if !Self::role(role_id).map_or(false, |role| Self::is_reverified(&role)) {
+                    continue;
+                }
+                let request = Request {
+                    requester: account.clone(),
+                    data: RequestData::ReqCheck {
+                        account: account.clone(),
+                        guild_name,
+                        role_name,
+                    },
+                };
+                let call: <T as OracleConfig>::Callback = Call::callback {
+                    result: SpVec::new(),
+                };
+                // NOTE the (minimum) oracle fee is paid by the pallet account,
+                // members are not charged for checks they didn't initiate. A
+                // failing request (e.g. there are no active operators) doesn't
+                // strip the membership, it is simply retried in the next round
+                let requested = <pallet_oracle::Pallet<T>>::initiate_request(
+                    frame_system::RawOrigin::Signed(payer.clone()).into(),
+                    call,
+                    request.encode(),
+                    fee,
+                );
+                let scheduled = Self::schedule_reverification(
+                    next_check,
+                    (guild_name, role_name, account.clone(), granted_at),
+                );
+                if let Err(error) = requested.and(scheduled) {
+                    Self::deposit_event(Event::ReverificationFailed(
+                        account, guild_name, role_name, error,
+                    ));
+                }
+            }
+            <T as Config>::WeightInfo::reverify_members(n_checks)
+        }
+
+        /// Index of the rate limiting window of feeless calls that the given
+        /// block belongs to.
+        pub fn free_call_window(now: T::BlockNumber) -> T::BlockNumber {
+            now / T::FreeCallWindow::get().max(One::one())
+        }
+
+        /// Number of feeless calls the account may still submit in the
+        /// current window.
+        pub fn free_calls_left(who: &T::AccountId) -> u32 {
+            let window = Self::free_call_window(frame_system::Pallet::<T>::block_number());
+            T::FreeCallsPerWindow::get().saturating_sub(Self::free_calls(window, who))
+        }
+
+        /// Uses up one of the feeless calls of the account in the current
+        /// window, returns `false` if there are none left.
+        pub fn use_free_call(who: &T::AccountId) -> bool {
+            let window = Self::free_call_window(frame_system::Pallet::<T>::block_number());
+            FreeCalls::<T>::mutate(window, who, |used| {
+                if *used >= T::FreeCallsPerWindow::get() {
+                    return false;
+                }
+                *used += 1;
+                true
+            })
+        }
+
+        // Remove the feeless call counters of expired windows
+        fn prune_free_calls(now: T::BlockNumber) -> Weight {
+            let window = Self::free_call_window(now);
+            let oldest = match Self::oldest_free_call_window() {
+                Some(oldest) if oldest < window => oldest,
+                Some(_) => return <T as Config>::WeightInfo::prune_free_calls(0),
+                None => {
+                    OldestFreeCallWindow::<T>::put(window);
+                    return <T as Config>::WeightInfo::prune_free_calls(0);
+                }
+            };
+            // NOTE windows are pruned one after the other, and a window is
+            // only done once all of its counters have been removed
+            let removed =
+                FreeCalls::<T>::clear_prefix(oldest, T::MaxFreeCallPrunesPerBlock::get(), None);
+            if removed.maybe_cursor.is_none() {
+                OldestFreeCallWindow::<T>::put(oldest.saturating_add(One::one()));
+            }
+            <T as Config>::WeightInfo::prune_free_calls(removed.unique)
+        }
+
+        /// Account paying the fees of the scheduled re-verifications.
+        pub fn account_id() -> T::AccountId {
+            T::PalletId::get().into_account_truncating()
+        }
+
+        /// Schedules the expiry of a membership, which is postponed to the
+        /// next block with a free slot if the given block is full.
+        fn schedule_expiry(
+            expiry: T::BlockNumber,
+            membership: ScheduledMembership<T>,
+        ) -> DispatchResult {
+            // NOTE the delay is bounded, so that looking for a free slot stays
+            // cheap even if the following blocks have been filled on purpose
+            let mut block = expiry;
+            for _ in 0..=T::MaxScheduleDelay::get() {
+                let mut expiries = Self::membership_expiries(block);
+                if expiries.try_push(membership.clone()).is_ok() {
+                    MembershipExpiries::<T>::insert(block, expiries);
+                    return Ok(());
+                }
+                block = block.saturating_add(1u32.into());
+            }
+            Err(Error::<T>::MaxExpiriesPerBlockExceeded.into())
+        }
+
+        /// Schedules the re-verification of a membership, replacing the one
+        /// already pending for it. Just like expiries, re-verifications are
+        /// postponed to the next block with a free slot.
+        pub(crate) fn schedule_reverification(
+            check_at: T::BlockNumber,
+            membership: ScheduledMembership<T>,
+        ) -> DispatchResult {
+            let (guild_name, role_name, account, _) = &membership;
+            let key = (*guild_name, *role_name);
+            if let Some(pending) = ScheduledReverifications::<T>::take(key, account) {
+                ReverificationQueue::<T>::mutate(pending, |checks| {
+                    checks.retain(|(g, r, a, _)| (g, r, a) != (guild_name, role_name, account))
+                });
+            }
+            let mut block = check_at;
+            for _ in 0..=T::MaxScheduleDelay::get() {
+                let mut checks = Self::reverification_queue(block);
+                if checks.try_push(membership.clone()).is_ok() {
+                    ReverificationQueue::<T>::insert(block, checks);
+                    ScheduledReverifications::<T>::insert(key, account, block);
+                    return Ok(());
+                }
+                block = block.saturating_add(1u32.into());
+            }
+            Err(Error::<T>::MaxReverificationsPerBlockExceeded.into())
+        }
+
+        fn ensure_not_banned(guild_name: &GuildName, account: &T::AccountId) -> DispatchResult {
+            let guild_id = Self::guild_id(guild_name).ok_or(Error::<T>::GuildDoesNotExist)?;
+            ensure!(
+                !Self::bans(guild_id).contains(account),
+                Error::<T>::AccountBanned
+            );
+            Ok(())
+        }
+
+        fn grant_membership(
+            role_id: T::Hash,
+            guild_name: GuildName,
+            role_name: RoleName,
+            account: &T::AccountId,
+        ) -> DispatchResult {
+            let now = frame_system::Pallet::<T>::block_number();
+            // NOTE the expiry and re-verification queues are shared by every
+            // guild, so a full queue doesn't prevent joining, the membership
+            // is kept until it's revoked otherwise (e.g. by a manager of the
+            // guild)
+            if let Some(validity) = Self::role_validity(role_id) {
+                let expiry = now.saturating_add(validity);
+                let membership = (guild_name, role_name, account.clone(), now);
+                if let Err(error) = Self::schedule_expiry(expiry, membership) {
+                    Self::deposit_event(Event::ExpiryNotScheduled(
+                        account.clone(),
+                        guild_name,
+                        role_name,
+                        error,
+                    ));
+                }
+            }
+            if Self::role(role_id).map_or(false, |role| Self::is_reverified(&role)) {
+                let next_check = now.saturating_add(T::ReverificationPeriod::get());
+                let membership = (guild_name, role_name, account.clone(), now);
+                if let Err(error) = Self::schedule_reverification(next_check, membership) {
+                    Self::deposit_event(Event::ReverificationFailed(
+                        account.clone(),
+                        guild_name,
+                        role_name,
+                        error,
+                    ));
+                }
+            }
+            if !Members::<T>::contains_key(role_id, account) {
+                AccountRoles::<T>::try_mutate(account, |role_ids| {
+                    role_ids
+                        .try_insert(role_id)
+                        .map_err(|_| Error::<T>::MaxRolesPerAccountExceeded)
+                })?;
+                if let Some(guild_id) = Self::guild_id(guild_name) {
+                    if !Self::is_guild_member(guild_id, account) {
+                        MemberCount::<T>::mutate(guild_id, |count| {
+                            *count = count.saturating_add(1)
+                        });
+                    }
+                }
+                MemberCount::<T>::mutate(role_id, |count| *count = count.saturating_add(1));
+            }
+            Members::<T>::insert(role_id, account, now);
+            Ok(())
+        }
+
+        fn has_or_filter(role: &RoleOf<T>) -> bool {
+            role.filter
+                .as_ref()
+                .map_or(false, |filter| filter.logic() == FilterLogic::Or)
+        }
+
+        /// Checks whether the members of a role are re-verified by the
+        /// oracle. Just like in 'request_oracle_check', roles whose filter is
+        /// in an OR relation with the requirements are skipped, because their
+        /// members might not satisfy the requirements at all.
+        pub(crate) fn is_reverified(role: &RoleOf<T>) -> bool {
+            role.requirements.is_some() && !Self::has_or_filter(role)
+        }
+
+        fn bump_role_version(role_id: T::Hash) -> u32 {
+            RoleVersions::<T>::mutate(role_id, |version| {
+                *version = version.wrapping_add(1);
+                *version
+            })
+        }
+
+        /// Checks a filter received in a call and converts it into the
+        /// bounded form stored in the role.
+        fn bounded_filter(filter: Filter) -> Result<BoundedFilter<T>, DispatchError> {
+            let max_leaves = T::MaxAllowlistLen::get().saturating_mul(T::MaxAllowlistChunks::get());
+            let validate_allowlist_len = |n_leaves: u32| {
+                ensure!(
+                    n_leaves > 0 && n_leaves <= max_leaves,
+                    Error::<T>::InvalidAllowlistLen
+                );
+                Ok::<_, DispatchError>(())
+            };
+            match filter {
+                Filter::Allowlist(root, logic, n_leaves) => {
+                    validate_allowlist_len(n_leaves)?;
+                    Ok(Filter::Allowlist(root, logic, n_leaves))
+                }
+                Filter::Guild(parent, logic) => {
+                    Self::validate_parent_filter(&parent)?;
+                    Ok(Filter::Guild(parent, logic))
+                }
+                Filter::Composite(composite, logic) => {
+                    ensure!(
+                        !composite.is_empty()
+                            && composite.len() <= T::MaxCompositeFilterLen::get() as usize,
+                        Error::<T>::InvalidCompositeFilterLen
+                    );
+                    for parent in composite.guilds.iter() {
+                        Self::validate_parent_filter(parent)?;
+                    }
+                    if let Some((_, n_leaves)) = composite.allowlist {
+                        validate_allowlist_len(n_leaves)?;
+                    }
+                    let guilds: BoundedParents<T> = composite
+                        .guilds
+                        .try_into()
+                        .map_err(|_| Error::<T>::InvalidCompositeFilterLen)?;
+                    let composite = BoundedComposite::<T> {
+                        guilds,
+                        allowlist: composite.allowlist,
+                        logic: composite.logic,
+                    };
+                    Ok(Filter::Composite(composite, logic))
+                }
+            }
+        }
+
+        fn validate_parent_filter(parent: &gn_common::filter::Guild) -> DispatchResult {
+            let guild_id = Self::guild_id(parent.name).ok_or(Error::<T>::GuildDoesNotExist)?;
+            if let Some(parent_role_name) = parent.role {
+                ensure!(
+                    RoleIdMap::<T>::contains_key(guild_id, parent_role_name),
+                    Error::<T>::RoleDoesNotExist
+                );
+            }
+            Ok(())
+        }
+
+        fn check_allowlist(
+            account: &T::AccountId,
+            proof: Option<MerkleProof>,
+            root: &gn_common::hash::Hash,
+            n_leaves: u32,
+        ) -> Result<bool, DispatchError> {
+            let Some(proof) = proof else {
+                return Err(Error::<T>::MissingAllowlistProof.into())
+            };
+            let id = Self::user_data(account, proof.id_index).ok_or(Error::<T>::IdNotRegistered)?;
+            let leaf = MerkleLeaf::Value(id.as_ref());
+            Ok(proof.verify(root, n_leaves as usize, leaf))
+        }
+
+        fn check_composite_filter(
+            account: &T::AccountId,
+            composite: &BoundedComposite<T>,
+            proof: Option<MerkleProof>,
+        ) -> Result<bool, DispatchError> {
+            let mut parents = composite
+                .guilds
+                .iter()
+                .map(|parent| Self::check_parent_role(account, parent));
+            let parent_access = match composite.logic {
+                FilterLogic::And => parents.all(|access| access),
+                FilterLogic::Or => parents.any(|access| access),
+            };
+            let Some((root, n_leaves)) = composite.allowlist else {
+                return Ok(parent_access)
+            };
+            // NOTE a proof is only required if the allowlist can change the
+            // outcome of the composite filter
+            match (composite.logic, parent_access) {
+                (FilterLogic::And, false) => Ok(false),
+                (FilterLogic::Or, true) => Ok(true),
+                _ => Self::check_allowlist(account, proof, &root, n_leaves),
+            }
+        }
+
         fn check_parent_role(account: &T::AccountId, parent: &gn_common::filter::Guild) -> bool {
             let Some(guild_id) = Self::guild_id(parent.name) else { return false };
             if let Some(parent_role_name) = parent.role {
@@ -652,6 +2394,259 @@ pub mod pallet {
                 access
             }
         }
+
+        /// Ids under which a role is listed as a child of its parents, i.e.
+        /// the parent role's id, or the guild's id if any role of the parent
+        /// guild grants access.
+        fn parent_ids(filter: &BoundedFilter<T>) -> SpVec<T::Hash> {
+            let parents: &[gn_common::filter::Guild] = match filter {
+                Filter::Guild(parent, _) => sp_std::slice::from_ref(parent),
+                Filter::Composite(composite, _) => composite.guilds.as_slice(),
+                Filter::Allowlist(..) => &[],
+            };
+            parents
+                .iter()
+                .filter_map(|parent| {
+                    let guild_id = Self::guild_id(parent.name)?;
+                    match parent.role {
+                        Some(role_name) => Self::role_id(guild_id, role_name),
+                        None => Some(guild_id),
+                    }
+                })
+                .collect()
+        }
+
+        fn add_child_role(
+            filter: &BoundedFilter<T>,
+            guild_name: &GuildName,
+            role_name: &RoleName,
+        ) -> DispatchResult {
+            for parent_id in Self::parent_ids(filter) {
+                ChildRoles::<T>::try_mutate(parent_id, |children| {
+                    if children.contains(&(*guild_name, *role_name)) {
+                        return Ok(());
+                    }
+                    children
+                        .try_push((*guild_name, *role_name))
+                        .map_err(|_| Error::<T>::MaxChildrenPerRoleExceeded)?;
+                    Ok::<_, DispatchError>(())
+                })?;
+            }
+            Ok(())
+        }
+
+        fn remove_child_role(
+            filter: &BoundedFilter<T>,
+            guild_name: &GuildName,
+            role_name: &RoleName,
+        ) {
+            for parent_id in Self::parent_ids(filter) {
+                ChildRoles::<T>::mutate_exists(parent_id, |maybe_children| {
+                    if let Some(children) = maybe_children {
+                        children.retain(|child| child != &(*guild_name, *role_name));
+                        if children.is_empty() {
+                            *maybe_children = None;
+                        }
+                    }
+                });
+            }
+        }
+
+        fn remove_account_role(account: &T::AccountId, role_id: T::Hash) {
+            AccountRoles::<T>::mutate_exists(account, |maybe_role_ids| {
+                if let Some(role_ids) = maybe_role_ids {
+                    role_ids.remove(&role_id);
+                    if role_ids.is_empty() {
+                        *maybe_role_ids = None;
+                    }
+                }
+            });
+        }
+
+        /// Checks whether the account is a member of any role of the guild.
+        pub(crate) fn is_guild_member(guild_id: T::Hash, account: &T::AccountId) -> bool {
+            let Some(guild) = Self::guild(guild_id) else { return false };
+            guild
+                .roles
+                .iter()
+                .filter_map(|role_name| Self::role_id(guild_id, role_name))
+                .any(|role_id| Members::<T>::contains_key(role_id, account))
+        }
+
+        /// Removes a membership, updates the member counts and queues the
+        /// account's memberships in the child roles of the revoked role for
+        /// re-evaluation. Returns whether
+        /// the account was a member of the role.
+        fn revoke_membership(
+            guild_name: &GuildName,
+            role_id: T::Hash,
+            account: &T::AccountId,
+        ) -> bool {
+            if Members::<T>::take(role_id, account).is_none() {
+                return false;
+            }
+            Self::remove_account_role(account, role_id);
+            MemberCount::<T>::mutate(role_id, |count| *count = count.saturating_sub(1));
+            let mut children = Self::child_roles(role_id);
+            if let Some(guild_id) = Self::guild_id(guild_name) {
+                if !Self::is_guild_member(guild_id, account) {
+                    MemberCount::<T>::mutate(guild_id, |count| *count = count.saturating_sub(1));
+                }
+                children.extend(Self::child_roles(guild_id));
+            }
+            for (child_guild_name, child_role_name) in children {
+                let Ok(child_id) = Self::existing_role_id(&child_guild_name, &child_role_name) else {
+                    continue
+                };
+                if Members::<T>::contains_key(child_id, account) {
+                    Self::queue_child_role_check((
+                        child_guild_name,
+                        child_role_name,
+                        account.clone(),
+                    ));
+                }
+            }
+            true
+        }
+
+        pub(crate) fn queue_child_role_check(check: (GuildName, RoleName, T::AccountId)) {
+            ChildRoleCheckRange::<T>::mutate(|(_, next)| {
+                ChildRoleChecks::<T>::insert(*next, check);
+                *next = next.saturating_add(1);
+            });
+        }
+
+        /// Number of child role checks waiting to be processed.
+        pub fn pending_child_role_checks() -> u64 {
+            let (first, next) = Self::child_role_check_range();
+            next.saturating_sub(first)
+        }
+
+        // Strip child role memberships whose parent roles have been revoked
+        fn revoke_child_roles() -> Weight {
+            let (first, next) = Self::child_role_check_range();
+            let n_checks = next
+                .saturating_sub(first)
+                .min(T::MaxChildChecksPerBlock::get().into());
+            if n_checks == 0 {
+                return <T as Config>::WeightInfo::revoke_child_roles(0);
+            }
+            // NOTE checks exceeding the per block limit are deferred to the
+            // next block, while checks resulting from revocations below are
+            // queued after them
+            let end = first + n_checks;
+            ChildRoleCheckRange::<T>::put((end, next));
+            for index in first..end {
+                let Some((guild_name, role_name, account)) = ChildRoleChecks::<T>::take(index) else {
+                    continue
+                };
+                // the role might have been deleted since
+                let Ok(role_id) = Self::existing_role_id(&guild_name, &role_name) else {
+                    continue
+                };
+                let Some(role) = Self::role(role_id) else { continue };
+                if Self::member(role_id, &account).is_none()
+                    || Self::keeps_child_role(&account, &role)
+                {
+                    continue;
+                }
+                Self::revoke_membership(&guild_name, role_id, &account);
+                Self::deposit_event(Event::RoleStripped(account, guild_name, role_name));
+            }
+            <T as Config>::WeightInfo::revoke_child_roles(n_checks as u32)
+        }
+
+        /// Revokes the memberships of the account that might have been
+        /// obtained via an allowlist, unless its parent roles grant access
+        /// on their own. Memberships depending on requirements are
+        /// re-verified in the next block, or revoked if the role is not
+        /// re-verified at all.
+        fn recheck_memberships(account: &T::AccountId) {
+            let next_check = frame_system::Pallet::<T>::block_number().saturating_add(One::one());
+            for role_id in Self::account_roles(account) {
+                let Some((guild_name, role_name)) = Self::role_name_of(role_id) else { continue };
+                let Some(role) = Self::role(role_id) else { continue };
+                let Some(granted_at) = Self::member(role_id, account) else { continue };
+                let parent_access = role
+                    .filter
+                    .as_ref()
+                    .map_or(false, |filter| Self::parent_access(account, filter));
+                let allowlist = role
+                    .filter
+                    .as_ref()
+                    .and_then(|filter| filter.allowlist_root())
+                    .is_some();
+                let reverified = Self::is_reverified(&role);
+                if !parent_access && (allowlist || (!reverified && role.requirements.is_some())) {
+                    Self::revoke_membership(&guild_name, role_id, account);
+                    Self::deposit_event(Event::RoleStripped(
+                        account.clone(),
+                        guild_name,
+                        role_name,
+                    ));
+                } else if reverified {
+                    let membership = (guild_name, role_name, account.clone(), granted_at);
+                    if let Err(error) = Self::schedule_reverification(next_check, membership) {
+                        Self::deposit_event(Event::ReverificationFailed(
+                            account.clone(),
+                            guild_name,
+                            role_name,
+                            error,
+                        ));
+                    }
+                }
+            }
+        }
+
+        /// Checks whether the parent roles of a filter grant access on their
+        /// own, i.e. without an allowlist proof.
+        fn parent_access(account: &T::AccountId, filter: &BoundedFilter<T>) -> bool {
+            match filter {
+                Filter::Guild(parent, _) => Self::check_parent_role(account, parent),
+                Filter::Composite(composite, _) => {
+                    let mut parents = composite
+                        .guilds
+                        .iter()
+                        .map(|parent| Self::check_parent_role(account, parent));
+                    match composite.logic {
+                        FilterLogic::And => {
+                            composite.allowlist.is_none() && parents.all(|access| access)
+                        }
+                        FilterLogic::Or => parents.any(|access| access),
+                    }
+                }
+                Filter::Allowlist(..) => false,
+            }
+        }
+
+        /// Checks whether a member of a child role still has access to it
+        /// after one of its parent roles has been revoked.
+        fn keeps_child_role(account: &T::AccountId, role: &RoleOf<T>) -> bool {
+            let (access, logic) = match &role.filter {
+                Some(Filter::Guild(parent, logic)) => {
+                    (Self::check_parent_role(account, parent), *logic)
+                }
+                Some(Filter::Composite(composite, logic)) => {
+                    let mut parents = composite
+                        .guilds
+                        .iter()
+                        .map(|parent| Self::check_parent_role(account, parent));
+                    let access = match composite.logic {
+                        FilterLogic::And => parents.all(|access| access),
+                        // NOTE allowlist proofs are only checked upon joining,
+                        // so the member might have joined via the allowlist
+                        FilterLogic::Or => {
+                            parents.any(|access| access) || composite.allowlist.is_some()
+                        }
+                    };
+                    (access, *logic)
+                }
+                _ => return true,
+            };
+            // NOTE members that might have joined by satisfying the role's
+            // requirements are left to the oracle re-verification
+            access || (logic == FilterLogic::Or && role.requirements.is_some())
+        }
     }
 
     impl<T: Config> CallbackWithParameter for Call<T> {
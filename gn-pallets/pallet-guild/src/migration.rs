@@ -0,0 +1,429 @@
+use super::*;
+
+pub mod v1 {
+    use super::*;
+    use frame_support::pallet_prelude::*;
+    use frame_support::sp_runtime::traits::Saturating;
+    use frame_support::traits::OnRuntimeUpgrade;
+    #[cfg(feature = "try-runtime")]
+    use sp_std::vec::Vec as SpVec;
+
+    /// Converts `Members` values from a bare `bool` to the block number of
+    /// the membership grant. Already existing memberships are treated as if
+    /// they were granted in the block of the upgrade and the members of
+    /// roles with requirements are queued for re-verification.
+    pub struct MigrateToV1<T>(PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let onchain_version = Pallet::<T>::on_chain_storage_version();
+            if onchain_version != 0 {
+                return T::DbWeight::get().reads(1);
+            }
+
+            let now = frame_system::Pallet::<T>::block_number();
+            let mut translated = 0u64;
+            Members::<T>::translate::<bool, _>(|_, _, _| {
+                translated += 1;
+                Some(now)
+            });
+
+            // schedule the first re-verification of members whose roles have
+            // requirements that are not in an OR relation with the filter
+            let next_check = now.saturating_add(T::ReverificationPeriod::get());
+            let mut reads = 0u64;
+            let mut scheduled = 0u64;
+            for (guild_id, role_name, role_id) in RoleIdMap::<T>::iter() {
+                reads += 3;
+                let Some(guild) = Guilds::<T>::get(guild_id) else { continue };
+                let reverified = Roles::<T>::get(role_id)
+                    .map_or(false, |role| Pallet::<T>::is_reverified(&role));
+                if !reverified {
+                    continue;
+                }
+                for account in Members::<T>::iter_key_prefix(role_id) {
+                    reads += 1;
+                    scheduled += 1;
+                    let membership = (guild.name, role_name, account, now);
+                    if Pallet::<T>::schedule_reverification(next_check, membership).is_err() {
+                        log::warn!(target: LOG_TARGET, "re-verification could not be scheduled");
+                    }
+                }
+            }
+            StorageVersion::new(1).put::<Pallet<T>>();
+
+            T::DbWeight::get().reads_writes(translated + reads + 2, translated + scheduled + 1)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<SpVec<u8>, &'static str> {
+            let n_members = Members::<T>::iter_keys().count() as u64;
+            Ok(n_members.encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: SpVec<u8>) -> Result<(), &'static str> {
+            let n_members_before =
+                u64::decode(&mut state.as_slice()).map_err(|_| "invalid pre-upgrade state")?;
+            let n_members_after = Members::<T>::iter_values().count() as u64;
+            ensure!(
+                n_members_before == n_members_after,
+                "number of members changed during the migration"
+            );
+            ensure!(
+                Pallet::<T>::on_chain_storage_version() == 1,
+                "storage version was not updated"
+            );
+            Ok(())
+        }
+    }
+}
+
+pub mod v2 {
+    use super::*;
+    use frame_support::pallet_prelude::*;
+    use frame_support::traits::OnRuntimeUpgrade;
+    use sp_std::collections::btree_set::BTreeSet;
+    #[cfg(feature = "try-runtime")]
+    use sp_std::vec::Vec as SpVec;
+
+    /// Initializes `MemberCount` for every role and guild from the already
+    /// existing memberships. Guild member counts hold the number of distinct
+    /// accounts that are members of at least one role of the guild.
+    pub struct MigrateToV2<T>(PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV2<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let onchain_version = Pallet::<T>::on_chain_storage_version();
+            if onchain_version != 1 {
+                return T::DbWeight::get().reads(1);
+            }
+
+            let mut reads = 0u64;
+            let mut writes = 0u64;
+            for (guild_id, guild) in Guilds::<T>::iter() {
+                reads += 1;
+                let mut guild_members = BTreeSet::new();
+                for role_name in guild.roles.iter() {
+                    reads += 1;
+                    let Some(role_id) = RoleIdMap::<T>::get(guild_id, role_name) else { continue };
+                    let mut role_members = 0u32;
+                    for account in Members::<T>::iter_key_prefix(role_id) {
+                        reads += 1;
+                        role_members += 1;
+                        guild_members.insert(account);
+                    }
+                    writes += 1;
+                    MemberCount::<T>::insert(role_id, role_members);
+                }
+                writes += 1;
+                MemberCount::<T>::insert(guild_id, guild_members.len() as u32);
+            }
+            StorageVersion::new(2).put::<Pallet<T>>();
+
+            T::DbWeight::get().reads_writes(reads + 1, writes + 1)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<SpVec<u8>, &'static str> {
+            let n_members = Members::<T>::iter_keys().count() as u64;
+            Ok(n_members.encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: SpVec<u8>) -> Result<(), &'static str> {
+            let n_members =
+                u64::decode(&mut state.as_slice()).map_err(|_| "invalid pre-upgrade state")?;
+            let n_counted = RoleIdMap::<T>::iter_values()
+                .map(|role_id| MemberCount::<T>::get(role_id) as u64)
+                .sum::<u64>();
+            ensure!(
+                n_members == n_counted,
+                "role member counts don't match the number of members"
+            );
+            ensure!(
+                Pallet::<T>::on_chain_storage_version() == 2,
+                "storage version was not updated"
+            );
+            Ok(())
+        }
+    }
+}
+
+pub mod v3 {
+    use super::*;
+    use frame_support::pallet_prelude::*;
+    use frame_support::traits::OnRuntimeUpgrade;
+    use gn_common::filter::{Composite, Filter};
+    use gn_common::{Guild, Role, RoleName};
+    use sp_std::vec::Vec as SpVec;
+
+    /// Moves guild and role data into bounded vectors. The encoding of
+    /// bounded vectors is the same as that of regular ones, so the entries
+    /// are only truncated if they exceed the current limits. Truncated
+    /// entries are logged and the roles dropped from a guild are removed
+    /// along with their memberships.
+    pub struct MigrateToV3<T>(PhantomData<T>);
+
+    fn bounded<E, S: Get<u32>>(items: SpVec<E>, what: &str) -> BoundedVec<E, S> {
+        if items.len() > S::get() as usize {
+            log::warn!(
+                target: LOG_TARGET,
+                "{} truncated from {} to {} items",
+                what,
+                items.len(),
+                S::get()
+            );
+        }
+        BoundedVec::truncate_from(items)
+    }
+
+    fn bounded_filter<T: Config>(filter: Filter) -> BoundedFilter<T> {
+        match filter {
+            Filter::Allowlist(root, logic, n_leaves) => Filter::Allowlist(root, logic, n_leaves),
+            Filter::Guild(parent, logic) => Filter::Guild(parent, logic),
+            Filter::Composite(composite, logic) => Filter::Composite(
+                Composite {
+                    guilds: bounded(composite.guilds, "composite filter"),
+                    allowlist: composite.allowlist,
+                    logic: composite.logic,
+                },
+                logic,
+            ),
+        }
+    }
+
+    // NOTE the bounded types are encoded just like the unbounded ones, so
+    // the items of the vectors can be counted the same way before and after
+    // the migration
+    #[cfg(feature = "try-runtime")]
+    fn n_items<T: Config>() -> u64 {
+        use frame_support::storage::migration::storage_iter;
+        use frame_support::traits::PalletInfoAccess;
+
+        let pallet = Pallet::<T>::name().as_bytes();
+        let mut n_items = 0u64;
+        for (_, guild) in storage_iter::<Guild<T::AccountId>>(pallet, b"Guilds") {
+            n_items += (guild.metadata.len() + guild.roles.len()) as u64;
+        }
+        for (_, role) in storage_iter::<Role>(pallet, b"Roles") {
+            if let Some(Filter::Composite(composite, _)) = role.filter {
+                n_items += composite.guilds.len() as u64;
+            }
+            if let Some((reqs, logic)) = role.requirements {
+                n_items += (reqs.len() + logic.len()) as u64;
+                n_items += reqs.iter().map(|req| req.len() as u64).sum::<u64>();
+            }
+        }
+        n_items
+    }
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV3<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let onchain_version = Pallet::<T>::on_chain_storage_version();
+            if onchain_version != 2 {
+                return T::DbWeight::get().reads(1);
+            }
+
+            let mut translated = 0u64;
+            let mut dropped_roles = SpVec::<(T::Hash, RoleName)>::new();
+            Guilds::<T>::translate::<Guild<T::AccountId>, _>(|guild_id, guild| {
+                translated += 1;
+                let max_roles = T::MaxRolesPerGuild::get() as usize;
+                dropped_roles.extend(
+                    guild
+                        .roles
+                        .iter()
+                        .skip(max_roles)
+                        .map(|role_name| (guild_id, *role_name)),
+                );
+                Some(Guild {
+                    name: guild.name,
+                    owner: guild.owner,
+                    metadata: bounded(guild.metadata, "guild metadata"),
+                    roles: bounded(guild.roles, "guild roles"),
+                })
+            });
+            Roles::<T>::translate::<Role, _>(|_, role| {
+                translated += 1;
+                let requirements = role.requirements.map(|(reqs, logic)| {
+                    let reqs = reqs
+                        .into_iter()
+                        .map(|req| bounded(req, "requirement"))
+                        .collect();
+                    (
+                        bounded(reqs, "role requirements"),
+                        bounded(logic, "requirement logic"),
+                    )
+                });
+                Some(Role {
+                    filter: role.filter.map(bounded_filter::<T>),
+                    requirements,
+                })
+            });
+
+            // NOTE roles that no longer fit into their guild are removed,
+            // otherwise their ids and memberships would be orphaned
+            let mut reads = 0u64;
+            let mut writes = 0u64;
+            for (guild_id, role_name) in dropped_roles {
+                reads += 1;
+                let Some(role_id) = RoleIdMap::<T>::take(guild_id, role_name) else { continue };
+                writes += 3;
+                Roles::<T>::remove(role_id);
+                MemberCount::<T>::remove(role_id);
+                let accounts = Members::<T>::drain_prefix(role_id)
+                    .map(|(account, _)| account)
+                    .collect::<SpVec<_>>();
+                for account in accounts {
+                    reads += 1;
+                    writes += 1;
+                    if !Pallet::<T>::is_guild_member(guild_id, &account) {
+                        writes += 1;
+                        MemberCount::<T>::mutate(guild_id, |count| {
+                            *count = count.saturating_sub(1)
+                        });
+                    }
+                }
+            }
+            StorageVersion::new(3).put::<Pallet<T>>();
+
+            T::DbWeight::get().reads_writes(translated + reads + 1, translated + writes + 1)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<SpVec<u8>, &'static str> {
+            let n_guilds = Guilds::<T>::iter_keys().count() as u64;
+            Ok((n_guilds, n_items::<T>()).encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: SpVec<u8>) -> Result<(), &'static str> {
+            let (n_guilds_before, n_items_before) = <(u64, u64)>::decode(&mut state.as_slice())
+                .map_err(|_| "invalid pre-upgrade state")?;
+            // NOTE entries that cannot be decoded into the bounded types are
+            // skipped while iterating, thus they would reduce the count
+            ensure!(
+                n_guilds_before == Guilds::<T>::iter_values().count() as u64,
+                "number of guilds changed during the migration"
+            );
+            ensure!(
+                RoleIdMap::<T>::iter_values().count() == Roles::<T>::iter_values().count(),
+                "role ids don't match the roles"
+            );
+            ensure!(
+                n_items_before == n_items::<T>(),
+                "entries were truncated during the migration"
+            );
+            ensure!(
+                Pallet::<T>::on_chain_storage_version() == 3,
+                "storage version was not updated"
+            );
+            Ok(())
+        }
+    }
+}
+
+pub mod v4 {
+    use super::*;
+    use frame_support::pallet_prelude::*;
+    use frame_support::traits::OnRuntimeUpgrade;
+    #[cfg(feature = "try-runtime")]
+    use sp_std::vec::Vec as SpVec;
+
+    /// Initializes the `AccountRoles` reverse index from the already existing
+    /// memberships. Roles exceeding `MaxRolesPerAccount` are not indexed.
+    pub struct MigrateToV4<T>(PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV4<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let onchain_version = Pallet::<T>::on_chain_storage_version();
+            if onchain_version != 3 {
+                return T::DbWeight::get().reads(1);
+            }
+
+            let mut indexed = 0u64;
+            for (role_id, account) in Members::<T>::iter_keys() {
+                indexed += 1;
+                AccountRoles::<T>::mutate(account, |role_ids| {
+                    let _ = role_ids.try_insert(role_id);
+                });
+            }
+            StorageVersion::new(4).put::<Pallet<T>>();
+
+            T::DbWeight::get().reads_writes(2 * indexed + 1, indexed + 1)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<SpVec<u8>, &'static str> {
+            let n_members = Members::<T>::iter_keys().count() as u64;
+            Ok(n_members.encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: SpVec<u8>) -> Result<(), &'static str> {
+            let n_members =
+                u64::decode(&mut state.as_slice()).map_err(|_| "invalid pre-upgrade state")?;
+            let n_indexed = AccountRoles::<T>::iter_values()
+                .map(|role_ids| role_ids.len() as u64)
+                .sum::<u64>();
+            ensure!(
+                n_members == n_indexed,
+                "account roles don't match the number of members"
+            );
+            ensure!(
+                Pallet::<T>::on_chain_storage_version() == 4,
+                "storage version was not updated"
+            );
+            Ok(())
+        }
+    }
+}
+
+pub mod v5 {
+    use super::*;
+    use frame_support::pallet_prelude::*;
+    use frame_support::traits::OnRuntimeUpgrade;
+    #[cfg(feature = "try-runtime")]
+    use sp_std::vec::Vec as SpVec;
+
+    /// Fills `RoleNameMap` with the names of the existing roles.
+    pub struct MigrateToV5<T>(PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV5<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let onchain_version = Pallet::<T>::on_chain_storage_version();
+            if onchain_version != 4 {
+                return T::DbWeight::get().reads(1);
+            }
+
+            let mut reads = 0u64;
+            let mut writes = 0u64;
+            for (guild_id, role_name, role_id) in RoleIdMap::<T>::iter() {
+                reads += 2;
+                let Some(guild) = Guilds::<T>::get(guild_id) else { continue };
+                writes += 1;
+                RoleNameMap::<T>::insert(role_id, (guild.name, role_name));
+            }
+            StorageVersion::new(5).put::<Pallet<T>>();
+
+            T::DbWeight::get().reads_writes(reads + 1, writes + 1)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(_state: SpVec<u8>) -> Result<(), &'static str> {
+            for (guild_id, role_name, role_id) in RoleIdMap::<T>::iter() {
+                let guild_name = Guilds::<T>::get(guild_id).map(|guild| guild.name);
+                ensure!(
+                    Pallet::<T>::role_name_of(role_id) == guild_name.map(|name| (name, role_name)),
+                    "role names don't match the role ids"
+                );
+            }
+            ensure!(
+                Pallet::<T>::on_chain_storage_version() == 5,
+                "storage version was not updated"
+            );
+            Ok(())
+        }
+    }
+}
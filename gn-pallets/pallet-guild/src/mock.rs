@@ -1,7 +1,8 @@
 pub use crate as pallet_guild;
 
 use frame_support::parameter_types;
-use frame_support::traits::{OnFinalize, OnInitialize};
+use frame_support::traits::{OnFinalize, OnInitialize, UnixTime};
+use frame_support::PalletId;
 use sp_core::H256;
 use sp_runtime::testing::Header;
 use sp_runtime::traits::{BlakeTwo256, ConstU32, ConstU64, IdentityLookup};
@@ -27,12 +28,46 @@ frame_support::construct_runtime!(
 parameter_types! {
     pub const ExistentialDeposit: Balance = 0;
     pub const MinimumFee: Balance = 0;
+    pub const FreeCallWindow: u64 = 10;
+    pub const FreeCallsPerWindow: u32 = 2;
+    pub const MaxAdminsPerGuild: u32 = 2;
+    pub const MaxAllowlistChunks: u32 = 4;
     pub const MaxAllowlistLen: u32 = 4;
+    pub const MaxBansPerGuild: u32 = 2;
+    pub const MaxChildChecksPerBlock: u32 = 2;
+    pub const MaxChildrenPerRole: u32 = 3;
+    pub const MaxCompositeFilterLen: u32 = 3;
+    pub const MaxExpiriesPerBlock: u32 = 2;
+    pub const MaxFreeCallPrunesPerBlock: u32 = 2;
     pub const MaxIdentities: u8 = 3;
+    pub const MaxJoinBatchLen: u32 = 4;
+    pub const MaxRolesPerAccount: u32 = 8;
     pub const MaxRolesPerGuild: u32 = 10;
     pub const MaxReqsPerRole: u32 = 10;
+    pub const MaxReverificationsPerBlock: u32 = 2;
+    pub const MaxScheduleDelay: u32 = 1;
     pub const MaxSerializedLen: u32 = 10;
+    pub const MaxSiweMessageLen: u32 = 1024;
+    pub const ReverificationPeriod: u64 = 5;
+    pub const GuildPalletId: PalletId = PalletId(*b"gn/guild");
     pub const ValidityPeriod: u64 = 10;
+    // deposits are zero by default and set in tests
+    pub static AllowlistByteDeposit: Balance = 0;
+    pub static GuildDeposit: Balance = 0;
+    pub static IdentityDeposit: Balance = 0;
+    pub static RoleDeposit: Balance = 0;
+    pub const SiweChainId: u64 = 1;
+    pub const SiweDomain: &'static str = "app.guild.xyz";
+    // seconds since the Unix epoch, set in tests
+    pub static Now: u64 = 1_677_672_000;
+}
+
+pub struct MockTime;
+
+impl UnixTime for MockTime {
+    fn now() -> core::time::Duration {
+        core::time::Duration::from_secs(Now::get())
+    }
 }
 
 impl frame_system::Config for TestRuntime {
@@ -75,13 +110,37 @@ impl pallet_balances::Config for TestRuntime {
 }
 
 impl pallet_guild::Config for TestRuntime {
+    type AllowlistByteDeposit = AllowlistByteDeposit;
+    type FreeCallWindow = FreeCallWindow;
+    type FreeCallsPerWindow = FreeCallsPerWindow;
+    type GuildDeposit = GuildDeposit;
+    type IdentityDeposit = IdentityDeposit;
+    type MaxAdminsPerGuild = MaxAdminsPerGuild;
+    type MaxAllowlistChunks = MaxAllowlistChunks;
     type MaxAllowlistLen = MaxAllowlistLen;
+    type MaxBansPerGuild = MaxBansPerGuild;
+    type MaxChildChecksPerBlock = MaxChildChecksPerBlock;
+    type MaxChildrenPerRole = MaxChildrenPerRole;
+    type MaxCompositeFilterLen = MaxCompositeFilterLen;
+    type MaxExpiriesPerBlock = MaxExpiriesPerBlock;
+    type MaxFreeCallPrunesPerBlock = MaxFreeCallPrunesPerBlock;
     type MaxIdentities = MaxIdentities;
+    type MaxJoinBatchLen = MaxJoinBatchLen;
+    type MaxRolesPerAccount = MaxRolesPerAccount;
     type MaxRolesPerGuild = MaxRolesPerGuild;
     type MaxReqsPerRole = MaxReqsPerRole;
+    type MaxReverificationsPerBlock = MaxReverificationsPerBlock;
+    type MaxScheduleDelay = MaxScheduleDelay;
     type MaxSerializedLen = MaxSerializedLen;
+    type MaxSiweMessageLen = MaxSiweMessageLen;
     type MyRandomness = RandomnessCollectiveFlip;
+    type PalletId = GuildPalletId;
+    type ReverificationPeriod = ReverificationPeriod;
+    type RoleDeposit = RoleDeposit;
     type RuntimeEvent = RuntimeEvent;
+    type SiweChainId = SiweChainId;
+    type SiweDomain = SiweDomain;
+    type UnixTime = MockTime;
     type WeightInfo = ();
 }
 
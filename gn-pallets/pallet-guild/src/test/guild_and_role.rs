@@ -1,5 +1,6 @@
 use super::*;
 use gn_common::filter::{Filter, Guild as GuildFilter, Logic as FilterLogic};
+use gn_common::merkle::Proof as MerkleProof;
 
 #[test]
 fn guild_creation() {
@@ -67,8 +68,8 @@ fn guild_with_free_roles() {
             let guild = <Guild>::guild(guild_id).unwrap();
             assert_eq!(guild.name, guild_name);
             assert_eq!(guild.owner, signer);
-            assert_eq!(guild.metadata, METADATA);
-            assert_eq!(&guild.roles, &role_names);
+            assert_eq!(guild.metadata.as_slice(), METADATA);
+            assert_eq!(guild.roles.as_slice(), role_names.as_slice());
 
             let role_id = <Guild>::role_id(guild_id, role_name).unwrap();
             let role = <Guild>::role(role_id).unwrap();
@@ -248,8 +249,8 @@ fn role_with_allowlist_filter() {
         let guild = <Guild>::guild(guild_id).unwrap();
         assert_eq!(guild.name, guild_name);
         assert_eq!(guild.owner, signer);
-        assert_eq!(guild.metadata, METADATA);
-        assert_eq!(guild.roles, &[role_name_0, role_name_1]);
+        assert_eq!(guild.metadata.as_slice(), METADATA);
+        assert_eq!(guild.roles.as_slice(), &[role_name_0, role_name_1]);
         let role_0 = <Guild>::role(role_id_0).unwrap();
         let role_1 = <Guild>::role(role_id_1).unwrap();
         assert_eq!(role_0.filter, Some(filter_0));
@@ -380,8 +381,8 @@ fn role_with_guild_filter() {
         let guild = <Guild>::guild(guild_id).unwrap();
         assert_eq!(guild.name, guild_name_0);
         assert_eq!(guild.owner, signer);
-        assert_eq!(guild.metadata, METADATA);
-        assert_eq!(&guild.roles, &[role_name_0, role_name_1]);
+        assert_eq!(guild.metadata.as_slice(), METADATA);
+        assert_eq!(guild.roles.as_slice(), &[role_name_0, role_name_1]);
 
         let role_id = <Guild>::role_id(guild_id, role_name_0).unwrap();
         let role = <Guild>::role(role_id).unwrap();
@@ -494,12 +495,1382 @@ fn unfiltered_role() {
         let guild = <Guild>::guild(guild_id).unwrap();
         assert_eq!(guild.name, guild_name);
         assert_eq!(guild.owner, signer);
-        assert_eq!(guild.metadata, METADATA);
-        assert_eq!(&guild.roles, &[role_name]);
+        assert_eq!(guild.metadata.as_slice(), METADATA);
+        assert_eq!(guild.roles.as_slice(), &[role_name]);
 
         let role_id = <Guild>::role_id(guild_id, role_name).unwrap();
         let role = <Guild>::role(role_id).unwrap();
         assert!(role.filter.is_none());
-        assert_eq!(role.requirements, Some(valid_requirements))
+        // NOTE bounded requirements are encoded just like unbounded ones
+        assert_eq!(
+            role.requirements.encode(),
+            Some(valid_requirements).encode()
+        )
+    });
+}
+
+#[test]
+fn guild_ownership_transfer() {
+    new_test_ext().execute_with(|| {
+        let owner = 1;
+        let new_owner = 2;
+        let other = 3;
+        let guild_name = [11u8; 32];
+        let other_guild_name = [22u8; 32];
+
+        dummy_guild(owner, guild_name);
+        let guild_id = <Guild>::guild_id(guild_name).unwrap();
+
+        let failing_transactions = vec![
+            (
+                <Guild>::transfer_guild_ownership(RuntimeOrigin::none(), guild_name, new_owner),
+                "BadOrigin",
+            ),
+            (
+                <Guild>::transfer_guild_ownership(
+                    RuntimeOrigin::signed(owner),
+                    other_guild_name,
+                    new_owner,
+                ),
+                "GuildDoesNotExist",
+            ),
+            (
+                <Guild>::transfer_guild_ownership(
+                    RuntimeOrigin::signed(other),
+                    guild_name,
+                    new_owner,
+                ),
+                "BadOrigin",
+            ),
+            (
+                <Guild>::accept_guild_ownership(RuntimeOrigin::signed(new_owner), guild_name),
+                "NoPendingOwnershipTransfer",
+            ),
+            (
+                <Guild>::accept_guild_ownership(RuntimeOrigin::signed(new_owner), other_guild_name),
+                "GuildDoesNotExist",
+            ),
+        ];
+
+        for (tx, raw_error_msg) in failing_transactions {
+            assert_eq!(error_msg(tx.unwrap_err()), raw_error_msg);
+        }
+
+        // offer the guild to an account and then redirect the offer
+        <Guild>::transfer_guild_ownership(RuntimeOrigin::signed(owner), guild_name, other).unwrap();
+        assert_eq!(
+            last_event(),
+            GuildEvent::GuildOwnershipOffered(owner, other, guild_name)
+        );
+        assert_eq!(<Guild>::pending_owner(guild_id), Some(other));
+        <Guild>::transfer_guild_ownership(RuntimeOrigin::signed(owner), guild_name, new_owner)
+            .unwrap();
+        assert_eq!(<Guild>::pending_owner(guild_id), Some(new_owner));
+
+        // only the pending owner may accept
+        assert_eq!(
+            error_msg(
+                <Guild>::accept_guild_ownership(RuntimeOrigin::signed(other), guild_name)
+                    .unwrap_err()
+            ),
+            "BadOrigin"
+        );
+        // ownership is not transferred until accepted
+        assert_eq!(<Guild>::guild(guild_id).unwrap().owner, owner);
+
+        <Guild>::accept_guild_ownership(RuntimeOrigin::signed(new_owner), guild_name).unwrap();
+        assert_eq!(
+            last_event(),
+            GuildEvent::GuildOwnershipTransferred(owner, new_owner, guild_name)
+        );
+        assert_eq!(<Guild>::guild(guild_id).unwrap().owner, new_owner);
+        assert!(<Guild>::pending_owner(guild_id).is_none());
+
+        // the previous owner lost their privileges
+        assert_eq!(
+            error_msg(
+                <Guild>::create_free_role(RuntimeOrigin::signed(owner), guild_name, [0u8; 32])
+                    .unwrap_err()
+            ),
+            "BadOrigin"
+        );
+        <Guild>::create_free_role(RuntimeOrigin::signed(new_owner), guild_name, [0u8; 32]).unwrap();
+    });
+}
+
+#[test]
+fn role_and_guild_deletion() {
+    let owner = 0;
+    let user_1 = 1;
+    let user_2 = 2;
+    let guild_name = [0u8; 32];
+    let free_role_name = [1u8; 32];
+    let allowlist_role_name = [2u8; 32];
+    let invalid_name = [100u8; 32];
+    let allowlist = vec![Identity::Address20([0u8; 20])];
+    let mut free_role_id = Default::default();
+    let mut allowlist_role_id = Default::default();
+    let mut ext = new_test_ext();
+
+    ext.execute_with(|| {
+        dummy_guild(owner, guild_name);
+        <Guild>::create_free_role(RuntimeOrigin::signed(owner), guild_name, free_role_name)
+            .unwrap();
+        <Guild>::create_role_with_allowlist(
+            RuntimeOrigin::signed(owner),
+            guild_name,
+            allowlist_role_name,
+            allowlist.clone(),
+            FilterLogic::Or,
+            None,
+        )
+        .unwrap();
+        let guild_id = <Guild>::guild_id(guild_name).unwrap();
+        free_role_id = <Guild>::role_id(guild_id, free_role_name).unwrap();
+        allowlist_role_id = <Guild>::role_id(guild_id, allowlist_role_name).unwrap();
+
+        for (user, seed) in [(user_1, [1u8; 32]), (user_2, [2u8; 32])] {
+            let (address, signature) = dummy_ecdsa_id_with_auth(user, seed);
+            <Guild>::register(
+                RuntimeOrigin::signed(user),
+                IdentityWithAuth::Ecdsa(address, signature),
+                0,
+                EXPIRES_AT,
+            )
+            .unwrap();
+            <Guild>::join(
+                RuntimeOrigin::signed(user),
+                guild_name,
+                free_role_name,
+                None,
+            )
+            .unwrap();
+        }
+    });
+
+    ext.persist_offchain_overlay();
+    let offchain_key = gn_common::offchain_allowlist_key(allowlist_role_id.as_ref());
+    assert_eq!(
+        ext.offchain_db().get(&offchain_key),
+        Some(allowlist.encode())
+    );
+
+    ext.execute_with(|| {
+        let failing_transactions = vec![
+            (
+                <Guild>::delete_role(RuntimeOrigin::none(), guild_name, free_role_name, 2),
+                "BadOrigin",
+            ),
+            (
+                <Guild>::delete_role(RuntimeOrigin::signed(user_1), guild_name, free_role_name, 2),
+                "BadOrigin",
+            ),
+            (
+                <Guild>::delete_role(
+                    RuntimeOrigin::signed(owner),
+                    invalid_name,
+                    free_role_name,
+                    2,
+                ),
+                "GuildDoesNotExist",
+            ),
+            (
+                <Guild>::delete_role(RuntimeOrigin::signed(owner), guild_name, invalid_name, 2),
+                "RoleDoesNotExist",
+            ),
+            (
+                <Guild>::delete_role(RuntimeOrigin::signed(owner), guild_name, free_role_name, 1),
+                "MaxMembersExceeded",
+            ),
+            (
+                <Guild>::delete_guild(RuntimeOrigin::signed(user_1), guild_name, 2),
+                "BadOrigin",
+            ),
+            (
+                <Guild>::delete_guild(RuntimeOrigin::signed(owner), invalid_name, 2),
+                "GuildDoesNotExist",
+            ),
+            (
+                <Guild>::delete_guild(RuntimeOrigin::signed(owner), guild_name, 1),
+                "MaxMembersExceeded",
+            ),
+        ];
+
+        for (tx, raw_error_msg) in failing_transactions {
+            assert_eq!(error_msg(tx.unwrap_err()), raw_error_msg);
+        }
+
+        // failed deletions leave the storage untouched
+        let guild_id = <Guild>::guild_id(guild_name).unwrap();
+        assert_eq!(
+            <Guild>::role_id(guild_id, free_role_name),
+            Some(free_role_id)
+        );
+        assert_eq!(<Guild>::member(free_role_id, user_1), Some(1));
+        assert_eq!(<Guild>::member(free_role_id, user_2), Some(1));
+
+        <Guild>::delete_role(
+            RuntimeOrigin::signed(owner),
+            guild_name,
+            allowlist_role_name,
+            0,
+        )
+        .unwrap();
+        assert_eq!(
+            last_event(),
+            GuildEvent::RoleDeleted(owner, guild_name, allowlist_role_name)
+        );
+        assert!(<Guild>::role_id(guild_id, allowlist_role_name).is_none());
+        assert!(<Guild>::role(allowlist_role_id).is_none());
+        assert_eq!(
+            <Guild>::guild(guild_id).unwrap().roles.to_vec(),
+            vec![free_role_name]
+        );
+
+        // the freed slot can be reused
+        <Guild>::create_free_role(
+            RuntimeOrigin::signed(owner),
+            guild_name,
+            allowlist_role_name,
+        )
+        .unwrap();
+
+        <Guild>::delete_guild(RuntimeOrigin::signed(owner), guild_name, 2).unwrap();
+        assert_eq!(last_event(), GuildEvent::GuildDeleted(owner, guild_name));
+        assert!(<Guild>::guild_id(guild_name).is_none());
+        assert!(<Guild>::guild(guild_id).is_none());
+        assert!(<Guild>::role_id(guild_id, free_role_name).is_none());
+        assert!(<Guild>::role(free_role_id).is_none());
+        assert!(<Guild>::member(free_role_id, user_1).is_none());
+        assert!(<Guild>::member(free_role_id, user_2).is_none());
+
+        // the guild name can be reused
+        dummy_guild(user_1, guild_name);
+    });
+
+    ext.persist_offchain_overlay();
+    assert!(ext.offchain_db().get(&offchain_key).is_none());
+}
+
+#[test]
+fn role_updates() {
+    let owner = 0;
+    let other = 1;
+    let guild_name = [0u8; 32];
+    let role_name = [0u8; 32];
+    let parent_role_name = [1u8; 32];
+    let invalid_name = [100u8; 32];
+    let max_reqs_per_role = <TestRuntime as pallet_guild::Config>::MaxReqsPerRole::get() as usize;
+    let max_serialized_len =
+        <TestRuntime as pallet_guild::Config>::MaxSerializedLen::get() as usize;
+    let allowlist = vec![Identity::Address20([0u8; 20])];
+    let parent_filter = GuildFilter {
+        name: guild_name,
+        role: Some(parent_role_name),
+    };
+    let mut role_id = Default::default();
+    let mut ext = new_test_ext();
+
+    ext.execute_with(|| {
+        dummy_guild(owner, guild_name);
+        <Guild>::create_role_with_allowlist(
+            RuntimeOrigin::signed(owner),
+            guild_name,
+            role_name,
+            allowlist.clone(),
+            FilterLogic::And,
+            None,
+        )
+        .unwrap();
+        <Guild>::create_free_role(RuntimeOrigin::signed(owner), guild_name, parent_role_name)
+            .unwrap();
+        let guild_id = <Guild>::guild_id(guild_name).unwrap();
+        role_id = <Guild>::role_id(guild_id, role_name).unwrap();
+        assert_eq!(<Guild>::role_version(role_id), 0);
+
+        let failing_transactions = vec![
+            (
+                <Guild>::update_role_requirements(
+                    RuntimeOrigin::none(),
+                    guild_name,
+                    role_name,
+                    None,
+                ),
+                "BadOrigin",
+            ),
+            (
+                <Guild>::update_role_requirements(
+                    RuntimeOrigin::signed(other),
+                    guild_name,
+                    role_name,
+                    None,
+                ),
+                "BadOrigin",
+            ),
+            (
+                <Guild>::update_role_requirements(
+                    RuntimeOrigin::signed(owner),
+                    invalid_name,
+                    role_name,
+                    None,
+                ),
+                "GuildDoesNotExist",
+            ),
+            (
+                <Guild>::update_role_requirements(
+                    RuntimeOrigin::signed(owner),
+                    guild_name,
+                    invalid_name,
+                    None,
+                ),
+                "RoleDoesNotExist",
+            ),
+            (
+                <Guild>::update_role_requirements(
+                    RuntimeOrigin::signed(owner),
+                    guild_name,
+                    role_name,
+                    Some((vec![vec![]; max_reqs_per_role + 1], vec![])),
+                ),
+                "MaxReqsPerRoleExceeded",
+            ),
+            (
+                <Guild>::update_role_requirements(
+                    RuntimeOrigin::signed(owner),
+                    guild_name,
+                    role_name,
+                    Some((vec![], vec![0u8; max_serialized_len + 1])),
+                ),
+                "MaxSerializedLenExceeded",
+            ),
+            (
+                <Guild>::update_role_filter(
+                    RuntimeOrigin::signed(other),
+                    guild_name,
+                    role_name,
+                    None,
+                ),
+                "BadOrigin",
+            ),
+            (
+                <Guild>::update_role_filter(
+                    RuntimeOrigin::signed(owner),
+                    guild_name,
+                    role_name,
+                    Some(Filter::Guild(
+                        GuildFilter {
+                            name: invalid_name,
+                            role: None,
+                        },
+                        FilterLogic::And,
+                    )),
+                ),
+                "GuildDoesNotExist",
+            ),
+            (
+                <Guild>::update_role_filter(
+                    RuntimeOrigin::signed(owner),
+                    guild_name,
+                    role_name,
+                    Some(Filter::Guild(
+                        GuildFilter {
+                            name: guild_name,
+                            role: Some(invalid_name),
+                        },
+                        FilterLogic::And,
+                    )),
+                ),
+                "RoleDoesNotExist",
+            ),
+            (
+                <Guild>::update_role_filter(
+                    RuntimeOrigin::signed(owner),
+                    guild_name,
+                    role_name,
+                    Some(Filter::Allowlist(Default::default(), FilterLogic::And, 0)),
+                ),
+                "InvalidAllowlistLen",
+            ),
+            (
+                <Guild>::update_role_filter(
+                    RuntimeOrigin::signed(owner),
+                    guild_name,
+                    role_name,
+                    Some(Filter::Composite(
+                        gn_common::filter::Composite {
+                            guilds: vec![],
+                            allowlist: None,
+                            logic: FilterLogic::Or,
+                        },
+                        FilterLogic::And,
+                    )),
+                ),
+                "InvalidCompositeFilterLen",
+            ),
+        ];
+
+        for (tx, raw_error_msg) in failing_transactions {
+            assert_eq!(error_msg(tx.unwrap_err()), raw_error_msg);
+        }
+        assert_eq!(<Guild>::role_version(role_id), 0);
+
+        let requirements = (vec![vec![1u8; max_serialized_len]], vec![2u8; 3]);
+        <Guild>::update_role_requirements(
+            RuntimeOrigin::signed(owner),
+            guild_name,
+            role_name,
+            Some(requirements.clone()),
+        )
+        .unwrap();
+        assert_eq!(
+            last_event(),
+            GuildEvent::RoleUpdated(owner, guild_name, role_name, 1)
+        );
+        let role = <Guild>::role(role_id).unwrap();
+        assert_eq!(role.requirements.encode(), Some(requirements).encode());
+        assert!(matches!(role.filter, Some(Filter::Allowlist(..))));
+    });
+
+    ext.persist_offchain_overlay();
+    let offchain_key = gn_common::offchain_allowlist_key(role_id.as_ref());
+    assert_eq!(
+        ext.offchain_db().get(&offchain_key),
+        Some(allowlist.encode())
+    );
+
+    ext.execute_with(|| {
+        <Guild>::update_role_filter(
+            RuntimeOrigin::signed(owner),
+            guild_name,
+            role_name,
+            Some(Filter::Guild(parent_filter, FilterLogic::Or)),
+        )
+        .unwrap();
+        assert_eq!(
+            last_event(),
+            GuildEvent::RoleUpdated(owner, guild_name, role_name, 2)
+        );
+        let role = <Guild>::role(role_id).unwrap();
+        assert_eq!(
+            role.filter,
+            Some(Filter::Guild(parent_filter, FilterLogic::Or))
+        );
+        let guild_id = <Guild>::guild_id(guild_name).unwrap();
+        let parent_role_id = <Guild>::role_id(guild_id, parent_role_name).unwrap();
+        assert_eq!(
+            <Guild>::child_roles(parent_role_id),
+            vec![(guild_name, role_name)]
+        );
+
+        <Guild>::update_role_requirements(
+            RuntimeOrigin::signed(owner),
+            guild_name,
+            role_name,
+            None,
+        )
+        .unwrap();
+        <Guild>::update_role_filter(RuntimeOrigin::signed(owner), guild_name, role_name, None)
+            .unwrap();
+        assert_eq!(
+            last_event(),
+            GuildEvent::RoleUpdated(owner, guild_name, role_name, 4)
+        );
+        let role = <Guild>::role(role_id).unwrap();
+        assert!(role.filter.is_none());
+        assert!(role.requirements.is_none());
+        assert!(<Guild>::child_roles(parent_role_id).is_empty());
+    });
+
+    // the allowlist is removed once the role is no longer filtered by it
+    ext.persist_offchain_overlay();
+    assert!(ext.offchain_db().get(&offchain_key).is_none());
+}
+
+#[test]
+fn guild_admins() {
+    new_test_ext().execute_with(|| {
+        let owner = 0;
+        let admin_1 = 1;
+        let admin_2 = 2;
+        let other = 3;
+        let guild_name = [0u8; 32];
+        let role_name = [0u8; 32];
+        let invalid_name = [100u8; 32];
+
+        dummy_guild(owner, guild_name);
+        let guild_id = <Guild>::guild_id(guild_name).unwrap();
+
+        <Guild>::add_admin(RuntimeOrigin::signed(owner), guild_name, admin_1).unwrap();
+        assert_eq!(last_event(), GuildEvent::AdminAdded(admin_1, guild_name));
+        <Guild>::add_admin(RuntimeOrigin::signed(owner), guild_name, admin_2).unwrap();
+        assert_eq!(last_event(), GuildEvent::AdminAdded(admin_2, guild_name));
+        assert_eq!(<Guild>::admins(guild_id), vec![admin_1, admin_2]);
+
+        let failing_transactions = vec![
+            (
+                <Guild>::add_admin(RuntimeOrigin::none(), guild_name, other),
+                "BadOrigin",
+            ),
+            (
+                <Guild>::add_admin(RuntimeOrigin::signed(admin_1), guild_name, other),
+                "BadOrigin",
+            ),
+            (
+                <Guild>::add_admin(RuntimeOrigin::signed(owner), invalid_name, other),
+                "GuildDoesNotExist",
+            ),
+            (
+                <Guild>::add_admin(RuntimeOrigin::signed(owner), guild_name, admin_1),
+                "AdminAlreadyExists",
+            ),
+            (
+                <Guild>::add_admin(RuntimeOrigin::signed(owner), guild_name, other),
+                "MaxAdminsPerGuildExceeded",
+            ),
+            (
+                <Guild>::remove_admin(RuntimeOrigin::signed(admin_1), guild_name, admin_2),
+                "BadOrigin",
+            ),
+            (
+                <Guild>::remove_admin(RuntimeOrigin::signed(owner), guild_name, other),
+                "AdminDoesNotExist",
+            ),
+            (
+                <Guild>::create_free_role(RuntimeOrigin::signed(other), guild_name, role_name),
+                "BadOrigin",
+            ),
+            (
+                <Guild>::transfer_guild_ownership(
+                    RuntimeOrigin::signed(admin_1),
+                    guild_name,
+                    admin_1,
+                ),
+                "BadOrigin",
+            ),
+            (
+                <Guild>::delete_guild(RuntimeOrigin::signed(admin_1), guild_name, 0),
+                "BadOrigin",
+            ),
+        ];
+
+        for (tx, raw_error_msg) in failing_transactions {
+            assert_eq!(error_msg(tx.unwrap_err()), raw_error_msg);
+        }
+
+        // admins can create and edit roles
+        <Guild>::create_free_role(RuntimeOrigin::signed(admin_1), guild_name, role_name).unwrap();
+        assert_eq!(
+            last_event(),
+            GuildEvent::RoleCreated(admin_1, guild_name, role_name)
+        );
+        <Guild>::update_role_requirements(
+            RuntimeOrigin::signed(admin_2),
+            guild_name,
+            role_name,
+            Some((vec![], vec![])),
+        )
+        .unwrap();
+        assert_eq!(
+            last_event(),
+            GuildEvent::RoleUpdated(admin_2, guild_name, role_name, 1)
+        );
+
+        <Guild>::remove_admin(RuntimeOrigin::signed(owner), guild_name, admin_1).unwrap();
+        assert_eq!(last_event(), GuildEvent::AdminRemoved(admin_1, guild_name));
+        assert_eq!(<Guild>::admins(guild_id), vec![admin_2]);
+
+        // removed admins lose their privileges
+        assert_eq!(
+            error_msg(
+                <Guild>::delete_role(RuntimeOrigin::signed(admin_1), guild_name, role_name, 0)
+                    .unwrap_err()
+            ),
+            "BadOrigin"
+        );
+        <Guild>::delete_role(RuntimeOrigin::signed(admin_2), guild_name, role_name, 0).unwrap();
+
+        <Guild>::delete_guild(RuntimeOrigin::signed(owner), guild_name, 0).unwrap();
+        assert!(<Guild>::admins(guild_id).is_empty());
+    });
+}
+
+#[test]
+fn guild_metadata_update() {
+    new_test_ext().execute_with(|| {
+        let owner = 0;
+        let admin = 1;
+        let guild_name = [0u8; 32];
+        let invalid_name = [100u8; 32];
+        let max_serialized_len =
+            <TestRuntime as pallet_guild::Config>::MaxSerializedLen::get() as usize;
+        let new_metadata = vec![1u8; max_serialized_len];
+
+        dummy_guild(owner, guild_name);
+        <Guild>::add_admin(RuntimeOrigin::signed(owner), guild_name, admin).unwrap();
+
+        let failing_transactions = vec![
+            (
+                <Guild>::update_guild_metadata(RuntimeOrigin::none(), guild_name, vec![]),
+                "BadOrigin",
+            ),
+            (
+                <Guild>::update_guild_metadata(RuntimeOrigin::signed(admin), guild_name, vec![]),
+                "BadOrigin",
+            ),
+            (
+                <Guild>::update_guild_metadata(RuntimeOrigin::signed(owner), invalid_name, vec![]),
+                "GuildDoesNotExist",
+            ),
+            (
+                <Guild>::update_guild_metadata(
+                    RuntimeOrigin::signed(owner),
+                    guild_name,
+                    vec![0u8; max_serialized_len + 1],
+                ),
+                "MaxSerializedLenExceeded",
+            ),
+        ];
+
+        for (tx, raw_error_msg) in failing_transactions {
+            assert_eq!(error_msg(tx.unwrap_err()), raw_error_msg);
+        }
+
+        let guild_id = <Guild>::guild_id(guild_name).unwrap();
+        assert_eq!(
+            <Guild>::guild(guild_id).unwrap().metadata.as_slice(),
+            METADATA
+        );
+
+        <Guild>::update_guild_metadata(
+            RuntimeOrigin::signed(owner),
+            guild_name,
+            new_metadata.clone(),
+        )
+        .unwrap();
+        assert_eq!(
+            last_event(),
+            GuildEvent::GuildMetadataUpdated(owner, guild_name)
+        );
+        assert_eq!(
+            <Guild>::guild(guild_id).unwrap().metadata.to_vec(),
+            new_metadata
+        );
+    });
+}
+
+#[test]
+fn guild_and_role_deposits() {
+    new_test_ext().execute_with(|| {
+        let owner = 0;
+        let admin = 1;
+        let poor = 2;
+        let guild_name = [0u8; 32];
+        let role_names = [[1u8; 32], [2u8; 32]];
+        let allowlist = vec![Identity::Address20([0u8; 20]); 2];
+        let allowlist_bytes = allowlist.encode().len() as u64;
+
+        GuildDeposit::set(100);
+        RoleDeposit::set(10);
+        AllowlistByteDeposit::set(1);
+        <Balances as Currency<AccountId>>::make_free_balance_be(&owner, 110);
+        <Balances as Currency<AccountId>>::make_free_balance_be(&admin, 100);
+
+        assert_eq!(
+            error_msg(
+                <Guild>::create_guild(RuntimeOrigin::signed(poor), guild_name, vec![]).unwrap_err()
+            ),
+            "InsufficientBalance"
+        );
+
+        dummy_guild(owner, guild_name);
+        let guild_id = <Guild>::guild_id(guild_name).unwrap();
+        assert_eq!(<Guild>::deposit(guild_id), Some((owner, 100)));
+        assert_eq!(Balances::reserved_balance(owner), 100);
+
+        <Guild>::create_free_role(RuntimeOrigin::signed(owner), guild_name, role_names[0]).unwrap();
+        assert_eq!(Balances::reserved_balance(owner), 110);
+        assert_eq!(
+            error_msg(
+                <Guild>::create_role_with_allowlist(
+                    RuntimeOrigin::signed(owner),
+                    guild_name,
+                    role_names[1],
+                    allowlist.clone(),
+                    FilterLogic::And,
+                    None,
+                )
+                .unwrap_err()
+            ),
+            "InsufficientBalance"
+        );
+
+        // admins pay for the roles they create
+        <Guild>::add_admin(RuntimeOrigin::signed(owner), guild_name, admin).unwrap();
+        <Guild>::create_role_with_allowlist(
+            RuntimeOrigin::signed(admin),
+            guild_name,
+            role_names[1],
+            allowlist,
+            FilterLogic::And,
+            None,
+        )
+        .unwrap();
+        let role_id = <Guild>::role_id(guild_id, role_names[1]).unwrap();
+        assert_eq!(
+            <Guild>::deposit(role_id),
+            Some((admin, 10 + allowlist_bytes))
+        );
+        assert_eq!(Balances::reserved_balance(admin), 10 + allowlist_bytes);
+
+        // the owner cannot afford any deposit, but the role's deposit is
+        // settled with its depositor
+        let longer_allowlist = vec![Identity::Address20([0u8; 20]); 3];
+        let longer_allowlist_bytes = longer_allowlist.encode().len() as u64;
+        <Guild>::update_allowlist(
+            RuntimeOrigin::signed(owner),
+            guild_name,
+            role_names[1],
+            longer_allowlist,
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            <Guild>::deposit(role_id),
+            Some((admin, 10 + longer_allowlist_bytes))
+        );
+        assert_eq!(
+            Balances::reserved_balance(admin),
+            10 + longer_allowlist_bytes
+        );
+        assert_eq!(Balances::reserved_balance(owner), 110);
+
+        // the deposit follows the allowlist of the filter and is always
+        // settled with the depositor
+        let parent_filter = GuildFilter {
+            name: guild_name,
+            role: Some(role_names[0]),
+        };
+        <Guild>::update_role_filter(
+            RuntimeOrigin::signed(owner),
+            guild_name,
+            role_names[1],
+            Some(Filter::Guild(parent_filter, FilterLogic::And)),
+        )
+        .unwrap();
+        assert_eq!(<Guild>::deposit(role_id), Some((admin, 10)));
+        assert_eq!(Balances::reserved_balance(admin), 10);
+
+        let max_identity_len = Identity::Other([0u8; 64]).encoded_size() as u64;
+        <Guild>::update_role_filter(
+            RuntimeOrigin::signed(owner),
+            guild_name,
+            role_names[1],
+            Some(Filter::Allowlist(Default::default(), FilterLogic::And, 1)),
+        )
+        .unwrap();
+        assert_eq!(
+            <Guild>::deposit(role_id),
+            Some((admin, 10 + max_identity_len))
+        );
+        assert_eq!(Balances::reserved_balance(admin), 10 + max_identity_len);
+
+        // deposits are refunded to the depositor
+        <Guild>::delete_role(RuntimeOrigin::signed(owner), guild_name, role_names[1], 0).unwrap();
+        assert!(<Guild>::deposit(role_id).is_none());
+        assert_eq!(Balances::reserved_balance(admin), 0);
+        assert_eq!(Balances::free_balance(admin), 100);
+
+        <Guild>::delete_guild(RuntimeOrigin::signed(owner), guild_name, 0).unwrap();
+        assert!(<Guild>::deposit(guild_id).is_none());
+        assert_eq!(Balances::reserved_balance(owner), 0);
+        assert_eq!(Balances::free_balance(owner), 110);
+    });
+}
+
+#[test]
+fn allowlist_updates() {
+    let owner = 0;
+    let user_1 = 1;
+    let user_2 = 2;
+    let guild_name = [0u8; 32];
+    let role_name = [1u8; 32];
+    let free_role_name = [2u8; 32];
+    let mut ext = new_test_ext();
+    let (id_1, signature_1) = ext.execute_with(|| dummy_ecdsa_id_with_auth(user_1, [1u8; 32]));
+    let (id_2, signature_2) = ext.execute_with(|| dummy_ecdsa_id_with_auth(user_2, [2u8; 32]));
+    let allowlist = vec![id_1, Identity::Address20([9u8; 20])];
+    let mut extended_allowlist = allowlist.clone();
+    extended_allowlist.push(id_2);
+    let updated_allowlist = vec![id_2];
+    let mut role_id = Default::default();
+
+    ext.execute_with(|| {
+        dummy_guild(owner, guild_name);
+        <Guild>::create_free_role(RuntimeOrigin::signed(owner), guild_name, free_role_name)
+            .unwrap();
+        <Guild>::create_role_with_allowlist(
+            RuntimeOrigin::signed(owner),
+            guild_name,
+            role_name,
+            allowlist.clone(),
+            FilterLogic::And,
+            None,
+        )
+        .unwrap();
+        let guild_id = <Guild>::guild_id(guild_name).unwrap();
+        role_id = <Guild>::role_id(guild_id, role_name).unwrap();
+
+        for (user, id, signature) in [(user_1, id_1, signature_1), (user_2, id_2, signature_2)] {
+            <Guild>::register(
+                RuntimeOrigin::signed(user),
+                IdentityWithAuth::Ecdsa(id, signature),
+                0,
+                EXPIRES_AT,
+            )
+            .unwrap();
+        }
+        let proof = MerkleProof::new(&allowlist, 0, 0);
+        <Guild>::join(
+            RuntimeOrigin::signed(user_1),
+            guild_name,
+            role_name,
+            Some(proof),
+        )
+        .unwrap();
+
+        let failing_transactions = vec![
+            (
+                <Guild>::update_allowlist(
+                    RuntimeOrigin::signed(user_1),
+                    guild_name,
+                    role_name,
+                    updated_allowlist.clone(),
+                    None,
+                ),
+                "BadOrigin",
+            ),
+            (
+                <Guild>::update_allowlist(
+                    RuntimeOrigin::signed(owner),
+                    guild_name,
+                    role_name,
+                    vec![],
+                    None,
+                ),
+                "InvalidAllowlistLen",
+            ),
+            (
+                <Guild>::update_allowlist(
+                    RuntimeOrigin::signed(owner),
+                    guild_name,
+                    free_role_name,
+                    updated_allowlist.clone(),
+                    None,
+                ),
+                "AllowlistDoesNotExist",
+            ),
+            (
+                <Guild>::update_allowlist(
+                    RuntimeOrigin::signed(owner),
+                    guild_name,
+                    role_name,
+                    updated_allowlist.clone(),
+                    Some(0),
+                ),
+                "MaxMembersExceeded",
+            ),
+            (
+                <Guild>::extend_allowlist(
+                    RuntimeOrigin::signed(owner),
+                    guild_name,
+                    role_name,
+                    allowlist.clone(),
+                    vec![],
+                ),
+                "InvalidAllowlistLen",
+            ),
+            (
+                <Guild>::extend_allowlist(
+                    RuntimeOrigin::signed(owner),
+                    guild_name,
+                    role_name,
+                    allowlist.clone(),
+                    vec![
+                        id_2;
+                        <TestRuntime as pallet_guild::Config>::MaxAllowlistLen::get() as usize - 1
+                    ],
+                ),
+                "InvalidAllowlistLen",
+            ),
+            (
+                <Guild>::extend_allowlist(
+                    RuntimeOrigin::signed(owner),
+                    guild_name,
+                    role_name,
+                    updated_allowlist.clone(),
+                    vec![id_2],
+                ),
+                "AllowlistMismatch",
+            ),
+        ];
+
+        for (tx, raw_error_msg) in failing_transactions {
+            assert_eq!(error_msg(tx.unwrap_err()), raw_error_msg);
+        }
+
+        // extending the list lets new identities join
+        <Guild>::extend_allowlist(
+            RuntimeOrigin::signed(owner),
+            guild_name,
+            role_name,
+            allowlist.clone(),
+            vec![id_2],
+        )
+        .unwrap();
+        assert_eq!(
+            last_event(),
+            GuildEvent::RoleUpdated(owner, guild_name, role_name, 1)
+        );
+        assert_eq!(
+            <Guild>::role(role_id).unwrap().filter,
+            Some(Filter::allowlist(&extended_allowlist, FilterLogic::And))
+        );
+        let proof = MerkleProof::new(&extended_allowlist, 2, 0);
+        <Guild>::join(
+            RuntimeOrigin::signed(user_2),
+            guild_name,
+            role_name,
+            Some(proof),
+        )
+        .unwrap();
+        assert_eq!(<Guild>::member(role_id, user_1), Some(1));
+        assert_eq!(<Guild>::member(role_id, user_2), Some(1));
+
+        // replacing the list strips members that are no longer on it
+        <Guild>::update_allowlist(
+            RuntimeOrigin::signed(owner),
+            guild_name,
+            role_name,
+            updated_allowlist.clone(),
+            Some(2),
+        )
+        .unwrap();
+        assert_eq!(
+            last_event(),
+            GuildEvent::RoleUpdated(owner, guild_name, role_name, 2)
+        );
+        assert_eq!(
+            <Guild>::role(role_id).unwrap().filter,
+            Some(Filter::allowlist(&updated_allowlist, FilterLogic::And))
+        );
+        assert!(<Guild>::member(role_id, user_1).is_none());
+        assert_eq!(<Guild>::member(role_id, user_2), Some(1));
+    });
+
+    ext.persist_offchain_overlay();
+    assert_eq!(
+        ext.offchain_db()
+            .get(&gn_common::offchain_allowlist_key(role_id.as_ref())),
+        Some(updated_allowlist.encode())
+    );
+}
+
+#[test]
+fn role_with_allowlist_root() {
+    let owner = 0;
+    let user = 1;
+    let guild_name = [0u8; 32];
+    let role_name = [1u8; 32];
+    let free_role_name = [2u8; 32];
+    let mut ext = new_test_ext();
+    let (id, signature) = ext.execute_with(|| dummy_ecdsa_id_with_auth(user, [1u8; 32]));
+    let mut allowlist = (0..5)
+        .map(|i| Identity::Address20([i; 20]))
+        .collect::<Vec<_>>();
+    allowlist.push(id);
+    let filter = Filter::<Vec<GuildFilter>>::allowlist(&allowlist, FilterLogic::And);
+    let Filter::Allowlist(root, _, _) = filter else {
+        unreachable!()
+    };
+    let n_leaves = allowlist.len() as u32;
+    let chunk_len = <TestRuntime as pallet_guild::Config>::MaxAllowlistLen::get() as usize;
+    let max_leaves =
+        chunk_len as u32 * <TestRuntime as pallet_guild::Config>::MaxAllowlistChunks::get();
+    let mut role_id = Default::default();
+
+    ext.execute_with(|| {
+        dummy_guild(owner, guild_name);
+        <Guild>::create_free_role(RuntimeOrigin::signed(owner), guild_name, free_role_name)
+            .unwrap();
+
+        let failing_transactions = vec![
+            (
+                <Guild>::create_role_with_allowlist_root(
+                    RuntimeOrigin::signed(user),
+                    guild_name,
+                    role_name,
+                    root,
+                    n_leaves,
+                    FilterLogic::And,
+                    None,
+                ),
+                "BadOrigin",
+            ),
+            (
+                <Guild>::create_role_with_allowlist_root(
+                    RuntimeOrigin::signed(owner),
+                    guild_name,
+                    role_name,
+                    root,
+                    0,
+                    FilterLogic::And,
+                    None,
+                ),
+                "InvalidAllowlistLen",
+            ),
+            (
+                <Guild>::create_role_with_allowlist_root(
+                    RuntimeOrigin::signed(owner),
+                    guild_name,
+                    role_name,
+                    root,
+                    max_leaves + 1,
+                    FilterLogic::And,
+                    None,
+                ),
+                "InvalidAllowlistLen",
+            ),
+            (
+                <Guild>::upload_allowlist_chunk(
+                    RuntimeOrigin::signed(owner),
+                    guild_name,
+                    free_role_name,
+                    0,
+                    allowlist[..chunk_len].to_vec(),
+                    MerkleProof::chunk(&allowlist, 0, chunk_len),
+                ),
+                "AllowlistDoesNotExist",
+            ),
+        ];
+        for (tx, raw_error_msg) in failing_transactions {
+            assert_eq!(error_msg(tx.unwrap_err()), raw_error_msg);
+        }
+
+        <Guild>::create_role_with_allowlist_root(
+            RuntimeOrigin::signed(owner),
+            guild_name,
+            role_name,
+            root,
+            n_leaves,
+            FilterLogic::And,
+            None,
+        )
+        .unwrap();
+        let guild_id = <Guild>::guild_id(guild_name).unwrap();
+        role_id = <Guild>::role_id(guild_id, role_name).unwrap();
+        assert_eq!(
+            <Guild>::role(role_id).unwrap().filter,
+            Some(Filter::Allowlist(root, FilterLogic::And, n_leaves))
+        );
+
+        let failing_transactions = vec![
+            (
+                <Guild>::upload_allowlist_chunk(
+                    RuntimeOrigin::signed(user),
+                    guild_name,
+                    role_name,
+                    0,
+                    allowlist[..chunk_len].to_vec(),
+                    MerkleProof::chunk(&allowlist, 0, chunk_len),
+                ),
+                "BadOrigin",
+            ),
+            (
+                <Guild>::upload_allowlist_chunk(
+                    RuntimeOrigin::signed(owner),
+                    guild_name,
+                    role_name,
+                    0,
+                    allowlist[..chunk_len - 1].to_vec(),
+                    MerkleProof::chunk(&allowlist, 0, chunk_len),
+                ),
+                "InvalidAllowlistLen",
+            ),
+            (
+                <Guild>::upload_allowlist_chunk(
+                    RuntimeOrigin::signed(owner),
+                    guild_name,
+                    role_name,
+                    1,
+                    allowlist[chunk_len - 1..].to_vec(),
+                    MerkleProof::chunk(&allowlist, 1, chunk_len),
+                ),
+                "InvalidAllowlistLen",
+            ),
+            (
+                <Guild>::upload_allowlist_chunk(
+                    RuntimeOrigin::signed(owner),
+                    guild_name,
+                    role_name,
+                    2,
+                    allowlist[..1].to_vec(),
+                    MerkleProof::chunk(&allowlist, 2, chunk_len),
+                ),
+                "InvalidAllowlistLen",
+            ),
+            (
+                <Guild>::upload_allowlist_chunk(
+                    RuntimeOrigin::signed(owner),
+                    guild_name,
+                    role_name,
+                    1,
+                    allowlist[..2].to_vec(),
+                    MerkleProof::chunk(&allowlist, 1, chunk_len),
+                ),
+                "AllowlistMismatch",
+            ),
+            (
+                <Guild>::upload_allowlist_chunk(
+                    RuntimeOrigin::signed(owner),
+                    guild_name,
+                    role_name,
+                    0,
+                    allowlist[..chunk_len].to_vec(),
+                    MerkleProof::chunk(&allowlist, 1, chunk_len),
+                ),
+                "AllowlistMismatch",
+            ),
+        ];
+        for (tx, raw_error_msg) in failing_transactions {
+            assert_eq!(error_msg(tx.unwrap_err()), raw_error_msg);
+        }
+
+        for (chunk_index, chunk) in allowlist.chunks(chunk_len).enumerate() {
+            <Guild>::upload_allowlist_chunk(
+                RuntimeOrigin::signed(owner),
+                guild_name,
+                role_name,
+                chunk_index as u32,
+                chunk.to_vec(),
+                MerkleProof::chunk(&allowlist, chunk_index, chunk_len),
+            )
+            .unwrap();
+            assert_eq!(
+                last_event(),
+                GuildEvent::AllowlistWritten(gn_common::offchain_allowlist_chunk_key(
+                    role_id.as_ref(),
+                    chunk_index as u32
+                ))
+            );
+        }
+
+        // proofs are verified against the root as usual
+        <Guild>::register(
+            RuntimeOrigin::signed(user),
+            IdentityWithAuth::Ecdsa(id, signature),
+            0,
+            EXPIRES_AT,
+        )
+        .unwrap();
+        let proof = MerkleProof::new(&allowlist, allowlist.len() - 1, 0);
+        <Guild>::join(
+            RuntimeOrigin::signed(user),
+            guild_name,
+            role_name,
+            Some(proof),
+        )
+        .unwrap();
+        assert_eq!(<Guild>::member(role_id, user), Some(1));
+    });
+
+    ext.persist_offchain_overlay();
+    for (chunk_index, chunk) in allowlist.chunks(chunk_len).enumerate() {
+        let chunk_key =
+            gn_common::offchain_allowlist_chunk_key(role_id.as_ref(), chunk_index as u32);
+        assert_eq!(ext.offchain_db().get(&chunk_key), Some(chunk.encode()));
+    }
+
+    // chunks are cleared together with the role
+    ext.execute_with(|| {
+        <Guild>::delete_role(RuntimeOrigin::signed(owner), guild_name, role_name, 1).unwrap();
+    });
+    ext.persist_offchain_overlay();
+    for chunk_index in 0..allowlist.chunks(chunk_len).len() {
+        let chunk_key =
+            gn_common::offchain_allowlist_chunk_key(role_id.as_ref(), chunk_index as u32);
+        assert!(ext.offchain_db().get(&chunk_key).is_none());
+    }
+}
+
+#[test]
+fn allowlist_chunks_are_bound_to_their_index() {
+    new_test_ext().execute_with(|| {
+        let owner = 0;
+        let guild_name = [0u8; 32];
+        let role_name = [1u8; 32];
+        let chunk_len = <TestRuntime as pallet_guild::Config>::MaxAllowlistLen::get() as usize;
+        let allowlist = (0..3 * chunk_len as u8)
+            .map(|i| Identity::Address20([i; 20]))
+            .collect::<Vec<_>>();
+        let Filter::Allowlist(root, _, n_leaves) =
+            Filter::<Vec<GuildFilter>>::allowlist(&allowlist, FilterLogic::And) else {
+            unreachable!()
+        };
+
+        dummy_guild(owner, guild_name);
+        <Guild>::create_role_with_allowlist_root(
+            RuntimeOrigin::signed(owner),
+            guild_name,
+            role_name,
+            root,
+            n_leaves,
+            FilterLogic::And,
+            None,
+        )
+        .unwrap();
+
+        let chunks = allowlist.chunks(chunk_len).collect::<Vec<_>>();
+        let proof_0 = MerkleProof::chunk(&allowlist, 0, chunk_len);
+        let proof_1 = MerkleProof::chunk(&allowlist, 1, chunk_len);
+        let failing_transactions = vec![
+            // valid chunk and proof under the index of its sibling
+            <Guild>::upload_allowlist_chunk(
+                RuntimeOrigin::signed(owner),
+                guild_name,
+                role_name,
+                1,
+                chunks[0].to_vec(),
+                proof_0.clone(),
+            ),
+            // proof moved to the index of the sibling
+            <Guild>::upload_allowlist_chunk(
+                RuntimeOrigin::signed(owner),
+                guild_name,
+                role_name,
+                0,
+                chunks[1].to_vec(),
+                MerkleProof {
+                    leaf_index: 0,
+                    ..proof_1.clone()
+                },
+            ),
+        ];
+        for tx in failing_transactions {
+            assert_eq!(error_msg(tx.unwrap_err()), "AllowlistMismatch");
+        }
+
+        for (chunk_index, proof) in [proof_0, proof_1].into_iter().enumerate() {
+            <Guild>::upload_allowlist_chunk(
+                RuntimeOrigin::signed(owner),
+                guild_name,
+                role_name,
+                chunk_index as u32,
+                chunks[chunk_index].to_vec(),
+                proof,
+            )
+            .unwrap();
+        }
+    });
+}
+
+#[test]
+fn guild_queries() {
+    new_test_ext().execute_with(|| {
+        let owner = 0;
+        let users = [1, 2, 3];
+        let guild_name = [0u8; 32];
+        let free_role = [1u8; 32];
+        let composite_role = [2u8; 32];
+        let missing_role = [3u8; 32];
+        let composite = gn_common::filter::Composite {
+            guilds: vec![GuildFilter {
+                name: guild_name,
+                role: Some(free_role),
+            }],
+            allowlist: None,
+            logic: FilterLogic::Or,
+        };
+        let requirements = (vec![vec![1, 2, 3], vec![4, 5]], vec![0]);
+
+        assert!(<Guild>::guild_by_name(&guild_name).is_none());
+        assert!(<Guild>::role_names(&guild_name).is_empty());
+
+        dummy_guild(owner, guild_name);
+        <Guild>::create_free_role(RuntimeOrigin::signed(owner), guild_name, free_role).unwrap();
+        <Guild>::create_role_with_composite_filter(
+            RuntimeOrigin::signed(owner),
+            guild_name,
+            composite_role,
+            composite.clone(),
+            FilterLogic::And,
+            Some(requirements.clone()),
+        )
+        .unwrap();
+
+        let guild = <Guild>::guild_by_name(&guild_name).unwrap();
+        assert_eq!(guild.name, guild_name);
+        assert_eq!(guild.owner, owner);
+        assert_eq!(guild.metadata, METADATA);
+        assert_eq!(guild.roles, vec![free_role, composite_role]);
+        assert_eq!(
+            <Guild>::role_names(&guild_name),
+            vec![free_role, composite_role]
+        );
+
+        let role = <Guild>::role_by_name(&guild_name, &free_role).unwrap();
+        assert!(role.filter.is_none());
+        assert!(role.requirements.is_none());
+        let role = <Guild>::role_by_name(&guild_name, &composite_role).unwrap();
+        assert_eq!(
+            role.filter,
+            Some(Filter::Composite(composite, FilterLogic::And))
+        );
+        assert_eq!(role.requirements, Some(requirements));
+        assert!(<Guild>::role_by_name(&guild_name, &missing_role).is_none());
+
+        for (i, &user) in users.iter().enumerate() {
+            let (address, signature) = dummy_ecdsa_id_with_auth(user, [i as u8 + 1; 32]);
+            <Guild>::register(
+                RuntimeOrigin::signed(user),
+                IdentityWithAuth::Ecdsa(address, signature),
+                2,
+                EXPIRES_AT,
+            )
+            .unwrap();
+            <Guild>::join(RuntimeOrigin::signed(user), guild_name, free_role, None).unwrap();
+            assert!(<Guild>::is_member_of(&guild_name, &free_role, &user));
+            assert!(!<Guild>::is_member_of(&guild_name, &composite_role, &user));
+            assert!(!<Guild>::is_member_of(&guild_name, &missing_role, &user));
+        }
+
+        let (address, signature) = dummy_ecdsa_id_with_auth(users[0], [9u8; 32]);
+        <Guild>::register(
+            RuntimeOrigin::signed(users[0]),
+            IdentityWithAuth::Ecdsa(address, signature),
+            0,
+            EXPIRES_AT,
+        )
+        .unwrap();
+        let identities = <Guild>::identities_of(&users[0]);
+        assert_eq!(
+            identities
+                .iter()
+                .map(|(index, _)| *index)
+                .collect::<Vec<_>>(),
+            vec![0, 2]
+        );
+        assert_eq!(identities[0].1, address);
+        assert!(<Guild>::identities_of(&owner).is_empty());
+
+        // pages don't overlap and together they contain every member
+        let first_page = <Guild>::members_page(&guild_name, &free_role, None, 2);
+        assert_eq!(first_page.len(), 2);
+        let second_page =
+            <Guild>::members_page(&guild_name, &free_role, first_page.last().copied(), 2);
+        assert_eq!(second_page.len(), 1);
+        let mut members = [first_page, second_page].concat();
+        members.sort();
+        assert_eq!(members, users);
+        assert!(<Guild>::members_page(&guild_name, &missing_role, None, 10).is_empty());
     });
 }
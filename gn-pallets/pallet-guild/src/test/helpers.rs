@@ -1,10 +1,13 @@
 use super::*;
+use frame_support::traits::OnInitialize;
 use gn_common::identity::{eth_hash_message, recover_prehashed, EcdsaSignature};
 use sp_core::Pair as PairT;
 
 pub const METADATA: &[u8] =
     &[12u8; <TestRuntime as pallet_guild::Config>::MaxSerializedLen::get() as usize];
 
+pub const EXPIRES_AT: u64 = 1000;
+
 pub fn last_event() -> pallet_guild::Event<TestRuntime> {
     System::events()
         .into_iter()
@@ -27,6 +30,13 @@ pub fn error_msg<'a>(error: DispatchError) -> &'a str {
     }
 }
 
+pub fn run_to_block(n: u64) {
+    while System::block_number() < n {
+        System::set_block_number(System::block_number() + 1);
+        <Guild as OnInitialize<u64>>::on_initialize(System::block_number());
+    }
+}
+
 pub fn dummy_answer(
     result: Vec<u8>,
     requester: AccountId,
@@ -47,13 +57,22 @@ pub fn dummy_guild(signer: AccountId, guild_name: GuildName) {
     let guild = <Guild>::guild(guild_id).unwrap();
     assert_eq!(guild.name, guild_name);
     assert_eq!(guild.owner, signer);
-    assert_eq!(guild.metadata, METADATA);
+    assert_eq!(guild.metadata.as_slice(), METADATA);
     assert!(guild.roles.is_empty());
 }
 
+pub fn verification_msg(user: AccountId) -> String {
+    gn_common::utils::verification_msg(
+        user,
+        <System>::block_hash(0),
+        <Guild>::registration_nonce(user),
+        EXPIRES_AT,
+    )
+}
+
 pub fn dummy_ecdsa_id_with_auth(user: AccountId, seed: [u8; 32]) -> (Identity, EcdsaSignature) {
     let keypair_ecdsa = sp_core::ecdsa::Pair::from_seed_slice(&seed).unwrap();
-    let msg = gn_common::utils::verification_msg(user);
+    let msg = verification_msg(user);
     let ecdsa_sig = EcdsaSignature(keypair_ecdsa.sign(msg.as_ref()).0);
     let ecdsa_pubkey = recover_prehashed(eth_hash_message(&msg), &ecdsa_sig.0).unwrap();
     let ecdsa_address: [u8; 20] = sp_core::keccak_256(&ecdsa_pubkey.serialize_uncompressed()[1..])
@@ -1,5 +1,7 @@
 use super::*;
-use gn_common::filter::{Guild as GuildFilter, Logic as FilterLogic};
+use gn_common::filter::{
+    Composite as CompositeFilter, Filter, Guild as GuildFilter, Logic as FilterLogic,
+};
 use gn_common::merkle::Proof as MerkleProof;
 
 #[test]
@@ -48,6 +50,7 @@ fn join_and_leave_free_role() {
             RuntimeOrigin::signed(user),
             IdentityWithAuth::Ecdsa(address, signature),
             0,
+            EXPIRES_AT,
         )
         .unwrap();
         <Guild>::join(RuntimeOrigin::signed(user), guild_name, role_name, None).unwrap();
@@ -58,7 +61,7 @@ fn join_and_leave_free_role() {
 
         let guild_id = <Guild>::guild_id(guild_name).unwrap();
         let role_id = <Guild>::role_id(guild_id, role_name).unwrap();
-        assert_eq!(<Guild>::member(role_id, user), Some(true));
+        assert_eq!(<Guild>::member(role_id, user), Some(1));
 
         <Guild>::leave(RuntimeOrigin::signed(user), guild_name, role_name).unwrap();
         assert_eq!(
@@ -93,6 +96,7 @@ fn join_and_leave_role_with_allowlist() {
             RuntimeOrigin::signed(user_1),
             IdentityWithAuth::Ecdsa(address, signature),
             id_index,
+            EXPIRES_AT,
         )
         .unwrap();
         assert_eq!(last_event(), GuildEvent::IdRegistered(user_1, id_index));
@@ -103,6 +107,7 @@ fn join_and_leave_role_with_allowlist() {
             RuntimeOrigin::signed(user_2),
             IdentityWithAuth::Ecdsa(address, signature),
             id_index,
+            EXPIRES_AT,
         )
         .unwrap();
         assert_eq!(last_event(), GuildEvent::IdRegistered(user_2, id_index));
@@ -134,12 +139,17 @@ fn join_and_leave_role_with_allowlist() {
 
     let proof_with_invalid_path = MerkleProof {
         path: vec![],
-        id_index,
+        ..proof.clone()
+    };
+
+    let proof_with_invalid_leaf_index = MerkleProof {
+        leaf_index: 0,
+        ..proof.clone()
     };
 
     let proof_with_invalid_id_index = MerkleProof {
-        path: proof.path.clone(),
         id_index: id_index + 1,
+        ..proof.clone()
     };
 
     ext.execute_with(|| {
@@ -157,6 +167,15 @@ fn join_and_leave_role_with_allowlist() {
                 ),
                 "AccessDenied",
             ),
+            (
+                <Guild>::join(
+                    RuntimeOrigin::signed(user_1),
+                    guild_name,
+                    role_name,
+                    Some(proof_with_invalid_leaf_index),
+                ),
+                "AccessDenied",
+            ),
             (
                 <Guild>::join(
                     RuntimeOrigin::signed(user_1),
@@ -193,7 +212,7 @@ fn join_and_leave_role_with_allowlist() {
             GuildEvent::RoleAssigned(user_1, guild_name, role_name)
         );
 
-        assert_eq!(<Guild>::member(role_id, user_1), Some(true));
+        assert_eq!(<Guild>::member(role_id, user_1), Some(1));
 
         <Guild>::leave(RuntimeOrigin::signed(user_1), guild_name, role_name).unwrap();
         assert_eq!(
@@ -258,6 +277,7 @@ fn join_and_leave_role_with_filter() {
             RuntimeOrigin::signed(user_1),
             IdentityWithAuth::Ecdsa(address, signature),
             0,
+            EXPIRES_AT,
         )
         .unwrap();
         let (address, signature) = dummy_ecdsa_id_with_auth(user_2, [2u8; 32]);
@@ -265,6 +285,7 @@ fn join_and_leave_role_with_filter() {
             RuntimeOrigin::signed(user_2),
             IdentityWithAuth::Ecdsa(address, signature),
             0,
+            EXPIRES_AT,
         )
         .unwrap();
 
@@ -359,6 +380,7 @@ fn join_and_leave_unfiltered_role() {
             RuntimeOrigin::signed(user),
             IdentityWithAuth::Other(Identity::Other([0u8; 64]), [0u8; 64]),
             0,
+            EXPIRES_AT,
         )
         .unwrap();
 
@@ -471,6 +493,7 @@ fn role_with_filtered_requirements() {
             RuntimeOrigin::signed(user),
             IdentityWithAuth::Other(Identity::Other([0u8; 64]), [0u8; 64]),
             0,
+            EXPIRES_AT,
         )
         .unwrap();
 
@@ -489,6 +512,7 @@ fn role_with_filtered_requirements() {
             RuntimeOrigin::signed(owner),
             IdentityWithAuth::Ecdsa(address, signature),
             0,
+            EXPIRES_AT,
         )
         .unwrap();
         assert_eq!(last_event(), GuildEvent::IdRegistered(owner, 0));
@@ -636,3 +660,1539 @@ fn role_with_filtered_requirements() {
         assert!(<Guild>::member(role_id_1, user).is_none());
     });
 }
+
+#[test]
+fn deregistered_member_can_be_stripped() {
+    new_test_ext().execute_with(|| {
+        let owner = 0;
+        let user = 1;
+        let operator = 2;
+        let guild_name = [0u8; 32];
+        let free_role_name = [0u8; 32];
+        let gated_role_name = [1u8; 32];
+        let mut request_id = 0;
+
+        <Oracle>::register_operator(RuntimeOrigin::root(), operator).unwrap();
+        <Oracle>::activate_operator(RuntimeOrigin::signed(operator)).unwrap();
+
+        dummy_guild(owner, guild_name);
+        <Guild>::create_free_role(RuntimeOrigin::signed(owner), guild_name, free_role_name)
+            .unwrap();
+        <Guild>::create_unfiltered_role(
+            RuntimeOrigin::signed(owner),
+            guild_name,
+            gated_role_name,
+            (vec![], vec![]),
+        )
+        .unwrap();
+        let guild_id = <Guild>::guild_id(guild_name).unwrap();
+        let free_role_id = <Guild>::role_id(guild_id, free_role_name).unwrap();
+        let gated_role_id = <Guild>::role_id(guild_id, gated_role_name).unwrap();
+
+        let (address, signature) = dummy_ecdsa_id_with_auth(user, [1u8; 32]);
+        <Guild>::register(
+            RuntimeOrigin::signed(user),
+            IdentityWithAuth::Ecdsa(address, signature),
+            0,
+            EXPIRES_AT,
+        )
+        .unwrap();
+        <Guild>::join(
+            RuntimeOrigin::signed(user),
+            guild_name,
+            free_role_name,
+            None,
+        )
+        .unwrap();
+        <Guild>::join(
+            RuntimeOrigin::signed(user),
+            guild_name,
+            gated_role_name,
+            None,
+        )
+        .unwrap();
+        <Oracle>::callback(
+            RuntimeOrigin::signed(operator),
+            request_id,
+            vec![u8::from(true)],
+        )
+        .unwrap();
+        request_id += 1;
+        assert!(<Guild>::member(gated_role_id, user).is_some());
+
+        // user removes their only identity
+        <Guild>::unregister(RuntimeOrigin::signed(user), 0).unwrap();
+        assert_eq!(last_event(), GuildEvent::IdDeregistered(user, 0));
+
+        // memberships remain until they are re-checked in the next block
+        assert!(<Guild>::member(free_role_id, user).is_some());
+        assert!(<Guild>::member(gated_role_id, user).is_some());
+        assert_eq!(
+            <Guild>::scheduled_reverification((guild_name, gated_role_name), user),
+            Some(2)
+        );
+
+        // user can still leave roles
+        <Guild>::leave(RuntimeOrigin::signed(user), guild_name, free_role_name).unwrap();
+        assert!(<Guild>::member(free_role_id, user).is_none());
+
+        // unregistered users can still be checked by anyone
+        <Guild>::request_oracle_check(
+            RuntimeOrigin::signed(owner),
+            user,
+            guild_name,
+            gated_role_name,
+        )
+        .unwrap();
+        <Oracle>::callback(
+            RuntimeOrigin::signed(operator),
+            request_id,
+            vec![u8::from(false)],
+        )
+        .unwrap();
+        assert_eq!(
+            last_event(),
+            GuildEvent::RoleStripped(user, guild_name, gated_role_name)
+        );
+        assert!(<Guild>::member(gated_role_id, user).is_none());
+    });
+}
+
+#[test]
+fn allowlist_memberships_are_revoked_on_deregistration() {
+    new_test_ext().execute_with(|| {
+        let owner = 0;
+        let user = 1;
+        let guild_name = [0u8; 32];
+        let free_role_name = [0u8; 32];
+        let allowlist_role_name = [1u8; 32];
+        let composite_role_name = [2u8; 32];
+
+        let (address, signature) = dummy_ecdsa_id_with_auth(user, [1u8; 32]);
+        <Guild>::register(
+            RuntimeOrigin::signed(user),
+            IdentityWithAuth::Ecdsa(address, signature),
+            0,
+            EXPIRES_AT,
+        )
+        .unwrap();
+        let allowlist = vec![Identity::Address20([0u8; 20]), address];
+        let Filter::Allowlist(root, _, n_leaves) =
+            Filter::<Vec<GuildFilter>>::allowlist(&allowlist, FilterLogic::And) else {
+            unreachable!()
+        };
+
+        dummy_guild(owner, guild_name);
+        <Guild>::create_free_role(RuntimeOrigin::signed(owner), guild_name, free_role_name)
+            .unwrap();
+        <Guild>::create_role_with_allowlist(
+            RuntimeOrigin::signed(owner),
+            guild_name,
+            allowlist_role_name,
+            allowlist.clone(),
+            FilterLogic::And,
+            None,
+        )
+        .unwrap();
+        // the free role grants access on its own
+        <Guild>::create_role_with_composite_filter(
+            RuntimeOrigin::signed(owner),
+            guild_name,
+            composite_role_name,
+            CompositeFilter {
+                guilds: vec![GuildFilter {
+                    name: guild_name,
+                    role: Some(free_role_name),
+                }],
+                allowlist: Some((root, n_leaves)),
+                logic: FilterLogic::Or,
+            },
+            FilterLogic::And,
+            None,
+        )
+        .unwrap();
+        let guild_id = <Guild>::guild_id(guild_name).unwrap();
+        let role_ids = [free_role_name, allowlist_role_name, composite_role_name]
+            .map(|role_name| <Guild>::role_id(guild_id, role_name).unwrap());
+
+        let proof = MerkleProof::new(&allowlist, 1, 0);
+        for (role_name, proof) in [
+            (free_role_name, None),
+            (allowlist_role_name, Some(proof.clone())),
+            (composite_role_name, Some(proof)),
+        ] {
+            <Guild>::join(RuntimeOrigin::signed(user), guild_name, role_name, proof).unwrap();
+        }
+
+        // the allowlist proof might have been given for the removed identity
+        <Guild>::unregister(RuntimeOrigin::signed(user), 0).unwrap();
+        assert_eq!(last_event(), GuildEvent::IdDeregistered(user, 0));
+        assert!(System::events().iter().any(|record| record.event
+            == RuntimeEvent::Guild(GuildEvent::RoleStripped(
+                user,
+                guild_name,
+                allowlist_role_name
+            ))));
+        assert!(<Guild>::member(role_ids[0], user).is_some());
+        assert!(<Guild>::member(role_ids[1], user).is_none());
+        assert!(<Guild>::member(role_ids[2], user).is_some());
+        assert_eq!(<Guild>::member_count(role_ids[1]), 0);
+        assert_eq!(<Guild>::member_count(guild_id), 1);
+    });
+}
+
+#[test]
+fn time_limited_memberships() {
+    new_test_ext().execute_with(|| {
+        let owner = 0;
+        let users = [1, 2, 3, 4, 5];
+        let guild_name = [0u8; 32];
+        let role_name = [1u8; 32];
+        let validity = 10;
+
+        dummy_guild(owner, guild_name);
+        <Guild>::create_free_role(RuntimeOrigin::signed(owner), guild_name, role_name).unwrap();
+        let guild_id = <Guild>::guild_id(guild_name).unwrap();
+        let role_id = <Guild>::role_id(guild_id, role_name).unwrap();
+
+        for (i, &user) in users.iter().enumerate() {
+            let (address, signature) = dummy_ecdsa_id_with_auth(user, [i as u8 + 1; 32]);
+            <Guild>::register(
+                RuntimeOrigin::signed(user),
+                IdentityWithAuth::Ecdsa(address, signature),
+                0,
+                EXPIRES_AT,
+            )
+            .unwrap();
+        }
+
+        let failing_transactions = vec![
+            (
+                <Guild>::set_role_validity(
+                    RuntimeOrigin::signed(users[0]),
+                    guild_name,
+                    role_name,
+                    Some(validity),
+                ),
+                "BadOrigin",
+            ),
+            (
+                <Guild>::set_role_validity(
+                    RuntimeOrigin::signed(owner),
+                    guild_name,
+                    [100u8; 32],
+                    Some(validity),
+                ),
+                "RoleDoesNotExist",
+            ),
+            (
+                <Guild>::set_role_validity(
+                    RuntimeOrigin::signed(owner),
+                    guild_name,
+                    role_name,
+                    Some(0),
+                ),
+                "InvalidValidityPeriod",
+            ),
+        ];
+
+        for (tx, raw_error_msg) in failing_transactions {
+            assert_eq!(error_msg(tx.unwrap_err()), raw_error_msg);
+        }
+
+        <Guild>::set_role_validity(
+            RuntimeOrigin::signed(owner),
+            guild_name,
+            role_name,
+            Some(validity),
+        )
+        .unwrap();
+        assert_eq!(
+            last_event(),
+            GuildEvent::RoleUpdated(owner, guild_name, role_name, 1)
+        );
+        assert_eq!(<Guild>::role_validity(role_id), Some(validity));
+
+        // memberships granted in block 1 expire in block 11
+        <Guild>::join(RuntimeOrigin::signed(users[0]), guild_name, role_name, None).unwrap();
+        <Guild>::join(RuntimeOrigin::signed(users[1]), guild_name, role_name, None).unwrap();
+        assert_eq!(<Guild>::member(role_id, users[0]), Some(1));
+        assert_eq!(<Guild>::membership_expiries(1 + validity).len(), 2);
+        // expiries scheduled for block 11 are full, so they are postponed
+        // to the next block
+        <Guild>::join(RuntimeOrigin::signed(users[2]), guild_name, role_name, None).unwrap();
+        <Guild>::join(RuntimeOrigin::signed(users[3]), guild_name, role_name, None).unwrap();
+        assert_eq!(<Guild>::membership_expiries(2 + validity).len(), 2);
+        // but only by 'MaxScheduleDelay' blocks, after that the membership
+        // is granted without an expiry
+        <Guild>::join(RuntimeOrigin::signed(users[4]), guild_name, role_name, None).unwrap();
+        assert_eq!(<Guild>::member(role_id, users[4]), Some(1));
+        assert!(System::events().iter().any(|record| record.event
+            == RuntimeEvent::Guild(GuildEvent::ExpiryNotScheduled(
+                users[4],
+                guild_name,
+                role_name,
+                pallet_guild::Error::<TestRuntime>::MaxExpiriesPerBlockExceeded.into()
+            ))));
+
+        // the full queue doesn't prevent joining the roles of other guilds
+        let other_guild_name = [2u8; 32];
+        dummy_guild(owner, other_guild_name);
+        <Guild>::create_free_role(RuntimeOrigin::signed(owner), other_guild_name, role_name)
+            .unwrap();
+        <Guild>::set_role_validity(
+            RuntimeOrigin::signed(owner),
+            other_guild_name,
+            role_name,
+            Some(validity),
+        )
+        .unwrap();
+        <Guild>::join(
+            RuntimeOrigin::signed(users[0]),
+            other_guild_name,
+            role_name,
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            last_event(),
+            GuildEvent::RoleAssigned(users[0], other_guild_name, role_name)
+        );
+
+        // first user renews their membership in block 5
+        run_to_block(5);
+        <Guild>::join(RuntimeOrigin::signed(users[0]), guild_name, role_name, None).unwrap();
+        assert_eq!(<Guild>::member(role_id, users[0]), Some(5));
+
+        run_to_block(1 + validity);
+        assert_eq!(
+            last_event(),
+            GuildEvent::RoleStripped(users[1], guild_name, role_name)
+        );
+        assert!(<Guild>::member(role_id, users[1]).is_none());
+        // stale expiry of the renewed membership is skipped
+        assert_eq!(<Guild>::member(role_id, users[0]), Some(5));
+        assert!(<Guild>::membership_expiries(1 + validity).is_empty());
+
+        run_to_block(2 + validity);
+        assert_eq!(
+            last_event(),
+            GuildEvent::RoleStripped(users[3], guild_name, role_name)
+        );
+        assert!(<Guild>::member(role_id, users[2]).is_none());
+        assert!(<Guild>::member(role_id, users[3]).is_none());
+
+        run_to_block(5 + validity);
+        assert_eq!(
+            last_event(),
+            GuildEvent::RoleStripped(users[0], guild_name, role_name)
+        );
+        assert!(<Guild>::member(role_id, users[0]).is_none());
+
+        // memberships granted without a validity period don't expire
+        <Guild>::set_role_validity(RuntimeOrigin::signed(owner), guild_name, role_name, None)
+            .unwrap();
+        assert!(<Guild>::role_validity(role_id).is_none());
+        <Guild>::join(RuntimeOrigin::signed(users[2]), guild_name, role_name, None).unwrap();
+        run_to_block(100);
+        assert_eq!(<Guild>::member(role_id, users[2]), Some(5 + validity));
+        assert_eq!(<Guild>::member(role_id, users[4]), Some(1));
+    });
+}
+
+#[test]
+fn scheduled_reverification() {
+    new_test_ext().execute_with(|| {
+        let owner = 0;
+        let operator = 10;
+        let users = [1, 2, 3];
+        let guild_name = [0u8; 32];
+        let role_name = [1u8; 32];
+        let period = <TestRuntime as pallet_guild::Config>::ReverificationPeriod::get();
+
+        <Oracle>::register_operator(RuntimeOrigin::root(), operator).unwrap();
+        <Oracle>::activate_operator(RuntimeOrigin::signed(operator)).unwrap();
+
+        dummy_guild(owner, guild_name);
+        <Guild>::create_unfiltered_role(
+            RuntimeOrigin::signed(owner),
+            guild_name,
+            role_name,
+            (vec![], vec![]),
+        )
+        .unwrap();
+        let guild_id = <Guild>::guild_id(guild_name).unwrap();
+        let role_id = <Guild>::role_id(guild_id, role_name).unwrap();
+
+        // users join in block 1 (request ids 0, 1, 2)
+        for (i, &user) in users.iter().enumerate() {
+            let (address, signature) = dummy_ecdsa_id_with_auth(user, [i as u8 + 1; 32]);
+            <Guild>::register(
+                RuntimeOrigin::signed(user),
+                IdentityWithAuth::Ecdsa(address, signature),
+                0,
+                EXPIRES_AT,
+            )
+            .unwrap();
+            <Guild>::join(RuntimeOrigin::signed(user), guild_name, role_name, None).unwrap();
+            <Oracle>::callback(
+                RuntimeOrigin::signed(operator),
+                i as u64,
+                vec![u8::from(true)],
+            )
+            .unwrap();
+            assert!(<Guild>::member(role_id, user).is_some());
+        }
+        // only two checks fit into a block, the third one is postponed to
+        // the next block
+        assert_eq!(<Guild>::reverification_queue(1 + period).len(), 2);
+        assert_eq!(<Guild>::reverification_queue(2 + period).len(), 1);
+        assert_eq!(
+            <Guild>::scheduled_reverification((guild_name, role_name), users[2]),
+            Some(2 + period)
+        );
+
+        // checks are requested (request ids 3, 4, 5) and paid by the pallet
+        run_to_block(1 + period);
+        assert!(<Guild>::reverification_queue(1 + period).is_empty());
+        assert_eq!(<Guild>::reverification_queue(1 + 2 * period).len(), 2);
+        assert_eq!(
+            <Oracle>::request(3).unwrap().requester,
+            <Guild>::account_id()
+        );
+
+        run_to_block(2 + period);
+        assert!(<Guild>::reverification_queue(2 + period).is_empty());
+        assert_eq!(<Guild>::reverification_queue(2 + 2 * period).len(), 1);
+
+        // second user no longer satisfies the requirements
+        <Oracle>::callback(RuntimeOrigin::signed(operator), 3, vec![u8::from(true)]).unwrap();
+        <Oracle>::callback(RuntimeOrigin::signed(operator), 4, vec![u8::from(false)]).unwrap();
+        assert_eq!(
+            last_event(),
+            GuildEvent::RoleStripped(users[1], guild_name, role_name)
+        );
+        <Oracle>::callback(RuntimeOrigin::signed(operator), 5, vec![u8::from(true)]).unwrap();
+        assert!(<Guild>::member(role_id, users[0]).is_some());
+        assert!(<Guild>::member(role_id, users[1]).is_none());
+        assert!(<Guild>::member(role_id, users[2]).is_some());
+
+        // checks of stripped members are dropped from the queue
+        run_to_block(2 + 2 * period);
+        assert_eq!(<Guild>::reverification_queue(1 + 3 * period).len(), 1);
+        assert_eq!(<Guild>::reverification_queue(2 + 3 * period).len(), 1);
+        assert!(<Guild>::scheduled_reverification((guild_name, role_name), users[1]).is_none());
+
+        // re-joining members replace their pending check (request id 8)
+        <Guild>::leave(RuntimeOrigin::signed(users[0]), guild_name, role_name).unwrap();
+        <Guild>::join(RuntimeOrigin::signed(users[0]), guild_name, role_name, None).unwrap();
+        <Oracle>::callback(RuntimeOrigin::signed(operator), 8, vec![u8::from(true)]).unwrap();
+        assert!(<Guild>::reverification_queue(1 + 3 * period).is_empty());
+        assert_eq!(<Guild>::reverification_queue(2 + 3 * period).len(), 2);
+        assert_eq!(
+            <Guild>::scheduled_reverification((guild_name, role_name), users[0]),
+            Some(2 + 3 * period)
+        );
+
+        // failing requests are reported and retried in the next round
+        <Oracle>::deactivate_operator(RuntimeOrigin::signed(operator)).unwrap();
+        run_to_block(2 + 3 * period);
+        assert_eq!(
+            last_event(),
+            GuildEvent::ReverificationFailed(
+                users[0],
+                guild_name,
+                role_name,
+                pallet_oracle::Error::<TestRuntime>::NoActiveOperators.into()
+            )
+        );
+        assert!(<Guild>::member(role_id, users[0]).is_some());
+        assert_eq!(<Guild>::reverification_queue(2 + 4 * period).len(), 2);
+    });
+}
+
+#[test]
+fn full_reverification_queue_does_not_prevent_joining() {
+    new_test_ext().execute_with(|| {
+        let owner = 0;
+        let operator = 10;
+        let users = [1, 2, 3, 4, 5];
+        let guild_name = [0u8; 32];
+        let role_name = [1u8; 32];
+        let period = <TestRuntime as pallet_guild::Config>::ReverificationPeriod::get();
+
+        <Oracle>::register_operator(RuntimeOrigin::root(), operator).unwrap();
+        <Oracle>::activate_operator(RuntimeOrigin::signed(operator)).unwrap();
+
+        dummy_guild(owner, guild_name);
+        <Guild>::create_unfiltered_role(
+            RuntimeOrigin::signed(owner),
+            guild_name,
+            role_name,
+            (vec![], vec![]),
+        )
+        .unwrap();
+        let guild_id = <Guild>::guild_id(guild_name).unwrap();
+        let role_id = <Guild>::role_id(guild_id, role_name).unwrap();
+
+        // users join in block 1 (request ids 0..=4)
+        for (i, &user) in users.iter().enumerate() {
+            let (address, signature) = dummy_ecdsa_id_with_auth(user, [i as u8 + 1; 32]);
+            <Guild>::register(
+                RuntimeOrigin::signed(user),
+                IdentityWithAuth::Ecdsa(address, signature),
+                0,
+                EXPIRES_AT,
+            )
+            .unwrap();
+            <Guild>::join(RuntimeOrigin::signed(user), guild_name, role_name, None).unwrap();
+            <Oracle>::callback(
+                RuntimeOrigin::signed(operator),
+                i as u64,
+                vec![u8::from(true)],
+            )
+            .unwrap();
+            assert!(<Guild>::member(role_id, user).is_some());
+        }
+        // checks can only be postponed by 'MaxScheduleDelay' blocks, the
+        // last user becomes a member without a scheduled check
+        assert_eq!(<Guild>::reverification_queue(1 + period).len(), 2);
+        assert_eq!(<Guild>::reverification_queue(2 + period).len(), 2);
+        assert!(<Guild>::scheduled_reverification((guild_name, role_name), users[4]).is_none());
+        assert!(System::events().iter().any(|record| record.event
+            == RuntimeEvent::Guild(GuildEvent::ReverificationFailed(
+                users[4],
+                guild_name,
+                role_name,
+                pallet_guild::Error::<TestRuntime>::MaxReverificationsPerBlockExceeded.into()
+            ))));
+        assert_eq!(<Guild>::member_count(role_id), 5);
+    });
+}
+
+#[test]
+fn members_of_roles_with_or_filters_are_not_reverified() {
+    new_test_ext().execute_with(|| {
+        let owner = 0;
+        let user = 1;
+        let guild_name = [0u8; 32];
+        let role_name = [1u8; 32];
+        let period = <TestRuntime as pallet_guild::Config>::ReverificationPeriod::get();
+
+        let (address, signature) = dummy_ecdsa_id_with_auth(user, [1u8; 32]);
+        <Guild>::register(
+            RuntimeOrigin::signed(user),
+            IdentityWithAuth::Ecdsa(address, signature),
+            0,
+            EXPIRES_AT,
+        )
+        .unwrap();
+        let allowlist = vec![address, Identity::Address20([0u8; 20])];
+
+        dummy_guild(owner, guild_name);
+        <Guild>::create_role_with_allowlist(
+            RuntimeOrigin::signed(owner),
+            guild_name,
+            role_name,
+            allowlist.clone(),
+            FilterLogic::Or,
+            Some((vec![], vec![])),
+        )
+        .unwrap();
+        let guild_id = <Guild>::guild_id(guild_name).unwrap();
+        let role_id = <Guild>::role_id(guild_id, role_name).unwrap();
+
+        // the allowlist grants access on its own, so the membership is not
+        // scheduled for re-verification
+        let proof = MerkleProof::new(&allowlist, 0, 0);
+        <Guild>::join(
+            RuntimeOrigin::signed(user),
+            guild_name,
+            role_name,
+            Some(proof),
+        )
+        .unwrap();
+        assert!(<Guild>::member(role_id, user).is_some());
+        assert!(<Guild>::scheduled_reverification((guild_name, role_name), user).is_none());
+        assert!(<Guild>::reverification_queue(1 + period).is_empty());
+
+        // a failed requirement check doesn't strip the allowlisted member
+        let answer = dummy_answer(
+            vec![u8::from(false)],
+            owner,
+            RequestData::ReqCheck {
+                account: user,
+                guild_name,
+                role_name,
+            },
+        );
+        <Guild>::callback(RuntimeOrigin::root(), answer.encode()).unwrap();
+        assert!(<Guild>::member(role_id, user).is_some());
+        assert_eq!(
+            last_event(),
+            GuildEvent::RoleAssigned(user, guild_name, role_name)
+        );
+
+        run_to_block(1 + 2 * period);
+        assert!(<Guild>::member(role_id, user).is_some());
+    });
+}
+
+#[test]
+fn kick_and_ban_members() {
+    new_test_ext().execute_with(|| {
+        let owner = 0;
+        let admin = 1;
+        let users = [2, 3, 4];
+        let guild_name = [0u8; 32];
+        let role_names = [[1u8; 32], [2u8; 32]];
+        let reason = b"spam".to_vec();
+
+        dummy_guild(owner, guild_name);
+        <Guild>::add_admin(RuntimeOrigin::signed(owner), guild_name, admin).unwrap();
+        for role_name in role_names {
+            <Guild>::create_free_role(RuntimeOrigin::signed(owner), guild_name, role_name).unwrap();
+        }
+        let guild_id = <Guild>::guild_id(guild_name).unwrap();
+        let role_ids = role_names.map(|role_name| <Guild>::role_id(guild_id, role_name).unwrap());
+
+        for (i, &user) in users.iter().enumerate() {
+            let (address, signature) = dummy_ecdsa_id_with_auth(user, [i as u8 + 1; 32]);
+            <Guild>::register(
+                RuntimeOrigin::signed(user),
+                IdentityWithAuth::Ecdsa(address, signature),
+                0,
+                EXPIRES_AT,
+            )
+            .unwrap();
+            for role_name in role_names {
+                <Guild>::join(RuntimeOrigin::signed(user), guild_name, role_name, None).unwrap();
+            }
+        }
+
+        let failing_transactions = vec![
+            (
+                <Guild>::kick_member(
+                    RuntimeOrigin::signed(users[0]),
+                    guild_name,
+                    role_names[0],
+                    users[1],
+                    reason.clone(),
+                ),
+                "BadOrigin",
+            ),
+            (
+                <Guild>::kick_member(
+                    RuntimeOrigin::signed(admin),
+                    guild_name,
+                    role_names[0],
+                    admin,
+                    reason.clone(),
+                ),
+                "MemberDoesNotExist",
+            ),
+            (
+                <Guild>::kick_member(
+                    RuntimeOrigin::signed(admin),
+                    guild_name,
+                    role_names[0],
+                    users[0],
+                    vec![0u8; METADATA.len() + 1],
+                ),
+                "MaxSerializedLenExceeded",
+            ),
+            (
+                <Guild>::ban_account(
+                    RuntimeOrigin::signed(users[0]),
+                    guild_name,
+                    users[1],
+                    reason.clone(),
+                ),
+                "BadOrigin",
+            ),
+            (
+                <Guild>::ban_account(
+                    RuntimeOrigin::signed(admin),
+                    guild_name,
+                    owner,
+                    reason.clone(),
+                ),
+                "CannotBanGuildOwner",
+            ),
+            (
+                <Guild>::unban_account(RuntimeOrigin::signed(admin), guild_name, users[0]),
+                "AccountNotBanned",
+            ),
+        ];
+
+        for (tx, raw_error_msg) in failing_transactions {
+            assert_eq!(error_msg(tx.unwrap_err()), raw_error_msg);
+        }
+
+        // kicked members can join again
+        <Guild>::kick_member(
+            RuntimeOrigin::signed(admin),
+            guild_name,
+            role_names[0],
+            users[0],
+            reason.clone(),
+        )
+        .unwrap();
+        assert_eq!(
+            last_event(),
+            GuildEvent::MemberKicked(users[0], guild_name, role_names[0], reason.clone())
+        );
+        assert!(<Guild>::member(role_ids[0], users[0]).is_none());
+        assert!(<Guild>::member(role_ids[1], users[0]).is_some());
+        <Guild>::join(
+            RuntimeOrigin::signed(users[0]),
+            guild_name,
+            role_names[0],
+            None,
+        )
+        .unwrap();
+
+        // banned accounts are kicked from all roles and can't join again
+        <Guild>::ban_account(
+            RuntimeOrigin::signed(owner),
+            guild_name,
+            users[0],
+            reason.clone(),
+        )
+        .unwrap();
+        assert_eq!(
+            last_event(),
+            GuildEvent::AccountBanned(users[0], guild_name, reason.clone())
+        );
+        assert_eq!(<Guild>::bans(guild_id), vec![users[0]]);
+        for role_id in role_ids {
+            assert!(<Guild>::member(role_id, users[0]).is_none());
+        }
+        assert_eq!(
+            error_msg(
+                <Guild>::join(
+                    RuntimeOrigin::signed(users[0]),
+                    guild_name,
+                    role_names[0],
+                    None
+                )
+                .unwrap_err()
+            ),
+            "AccountBanned"
+        );
+        assert_eq!(
+            error_msg(
+                <Guild>::ban_account(
+                    RuntimeOrigin::signed(owner),
+                    guild_name,
+                    users[0],
+                    reason.clone(),
+                )
+                .unwrap_err()
+            ),
+            "AccountAlreadyBanned"
+        );
+
+        <Guild>::ban_account(
+            RuntimeOrigin::signed(owner),
+            guild_name,
+            users[1],
+            reason.clone(),
+        )
+        .unwrap();
+        assert_eq!(
+            error_msg(
+                <Guild>::ban_account(
+                    RuntimeOrigin::signed(owner),
+                    guild_name,
+                    users[2],
+                    reason.clone(),
+                )
+                .unwrap_err()
+            ),
+            "MaxBansPerGuildExceeded"
+        );
+        // other members are unaffected
+        assert!(<Guild>::member(role_ids[0], users[2]).is_some());
+
+        <Guild>::unban_account(RuntimeOrigin::signed(admin), guild_name, users[0]).unwrap();
+        assert_eq!(
+            last_event(),
+            GuildEvent::AccountUnbanned(users[0], guild_name)
+        );
+        assert_eq!(<Guild>::bans(guild_id), vec![users[1]]);
+        <Guild>::join(
+            RuntimeOrigin::signed(users[0]),
+            guild_name,
+            role_names[0],
+            None,
+        )
+        .unwrap();
+        assert!(<Guild>::member(role_ids[0], users[0]).is_some());
+    });
+}
+
+#[test]
+fn join_role_with_composite_filter() {
+    let owner = 0;
+    let user_1 = 1;
+    let user_2 = 2;
+    let g0 = [0u8; 32];
+    let g1 = [1u8; 32];
+    let g0r0 = [0u8; 32];
+    let g1r0 = [10u8; 32];
+    let any_of = [20u8; 32];
+    let all_of = [21u8; 32];
+    let with_allowlist = [22u8; 32];
+    let invalid_name = [100u8; 32];
+    let mut ext = new_test_ext();
+    let (address_1, signature_1) = ext.execute_with(|| dummy_ecdsa_id_with_auth(user_1, [1u8; 32]));
+    let (address_2, signature_2) = ext.execute_with(|| dummy_ecdsa_id_with_auth(user_2, [2u8; 32]));
+    let allowlist = vec![address_1, Identity::Address20([9u8; 20])];
+    let filter = Filter::<Vec<GuildFilter>>::allowlist(&allowlist, FilterLogic::And);
+    let Some(allowlist_root) = filter.allowlist_root() else {
+        unreachable!()
+    };
+    let parents = vec![
+        GuildFilter {
+            name: g0,
+            role: Some(g0r0),
+        },
+        GuildFilter {
+            name: g1,
+            role: Some(g1r0),
+        },
+    ];
+    let composite = |guilds: Vec<GuildFilter>, allowlist, logic| CompositeFilter {
+        guilds,
+        allowlist,
+        logic,
+    };
+
+    ext.execute_with(|| {
+        dummy_guild(owner, g0);
+        dummy_guild(owner, g1);
+        <Guild>::create_free_role(RuntimeOrigin::signed(owner), g0, g0r0).unwrap();
+        <Guild>::create_free_role(RuntimeOrigin::signed(owner), g1, g1r0).unwrap();
+
+        let max_len = <TestRuntime as pallet_guild::Config>::MaxCompositeFilterLen::get() as usize;
+        let failing_transactions = vec![
+            (
+                <Guild>::create_role_with_composite_filter(
+                    RuntimeOrigin::signed(owner),
+                    g0,
+                    any_of,
+                    composite(vec![], None, FilterLogic::Or),
+                    FilterLogic::And,
+                    None,
+                ),
+                "InvalidCompositeFilterLen",
+            ),
+            (
+                <Guild>::create_role_with_composite_filter(
+                    RuntimeOrigin::signed(owner),
+                    g0,
+                    any_of,
+                    composite(
+                        vec![parents[0]; max_len],
+                        Some(allowlist_root),
+                        FilterLogic::Or,
+                    ),
+                    FilterLogic::And,
+                    None,
+                ),
+                "InvalidCompositeFilterLen",
+            ),
+            (
+                <Guild>::create_role_with_composite_filter(
+                    RuntimeOrigin::signed(owner),
+                    g0,
+                    any_of,
+                    composite(
+                        vec![GuildFilter {
+                            name: invalid_name,
+                            role: None,
+                        }],
+                        None,
+                        FilterLogic::Or,
+                    ),
+                    FilterLogic::And,
+                    None,
+                ),
+                "GuildDoesNotExist",
+            ),
+            (
+                <Guild>::create_role_with_composite_filter(
+                    RuntimeOrigin::signed(owner),
+                    g0,
+                    any_of,
+                    composite(
+                        vec![GuildFilter {
+                            name: g1,
+                            role: Some(invalid_name),
+                        }],
+                        None,
+                        FilterLogic::Or,
+                    ),
+                    FilterLogic::And,
+                    None,
+                ),
+                "RoleDoesNotExist",
+            ),
+            (
+                <Guild>::create_role_with_composite_filter(
+                    RuntimeOrigin::signed(owner),
+                    g0,
+                    any_of,
+                    composite(vec![], Some((allowlist_root.0, 0)), FilterLogic::Or),
+                    FilterLogic::And,
+                    None,
+                ),
+                "InvalidAllowlistLen",
+            ),
+        ];
+        for (tx, raw_error_msg) in failing_transactions {
+            assert_eq!(error_msg(tx.unwrap_err()), raw_error_msg);
+        }
+
+        for (role_name, filter) in [
+            (any_of, composite(parents.clone(), None, FilterLogic::Or)),
+            (all_of, composite(parents.clone(), None, FilterLogic::And)),
+            (
+                with_allowlist,
+                composite(parents[..1].to_vec(), Some(allowlist_root), FilterLogic::Or),
+            ),
+        ] {
+            <Guild>::create_role_with_composite_filter(
+                RuntimeOrigin::signed(owner),
+                g0,
+                role_name,
+                filter,
+                FilterLogic::And,
+                None,
+            )
+            .unwrap();
+        }
+
+        for (user, address, signature) in [
+            (user_1, address_1, signature_1),
+            (user_2, address_2, signature_2),
+        ] {
+            <Guild>::register(
+                RuntimeOrigin::signed(user),
+                IdentityWithAuth::Ecdsa(address, signature),
+                0,
+                EXPIRES_AT,
+            )
+            .unwrap();
+        }
+
+        let g0_id = <Guild>::guild_id(g0).unwrap();
+        let any_of_id = <Guild>::role_id(g0_id, any_of).unwrap();
+        let all_of_id = <Guild>::role_id(g0_id, all_of).unwrap();
+        let with_allowlist_id = <Guild>::role_id(g0_id, with_allowlist).unwrap();
+
+        // user 1 is in guild 1 role 0 only
+        <Guild>::join(RuntimeOrigin::signed(user_1), g1, g1r0, None).unwrap();
+        <Guild>::join(RuntimeOrigin::signed(user_1), g0, any_of, None).unwrap();
+        assert_eq!(
+            error_msg(<Guild>::join(RuntimeOrigin::signed(user_1), g0, all_of, None).unwrap_err()),
+            "AccessDenied"
+        );
+        // user 1 is not in guild 0 role 0, but is on the allowlist
+        assert_eq!(
+            error_msg(
+                <Guild>::join(RuntimeOrigin::signed(user_1), g0, with_allowlist, None).unwrap_err()
+            ),
+            "MissingAllowlistProof"
+        );
+        let proof = MerkleProof::new(&allowlist, 0, 0);
+        <Guild>::join(
+            RuntimeOrigin::signed(user_1),
+            g0,
+            with_allowlist,
+            Some(proof),
+        )
+        .unwrap();
+        assert!(<Guild>::member(any_of_id, user_1).is_some());
+        assert!(<Guild>::member(all_of_id, user_1).is_none());
+        assert!(<Guild>::member(with_allowlist_id, user_1).is_some());
+
+        // user 2 is in both parent roles, so no proof is needed
+        <Guild>::join(RuntimeOrigin::signed(user_2), g0, g0r0, None).unwrap();
+        <Guild>::join(RuntimeOrigin::signed(user_2), g1, g1r0, None).unwrap();
+        <Guild>::join(RuntimeOrigin::signed(user_2), g0, any_of, None).unwrap();
+        <Guild>::join(RuntimeOrigin::signed(user_2), g0, all_of, None).unwrap();
+        <Guild>::join(RuntimeOrigin::signed(user_2), g0, with_allowlist, None).unwrap();
+        assert!(<Guild>::member(any_of_id, user_2).is_some());
+        assert!(<Guild>::member(all_of_id, user_2).is_some());
+        assert!(<Guild>::member(with_allowlist_id, user_2).is_some());
+    });
+}
+
+#[test]
+fn child_roles_are_revoked_with_their_parents() {
+    let owner = 0;
+    let user_1 = 1;
+    let user_2 = 2;
+    let g0 = [0u8; 32];
+    let g1 = [1u8; 32];
+    let parent = [0u8; 32];
+    let child = [1u8; 32];
+    let grandchild = [2u8; 32];
+    let child_with_reqs = [3u8; 32];
+    let other_child = [4u8; 32];
+    let guild_child = [10u8; 32];
+    let child_of = |role_name| GuildFilter {
+        name: g0,
+        role: Some(role_name),
+    };
+
+    new_test_ext().execute_with(|| {
+        dummy_guild(owner, g0);
+        dummy_guild(owner, g1);
+        <Guild>::create_free_role(RuntimeOrigin::signed(owner), g0, parent).unwrap();
+        for (role_name, filter, logic, requirements) in [
+            (child, child_of(parent), FilterLogic::And, None),
+            (grandchild, child_of(child), FilterLogic::And, None),
+            (
+                child_with_reqs,
+                child_of(parent),
+                FilterLogic::Or,
+                Some((vec![], vec![])),
+            ),
+            (other_child, child_of(parent), FilterLogic::And, None),
+        ] {
+            <Guild>::create_child_role(
+                RuntimeOrigin::signed(owner),
+                g0,
+                role_name,
+                filter,
+                logic,
+                requirements,
+            )
+            .unwrap();
+        }
+        <Guild>::create_child_role(
+            RuntimeOrigin::signed(owner),
+            g1,
+            guild_child,
+            GuildFilter {
+                name: g0,
+                role: None,
+            },
+            FilterLogic::And,
+            None,
+        )
+        .unwrap();
+
+        let g0_id = <Guild>::guild_id(g0).unwrap();
+        let g1_id = <Guild>::guild_id(g1).unwrap();
+        let parent_id = <Guild>::role_id(g0_id, parent).unwrap();
+        let child_id = <Guild>::role_id(g0_id, child).unwrap();
+        let grandchild_id = <Guild>::role_id(g0_id, grandchild).unwrap();
+        let child_with_reqs_id = <Guild>::role_id(g0_id, child_with_reqs).unwrap();
+        let guild_child_id = <Guild>::role_id(g1_id, guild_child).unwrap();
+        assert_eq!(
+            <Guild>::child_roles(parent_id),
+            vec![(g0, child), (g0, child_with_reqs), (g0, other_child)]
+        );
+        assert_eq!(<Guild>::child_roles(g0_id), vec![(g1, guild_child)]);
+
+        // parent role has too many children
+        assert_eq!(
+            error_msg(
+                <Guild>::create_child_role(
+                    RuntimeOrigin::signed(owner),
+                    g1,
+                    child,
+                    child_of(parent),
+                    FilterLogic::And,
+                    None,
+                )
+                .unwrap_err()
+            ),
+            "MaxChildrenPerRoleExceeded"
+        );
+
+        // deleted roles are removed from their parents' children
+        <Guild>::delete_role(RuntimeOrigin::signed(owner), g0, other_child, 0).unwrap();
+        assert_eq!(
+            <Guild>::child_roles(parent_id),
+            vec![(g0, child), (g0, child_with_reqs)]
+        );
+
+        for (i, &user) in [user_1, user_2].iter().enumerate() {
+            let (address, signature) = dummy_ecdsa_id_with_auth(user, [i as u8 + 1; 32]);
+            <Guild>::register(
+                RuntimeOrigin::signed(user),
+                IdentityWithAuth::Ecdsa(address, signature),
+                0,
+                EXPIRES_AT,
+            )
+            .unwrap();
+            for role_name in [parent, child, grandchild, child_with_reqs] {
+                <Guild>::join(RuntimeOrigin::signed(user), g0, role_name, None).unwrap();
+            }
+            <Guild>::join(RuntimeOrigin::signed(user), g1, guild_child, None).unwrap();
+        }
+
+        // losing the parent role queues the dependent memberships
+        <Guild>::leave(RuntimeOrigin::signed(user_1), g0, parent).unwrap();
+        <Guild>::kick_member(
+            RuntimeOrigin::signed(owner),
+            g0,
+            parent,
+            user_2,
+            b"spam".to_vec(),
+        )
+        .unwrap();
+        assert_eq!(<Guild>::pending_child_role_checks(), 6);
+
+        // checks are processed in batches of 'MaxChildChecksPerBlock'
+        run_to_block(2);
+        assert!(<Guild>::member(child_id, user_1).is_none());
+        assert!(<Guild>::member(child_with_reqs_id, user_1).is_some());
+        assert!(<Guild>::member(child_id, user_2).is_some());
+        assert_eq!(last_event(), GuildEvent::RoleStripped(user_1, g0, child));
+        // the grandchild and the guild child are checked after the
+        // deferred checks
+        assert_eq!(<Guild>::pending_child_role_checks(), 6);
+        assert!(<Guild>::member(grandchild_id, user_1).is_some());
+
+        run_to_block(10);
+        assert_eq!(<Guild>::pending_child_role_checks(), 0);
+        assert_eq!(<Guild>::child_role_check_range(), (12, 12));
+        for user in [user_1, user_2] {
+            assert!(<Guild>::member(parent_id, user).is_none());
+            assert!(<Guild>::member(child_id, user).is_none());
+            assert!(<Guild>::member(grandchild_id, user).is_none());
+            // members might have joined by satisfying the requirements
+            assert!(<Guild>::member(child_with_reqs_id, user).is_some());
+            // members are still in a role of the parent guild
+            assert!(<Guild>::member(guild_child_id, user).is_some());
+        }
+
+        // leaving the last role of the parent guild revokes the guild child
+        <Guild>::leave(RuntimeOrigin::signed(user_1), g0, child_with_reqs).unwrap();
+        run_to_block(11);
+        assert!(<Guild>::member(guild_child_id, user_1).is_none());
+        assert!(<Guild>::member(guild_child_id, user_2).is_some());
+        assert_eq!(
+            last_event(),
+            GuildEvent::RoleStripped(user_1, g1, guild_child)
+        );
+    });
+}
+
+#[test]
+fn child_roles_are_revoked_with_their_deleted_parents() {
+    let owner = 0;
+    let user = 1;
+    let guild_name = [0u8; 32];
+    let parent = [0u8; 32];
+    let child = [1u8; 32];
+
+    new_test_ext().execute_with(|| {
+        dummy_guild(owner, guild_name);
+        <Guild>::create_free_role(RuntimeOrigin::signed(owner), guild_name, parent).unwrap();
+        <Guild>::create_child_role(
+            RuntimeOrigin::signed(owner),
+            guild_name,
+            child,
+            GuildFilter {
+                name: guild_name,
+                role: Some(parent),
+            },
+            FilterLogic::And,
+            None,
+        )
+        .unwrap();
+        let guild_id = <Guild>::guild_id(guild_name).unwrap();
+        let parent_id = <Guild>::role_id(guild_id, parent).unwrap();
+        let child_id = <Guild>::role_id(guild_id, child).unwrap();
+
+        let (address, signature) = dummy_ecdsa_id_with_auth(user, [1u8; 32]);
+        <Guild>::register(
+            RuntimeOrigin::signed(user),
+            IdentityWithAuth::Ecdsa(address, signature),
+            0,
+            EXPIRES_AT,
+        )
+        .unwrap();
+        for role_name in [parent, child] {
+            <Guild>::join(RuntimeOrigin::signed(user), guild_name, role_name, None).unwrap();
+        }
+        assert_eq!(<Guild>::member_count(guild_id), 1);
+
+        // deleting the parent role queues the memberships of its children
+        <Guild>::delete_role(RuntimeOrigin::signed(owner), guild_name, parent, 1).unwrap();
+        assert!(<Guild>::member(parent_id, user).is_none());
+        assert!(<Guild>::child_roles(parent_id).is_empty());
+        assert_eq!(<Guild>::pending_child_role_checks(), 1);
+        assert_eq!(<Guild>::member_count(guild_id), 1);
+
+        run_to_block(2);
+        assert!(<Guild>::member(child_id, user).is_none());
+        assert_eq!(<Guild>::pending_child_role_checks(), 0);
+        assert_eq!(<Guild>::member_count(guild_id), 0);
+        assert_eq!(
+            last_event(),
+            GuildEvent::RoleStripped(user, guild_name, child)
+        );
+    });
+}
+
+#[test]
+fn member_counts() {
+    let owner = 0;
+    let user_1 = 1;
+    let user_2 = 2;
+    let guild_name = [0u8; 32];
+    let role_names = [[0u8; 32], [1u8; 32]];
+
+    new_test_ext().execute_with(|| {
+        dummy_guild(owner, guild_name);
+        for role_name in role_names {
+            <Guild>::create_free_role(RuntimeOrigin::signed(owner), guild_name, role_name).unwrap();
+        }
+        let guild_id = <Guild>::guild_id(guild_name).unwrap();
+        let role_ids = role_names.map(|role_name| <Guild>::role_id(guild_id, role_name).unwrap());
+        for (i, &user) in [user_1, user_2].iter().enumerate() {
+            let (address, signature) = dummy_ecdsa_id_with_auth(user, [i as u8 + 1; 32]);
+            <Guild>::register(
+                RuntimeOrigin::signed(user),
+                IdentityWithAuth::Ecdsa(address, signature),
+                0,
+                EXPIRES_AT,
+            )
+            .unwrap();
+        }
+        assert_eq!(<Guild>::member_count(guild_id), 0);
+
+        <Guild>::join(
+            RuntimeOrigin::signed(user_1),
+            guild_name,
+            role_names[0],
+            None,
+        )
+        .unwrap();
+        <Guild>::join(
+            RuntimeOrigin::signed(user_1),
+            guild_name,
+            role_names[1],
+            None,
+        )
+        .unwrap();
+        <Guild>::join(
+            RuntimeOrigin::signed(user_2),
+            guild_name,
+            role_names[0],
+            None,
+        )
+        .unwrap();
+        // joining again doesn't change the counts
+        <Guild>::join(
+            RuntimeOrigin::signed(user_2),
+            guild_name,
+            role_names[0],
+            None,
+        )
+        .unwrap();
+        assert_eq!(<Guild>::member_count(role_ids[0]), 2);
+        assert_eq!(<Guild>::member_count(role_ids[1]), 1);
+        assert_eq!(<Guild>::member_count(guild_id), 2);
+
+        // user 1 is still a member of the guild via role 1
+        <Guild>::leave(RuntimeOrigin::signed(user_1), guild_name, role_names[0]).unwrap();
+        assert_eq!(<Guild>::member_count(role_ids[0]), 1);
+        assert_eq!(<Guild>::member_count(guild_id), 2);
+
+        <Guild>::kick_member(
+            RuntimeOrigin::signed(owner),
+            guild_name,
+            role_names[0],
+            user_2,
+            vec![],
+        )
+        .unwrap();
+        assert_eq!(<Guild>::member_count(role_ids[0]), 0);
+        assert_eq!(<Guild>::member_count(guild_id), 1);
+
+        <Guild>::delete_role(RuntimeOrigin::signed(owner), guild_name, role_names[1], 1).unwrap();
+        assert_eq!(<Guild>::member_count(role_ids[1]), 0);
+        assert_eq!(<Guild>::member_count(guild_id), 0);
+
+        <Guild>::join(
+            RuntimeOrigin::signed(user_2),
+            guild_name,
+            role_names[0],
+            None,
+        )
+        .unwrap();
+        assert_eq!(<Guild>::member_count(guild_id), 1);
+        <Guild>::delete_guild(RuntimeOrigin::signed(owner), guild_name, 1).unwrap();
+        assert_eq!(<Guild>::member_count(role_ids[0]), 0);
+        assert_eq!(<Guild>::member_count(guild_id), 0);
+    });
+}
+
+#[test]
+fn account_roles_are_indexed() {
+    let owner = 0;
+    let user = 1;
+    let guild_name = [0u8; 32];
+    let max_roles = <TestRuntime as pallet_guild::Config>::MaxRolesPerAccount::get() as usize;
+    let role_names = (0..=max_roles)
+        .map(|i| [i as u8; 32])
+        .collect::<Vec<[u8; 32]>>();
+
+    new_test_ext().execute_with(|| {
+        dummy_guild(owner, guild_name);
+        for role_name in role_names.iter() {
+            <Guild>::create_free_role(RuntimeOrigin::signed(owner), guild_name, *role_name)
+                .unwrap();
+        }
+        let guild_id = <Guild>::guild_id(guild_name).unwrap();
+        let role_ids = role_names
+            .iter()
+            .map(|role_name| <Guild>::role_id(guild_id, role_name).unwrap())
+            .collect::<Vec<_>>();
+        let (address, signature) = dummy_ecdsa_id_with_auth(user, [1u8; 32]);
+        <Guild>::register(
+            RuntimeOrigin::signed(user),
+            IdentityWithAuth::Ecdsa(address, signature),
+            0,
+            EXPIRES_AT,
+        )
+        .unwrap();
+        assert!(<Guild>::account_roles(user).is_empty());
+
+        for role_name in role_names.iter().take(max_roles) {
+            <Guild>::join(RuntimeOrigin::signed(user), guild_name, *role_name, None).unwrap();
+        }
+        let account_roles = <Guild>::account_roles(user);
+        assert_eq!(account_roles.len(), max_roles);
+        assert!(role_ids
+            .iter()
+            .take(max_roles)
+            .all(|role_id| account_roles.contains(role_id)));
+        // role ids are resolved to names without iterating the roles
+        for (role_id, role_name) in role_ids.iter().zip(role_names.iter()) {
+            assert_eq!(
+                <Guild>::role_name_of(role_id),
+                Some((guild_name, *role_name))
+            );
+        }
+
+        assert_eq!(
+            error_msg(
+                <Guild>::join(
+                    RuntimeOrigin::signed(user),
+                    guild_name,
+                    role_names[max_roles],
+                    None
+                )
+                .unwrap_err()
+            ),
+            "MaxRolesPerAccountExceeded"
+        );
+
+        <Guild>::leave(RuntimeOrigin::signed(user), guild_name, role_names[0]).unwrap();
+        assert!(!<Guild>::account_roles(user).contains(&role_ids[0]));
+        <Guild>::join(
+            RuntimeOrigin::signed(user),
+            guild_name,
+            role_names[max_roles],
+            None,
+        )
+        .unwrap();
+        assert!(<Guild>::account_roles(user).contains(&role_ids[max_roles]));
+
+        <Guild>::delete_role(RuntimeOrigin::signed(owner), guild_name, role_names[1], 1).unwrap();
+        assert!(!<Guild>::account_roles(user).contains(&role_ids[1]));
+        assert!(<Guild>::role_name_of(role_ids[1]).is_none());
+
+        <Guild>::delete_guild(RuntimeOrigin::signed(owner), guild_name, max_roles as u32).unwrap();
+        assert!(<Guild>::account_roles(user).is_empty());
+        assert!(!pallet_guild::AccountRoles::<TestRuntime>::contains_key(
+            user
+        ));
+        assert!(role_ids
+            .iter()
+            .all(|role_id| <Guild>::role_name_of(role_id).is_none()));
+    });
+}
+
+#[test]
+fn join_many_roles() {
+    use frame_support::traits::UnfilteredDispatchable;
+    use parity_scale_codec::Decode;
+
+    let owner = 0;
+    let operator = 1;
+    let user = 2;
+    let guild_name = [0u8; 32];
+    let free_role = [1u8; 32];
+    let first_role = [2u8; 32];
+    let second_role = [3u8; 32];
+    let child_role = [4u8; 32];
+
+    new_test_ext().execute_with(|| {
+        dummy_guild(owner, guild_name);
+        <Guild>::create_free_role(RuntimeOrigin::signed(owner), guild_name, free_role).unwrap();
+        for role_name in [first_role, second_role] {
+            <Guild>::create_unfiltered_role(
+                RuntimeOrigin::signed(owner),
+                guild_name,
+                role_name,
+                (vec![], vec![]),
+            )
+            .unwrap();
+        }
+        <Guild>::create_child_role(
+            RuntimeOrigin::signed(owner),
+            guild_name,
+            child_role,
+            GuildFilter {
+                name: guild_name,
+                role: Some(first_role),
+            },
+            FilterLogic::And,
+            None,
+        )
+        .unwrap();
+        let guild_id = <Guild>::guild_id(guild_name).unwrap();
+        let role_id = |role_name| <Guild>::role_id(guild_id, role_name).unwrap();
+
+        <Oracle>::register_operator(RuntimeOrigin::root(), operator).unwrap();
+        <Oracle>::activate_operator(RuntimeOrigin::signed(operator)).unwrap();
+        let (address, signature) = dummy_ecdsa_id_with_auth(user, [1u8; 32]);
+        <Guild>::register(
+            RuntimeOrigin::signed(user),
+            IdentityWithAuth::Ecdsa(address, signature),
+            0,
+            EXPIRES_AT,
+        )
+        .unwrap();
+
+        // a single denied role fails the whole batch, which is only rolled
+        // back when dispatched as a call
+        let roles = vec![
+            (guild_name, free_role, None),
+            (guild_name, child_role, None),
+        ];
+        let call = pallet_guild::Call::<TestRuntime>::join_many {
+            roles: roles.try_into().unwrap(),
+        };
+        assert_eq!(
+            error_msg(
+                call.dispatch_bypass_filter(RuntimeOrigin::signed(user))
+                    .unwrap_err()
+                    .error
+            ),
+            "AccessDenied"
+        );
+        assert!(<Guild>::member(role_id(free_role), user).is_none());
+        assert!(<Oracle>::request(0).is_none());
+
+        // roles without requirements are joined right away, the rest are
+        // checked by a single oracle request
+        let roles = vec![
+            (guild_name, free_role, None),
+            (guild_name, first_role, None),
+            (guild_name, second_role, None),
+        ];
+        <Guild>::join_many(RuntimeOrigin::signed(user), roles.try_into().unwrap()).unwrap();
+        assert!(<Guild>::member(role_id(free_role), user).is_some());
+        assert!(<Guild>::member(role_id(first_role), user).is_none());
+        let request = gn_common::Request::<AccountId>::decode(
+            &mut <Oracle>::request(0).unwrap().data.as_slice(),
+        )
+        .unwrap();
+        assert_eq!(
+            request.data,
+            RequestData::ReqCheckMany {
+                account: user,
+                roles: vec![(guild_name, first_role), (guild_name, second_role)],
+            }
+        );
+        assert!(<Oracle>::request(1).is_none());
+
+        // the answer should contain a result for every role
+        assert_eq!(
+            error_msg(
+                <Oracle>::callback(RuntimeOrigin::signed(operator), 0, vec![u8::from(true)])
+                    .unwrap_err()
+            ),
+            "InvalidOracleAnswer"
+        );
+        <Oracle>::callback(
+            RuntimeOrigin::signed(operator),
+            0,
+            vec![u8::from(true), u8::from(false)],
+        )
+        .unwrap();
+        assert_eq!(
+            last_event(),
+            GuildEvent::RoleAssigned(user, guild_name, first_role)
+        );
+        assert!(<Guild>::member(role_id(first_role), user).is_some());
+        assert!(<Guild>::member(role_id(second_role), user).is_none());
+
+        // the child role is now available
+        let roles = vec![
+            (guild_name, child_role, None),
+            (guild_name, second_role, None),
+        ];
+        <Guild>::join_many(RuntimeOrigin::signed(user), roles.try_into().unwrap()).unwrap();
+        assert!(<Guild>::member(role_id(child_role), user).is_some());
+        assert_eq!(
+            error_msg(
+                <Oracle>::callback(RuntimeOrigin::signed(operator), 1, vec![u8::from(false)])
+                    .unwrap_err()
+            ),
+            "AccessDenied"
+        );
+        assert!(<Guild>::member(role_id(second_role), user).is_none());
+        assert_eq!(<Guild>::account_roles(user).len(), 3);
+    });
+}
+
+#[test]
+fn free_calls_are_counted_and_pruned() {
+    new_test_ext().execute_with(|| {
+        let users = [1, 2, 3];
+        let free_calls = FreeCallsPerWindow::get();
+
+        run_to_block(2);
+        assert_eq!(<Guild>::oldest_free_call_window(), Some(0));
+        for user in users {
+            assert_eq!(<Guild>::free_calls_left(&user), free_calls);
+            for _ in 0..free_calls {
+                assert!(<Guild>::use_free_call(&user));
+            }
+            assert!(!<Guild>::use_free_call(&user));
+            assert_eq!(<Guild>::free_calls_left(&user), 0);
+        }
+
+        // the quota is renewed in the next window
+        let window_len = FreeCallWindow::get();
+        run_to_block(window_len);
+        assert_eq!(<Guild>::free_call_window(window_len), 1);
+        assert_eq!(<Guild>::free_calls_left(&users[0]), free_calls);
+        assert!(<Guild>::use_free_call(&users[0]));
+
+        // counters of the expired window are pruned in batches
+        let remaining = users
+            .iter()
+            .filter(|&&user| <Guild>::free_calls(0, user) > 0)
+            .count();
+        assert_eq!(remaining, 1);
+        assert_eq!(<Guild>::oldest_free_call_window(), Some(0));
+        run_to_block(window_len + 1);
+        assert!(users.iter().all(|&user| <Guild>::free_calls(0, user) == 0));
+        assert_eq!(<Guild>::oldest_free_call_window(), Some(1));
+        assert_eq!(<Guild>::free_calls(1, users[0]), 1);
+    });
+}
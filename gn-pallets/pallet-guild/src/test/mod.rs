@@ -6,6 +6,7 @@ mod register;
 use helpers::*;
 
 use crate::mock::*;
+use frame_support::traits::Currency;
 type AccountId = <TestRuntime as frame_system::Config>::AccountId;
 
 use gn_common::{
@@ -25,6 +26,8 @@ fn callback_can_only_be_called_by_root() {
             RequestData::Register {
                 identity_with_auth: IdentityWithAuth::Other(Identity::Other([0u8; 64]), [0u8; 64]),
                 index: 0,
+                nonce: 0,
+                expires_at: EXPIRES_AT,
             },
         )
         .encode();
@@ -35,6 +38,8 @@ fn callback_can_only_be_called_by_root() {
             RequestData::Register {
                 identity_with_auth: IdentityWithAuth::Other(Identity::Other([0u8; 64]), [0u8; 64]),
                 index: <TestRuntime as pallet_guild::Config>::MaxIdentities::get(),
+                nonce: 0,
+                expires_at: EXPIRES_AT,
             },
         )
         .encode();
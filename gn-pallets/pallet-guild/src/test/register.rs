@@ -14,6 +14,7 @@ fn unsuccessful_registrations() {
                     RuntimeOrigin::none(),
                     IdentityWithAuth::Other(Identity::Other([0u8; 64]), [0u8; 64]),
                     0,
+                    EXPIRES_AT,
                 ),
                 "BadOrigin",
             ),
@@ -22,6 +23,7 @@ fn unsuccessful_registrations() {
                     RuntimeOrigin::root(),
                     IdentityWithAuth::Other(Identity::Other([0u8; 64]), [0u8; 64]),
                     0,
+                    EXPIRES_AT,
                 ),
                 "BadOrigin",
             ),
@@ -30,14 +32,25 @@ fn unsuccessful_registrations() {
                     RuntimeOrigin::signed(user),
                     IdentityWithAuth::Other(Identity::Other([0u8; 64]), [0u8; 64]),
                     max_identities,
+                    EXPIRES_AT,
                 ),
                 "MaxIdentitiesExceeded",
             ),
+            (
+                <Guild>::register(
+                    RuntimeOrigin::signed(user),
+                    IdentityWithAuth::Other(Identity::Other([0u8; 64]), [0u8; 64]),
+                    0,
+                    0,
+                ),
+                "VerificationMsgExpired",
+            ),
             (
                 <Guild>::register(
                     RuntimeOrigin::signed(user),
                     IdentityWithAuth::Other(Identity::Other([0u8; 64]), [0u8; 64]),
                     max_identities - 1,
+                    EXPIRES_AT,
                 ),
                 "NoActiveOperators",
             ),
@@ -49,6 +62,7 @@ fn unsuccessful_registrations() {
                         EcdsaSignature([0u8; 65]),
                     ),
                     max_identities - 1,
+                    EXPIRES_AT,
                 ),
                 "AccessDenied",
             ),
@@ -60,6 +74,7 @@ fn unsuccessful_registrations() {
                         EcdsaSignature([0u8; 65]),
                     ),
                     0,
+                    EXPIRES_AT,
                 ),
                 "AccessDenied",
             ),
@@ -71,6 +86,7 @@ fn unsuccessful_registrations() {
                         Ed25519Signature([0u8; 64]),
                     ),
                     1,
+                    EXPIRES_AT,
                 ),
                 "AccessDenied",
             ),
@@ -82,6 +98,7 @@ fn unsuccessful_registrations() {
                         Sr25519Signature([0u8; 64]),
                     ),
                     max_identities - 1,
+                    EXPIRES_AT,
                 ),
                 "AccessDenied",
             ),
@@ -105,49 +122,139 @@ fn successful_on_chain_registrations() {
         let keypair_edwards = sp_core::ed25519::Pair::from_seed_slice(&seed).unwrap();
         let keypair_ristretto = sp_core::sr25519::Pair::from_seed_slice(&seed).unwrap();
 
-        // sign message
-        let msg = gn_common::utils::verification_msg(user);
+        // sign message and generate identity with auth
+        let msg = verification_msg(user);
         let sig_ecdsa = EcdsaSignature(keypair_ecdsa.sign(msg.as_ref()).0);
-        let sig_edwards = Ed25519Signature(keypair_edwards.sign(msg.as_ref()).0);
-        let sig_ristretto = Sr25519Signature(keypair_ristretto.sign(msg.as_ref()).0);
-
-        // generate identities with auth
         let ecdsa_pubkey = recover_prehashed(eth_hash_message(&msg), &sig_ecdsa.0).unwrap();
         let ecdsa_address: [u8; 20] =
             sp_core::keccak_256(&ecdsa_pubkey.serialize_uncompressed()[1..])[12..]
                 .try_into()
                 .unwrap();
-
         let id_with_auth_ecdsa =
             IdentityWithAuth::Ecdsa(Identity::Address20(ecdsa_address), sig_ecdsa);
-        let id_with_auth_edwards =
-            IdentityWithAuth::Ed25519(Identity::Address32(keypair_edwards.public().0), sig_edwards);
-        let id_with_auth_ristretto = IdentityWithAuth::Sr25519(
-            Identity::Address32(keypair_ristretto.public().0),
-            sig_ristretto,
-        );
 
         // register various identities for user
-        <Guild>::register(RuntimeOrigin::signed(user), id_with_auth_ecdsa, index).unwrap();
+        <Guild>::register(
+            RuntimeOrigin::signed(user),
+            id_with_auth_ecdsa,
+            index,
+            EXPIRES_AT,
+        )
+        .unwrap();
         assert_eq!(last_event(), GuildEvent::IdRegistered(user, index));
         assert_eq!(
             <Guild>::user_data(user, index),
             Some(Identity::Address20(ecdsa_address))
         );
+        assert_eq!(<Guild>::registration_nonce(user), 1);
         index += 1;
-        <Guild>::register(RuntimeOrigin::signed(user), id_with_auth_edwards, index).unwrap();
+        // NOTE each registration consumes a nonce, so a new message has to
+        // be signed
+        let msg = verification_msg(user);
+        let sig_edwards = Ed25519Signature(keypair_edwards.sign(msg.as_ref()).0);
+        let id_with_auth_edwards =
+            IdentityWithAuth::Ed25519(Identity::Address32(keypair_edwards.public().0), sig_edwards);
+        <Guild>::register(
+            RuntimeOrigin::signed(user),
+            id_with_auth_edwards,
+            index,
+            EXPIRES_AT,
+        )
+        .unwrap();
         assert_eq!(last_event(), GuildEvent::IdRegistered(user, index));
         assert_eq!(
             <Guild>::user_data(user, index),
             Some(Identity::Address32(keypair_edwards.public().0))
         );
         index += 1;
-        <Guild>::register(RuntimeOrigin::signed(user), id_with_auth_ristretto, index).unwrap();
+        let msg = verification_msg(user);
+        let sig_ristretto = Sr25519Signature(keypair_ristretto.sign(msg.as_ref()).0);
+        let id_with_auth_ristretto = IdentityWithAuth::Sr25519(
+            Identity::Address32(keypair_ristretto.public().0),
+            sig_ristretto,
+        );
+        <Guild>::register(
+            RuntimeOrigin::signed(user),
+            id_with_auth_ristretto,
+            index,
+            EXPIRES_AT,
+        )
+        .unwrap();
         assert_eq!(
             <Guild>::user_data(user, index),
             Some(Identity::Address32(keypair_ristretto.public().0))
         );
         assert_eq!(last_event(), GuildEvent::IdRegistered(user, index));
+        assert_eq!(<Guild>::registration_nonce(user), 3);
+    });
+}
+
+#[test]
+fn verification_msg_replays() {
+    new_test_ext().execute_with(|| {
+        let user = 1;
+        let index = 0;
+        let seed = [2u8; 32];
+        let keypair = sp_core::sr25519::Pair::from_seed_slice(&seed).unwrap();
+        let identity = Identity::Address32(keypair.public().0);
+
+        let msg = verification_msg(user);
+        let signature = Sr25519Signature(keypair.sign(msg.as_ref()).0);
+        let id_with_auth = IdentityWithAuth::Sr25519(identity, signature);
+
+        // the signed message contains the expiry, so it cannot be extended
+        assert_eq!(
+            error_msg(
+                <Guild>::register(
+                    RuntimeOrigin::signed(user),
+                    id_with_auth,
+                    index,
+                    EXPIRES_AT + 1
+                )
+                .unwrap_err()
+            ),
+            "AccessDenied"
+        );
+        // the signed message is bound to the signer account
+        assert_eq!(
+            error_msg(
+                <Guild>::register(
+                    RuntimeOrigin::signed(user + 1),
+                    id_with_auth,
+                    index,
+                    EXPIRES_AT
+                )
+                .unwrap_err()
+            ),
+            "AccessDenied"
+        );
+
+        <Guild>::register(RuntimeOrigin::signed(user), id_with_auth, index, EXPIRES_AT).unwrap();
+        assert_eq!(<Guild>::user_data(user, index), Some(identity));
+        <Guild>::unregister(RuntimeOrigin::signed(user), index).unwrap();
+
+        // the same signature cannot be used twice
+        assert_eq!(
+            error_msg(
+                <Guild>::register(RuntimeOrigin::signed(user), id_with_auth, index, EXPIRES_AT)
+                    .unwrap_err()
+            ),
+            "AccessDenied"
+        );
+
+        // the message expires
+        run_to_block(EXPIRES_AT + 1);
+        let msg = verification_msg(user);
+        let signature = Sr25519Signature(keypair.sign(msg.as_ref()).0);
+        let id_with_auth = IdentityWithAuth::Sr25519(identity, signature);
+        assert_eq!(
+            error_msg(
+                <Guild>::register(RuntimeOrigin::signed(user), id_with_auth, index, EXPIRES_AT)
+                    .unwrap_err()
+            ),
+            "VerificationMsgExpired"
+        );
+        assert!(<Guild>::user_data(user, index).is_none());
     });
 }
 
@@ -167,33 +274,39 @@ fn successful_off_chain_registrations() {
         <Oracle>::register_operator(RuntimeOrigin::root(), operator).unwrap();
         <Oracle>::activate_operator(RuntimeOrigin::signed(operator)).unwrap();
         // user registers id that requires off-chain verification
-        <Guild>::register(RuntimeOrigin::signed(user), id_auth_zero, index).unwrap();
+        <Guild>::register(RuntimeOrigin::signed(user), id_auth_zero, index, EXPIRES_AT).unwrap();
         // pallet receives a dummy oracle answer
         let request_data = RequestData::Register {
             identity_with_auth: id_auth_zero,
             index,
+            nonce: 0,
+            expires_at: EXPIRES_AT,
         };
         let answer = dummy_answer(vec![u8::from(true)], user, request_data);
         <Guild>::callback(RuntimeOrigin::root(), answer.encode()).unwrap();
         assert_eq!(<Guild>::user_data(user, index), Some(id_zero));
         assert_eq!(last_event(), GuildEvent::IdRegistered(user, index));
         // user overrides previous id that requires off-chain verification
-        <Guild>::register(RuntimeOrigin::signed(user), id_auth_one, index).unwrap();
+        <Guild>::register(RuntimeOrigin::signed(user), id_auth_one, index, EXPIRES_AT).unwrap();
         // pallet receives a dummy oracle answer
         let request_data = RequestData::Register {
             identity_with_auth: id_auth_one,
             index,
+            nonce: 1,
+            expires_at: EXPIRES_AT,
         };
         let answer = dummy_answer(vec![u8::from(true)], user, request_data);
         <Guild>::callback(RuntimeOrigin::root(), answer.encode()).unwrap();
         assert_eq!(<Guild>::user_data(user, index), Some(id_one));
         assert_eq!(last_event(), GuildEvent::IdRegistered(user, index));
         // user tries to override again
-        <Guild>::register(RuntimeOrigin::signed(user), id_auth_zero, index).unwrap();
+        <Guild>::register(RuntimeOrigin::signed(user), id_auth_zero, index, EXPIRES_AT).unwrap();
         // pallet receives a dummy oracle answer
         let request_data = RequestData::Register {
             identity_with_auth: id_auth_zero,
             index,
+            nonce: 2,
+            expires_at: EXPIRES_AT,
         };
         let answer = dummy_answer(vec![u8::from(false)], user, request_data);
         assert_eq!(
@@ -201,6 +314,20 @@ fn successful_off_chain_registrations() {
             "AccessDenied"
         );
         assert_eq!(<Guild>::user_data(user, index), Some(id_one));
+        assert_eq!(<Guild>::registration_nonce(user), 2);
+        // answers with an already consumed nonce are rejected
+        let request_data = RequestData::Register {
+            identity_with_auth: id_auth_zero,
+            index,
+            nonce: 1,
+            expires_at: EXPIRES_AT,
+        };
+        let answer = dummy_answer(vec![u8::from(true)], user, request_data);
+        assert_eq!(
+            error_msg(<Guild>::callback(RuntimeOrigin::root(), answer.encode()).unwrap_err()),
+            "InvalidRegistrationNonce"
+        );
+        assert_eq!(<Guild>::user_data(user, index), Some(id_one));
     });
 }
 
@@ -210,9 +337,7 @@ fn successful_idenity_overrides() {
         let operator = 0;
         let user = 2;
         let seed = [12u8; 32];
-        let msg = gn_common::utils::verification_msg(user);
         let keypair_edwards = sp_core::ed25519::Pair::from_seed_slice(&seed).unwrap();
-        let sig_edwards = Ed25519Signature(keypair_edwards.sign(msg.as_ref()).0);
         let id_edwards = Identity::Address32(keypair_edwards.public().0);
         let id_zero = Identity::Other([0u8; 64]);
         let id_one = Identity::Other([1u8; 64]);
@@ -227,26 +352,492 @@ fn successful_idenity_overrides() {
         let request_data: RequestData<AccountId> = RequestData::Register {
             identity_with_auth,
             index,
+            nonce: 0,
+            expires_at: EXPIRES_AT,
         };
-        <Guild>::register(RuntimeOrigin::signed(user), identity_with_auth, index).unwrap();
+        <Guild>::register(
+            RuntimeOrigin::signed(user),
+            identity_with_auth,
+            index,
+            EXPIRES_AT,
+        )
+        .unwrap();
         assert!(<Guild>::user_data(user, index).is_none()); // no id registered yet
         let answer = dummy_answer(vec![u8::from(true)], user, request_data);
         <Guild>::callback(RuntimeOrigin::root(), answer.encode()).unwrap();
         assert_eq!(<Guild>::user_data(user, index), Some(id_zero));
         // user overrides an off-chain-verified identity with an on-chain id
+        let msg = verification_msg(user);
+        let sig_edwards = Ed25519Signature(keypair_edwards.sign(msg.as_ref()).0);
         let identity_with_auth = IdentityWithAuth::Ed25519(id_edwards, sig_edwards);
-        <Guild>::register(RuntimeOrigin::signed(user), identity_with_auth, index).unwrap();
+        <Guild>::register(
+            RuntimeOrigin::signed(user),
+            identity_with_auth,
+            index,
+            EXPIRES_AT,
+        )
+        .unwrap();
         assert_eq!(<Guild>::user_data(user, index), Some(id_edwards));
         // user overrides an on-chain-verified identity with an off-chain id
         let identity_with_auth = IdentityWithAuth::Other(id_one, auth);
         let request_data: RequestData<AccountId> = RequestData::Register {
             identity_with_auth,
             index,
+            nonce: 2,
+            expires_at: EXPIRES_AT,
         };
-        <Guild>::register(RuntimeOrigin::signed(user), identity_with_auth, index).unwrap();
+        <Guild>::register(
+            RuntimeOrigin::signed(user),
+            identity_with_auth,
+            index,
+            EXPIRES_AT,
+        )
+        .unwrap();
         assert_eq!(<Guild>::user_data(user, index), Some(id_edwards));
         let answer = dummy_answer(vec![u8::from(true)], user, request_data);
         <Guild>::callback(RuntimeOrigin::root(), answer.encode()).unwrap();
         assert_eq!(<Guild>::user_data(user, index), Some(id_one));
     });
 }
+
+#[test]
+fn identity_deregistration() {
+    new_test_ext().execute_with(|| {
+        let user = 1;
+        let index = 0;
+        let (address, signature) = dummy_ecdsa_id_with_auth(user, [1u8; 32]);
+
+        <Guild>::register(
+            RuntimeOrigin::signed(user),
+            IdentityWithAuth::Ecdsa(address, signature),
+            index,
+            EXPIRES_AT,
+        )
+        .unwrap();
+        assert_eq!(<Guild>::user_data(user, index), Some(address));
+
+        let failing_transactions = vec![
+            (
+                <Guild>::unregister(RuntimeOrigin::none(), index),
+                "BadOrigin",
+            ),
+            (
+                <Guild>::unregister(RuntimeOrigin::root(), index),
+                "BadOrigin",
+            ),
+            (
+                <Guild>::unregister(RuntimeOrigin::signed(user), index + 1),
+                "IdNotRegistered",
+            ),
+            (
+                <Guild>::unregister(RuntimeOrigin::signed(user + 1), index),
+                "IdNotRegistered",
+            ),
+        ];
+
+        for (tx, raw_error_msg) in failing_transactions {
+            assert_eq!(error_msg(tx.unwrap_err()), raw_error_msg);
+        }
+
+        <Guild>::unregister(RuntimeOrigin::signed(user), index).unwrap();
+        assert_eq!(last_event(), GuildEvent::IdDeregistered(user, index));
+        assert!(<Guild>::user_data(user, index).is_none());
+        assert_eq!(
+            error_msg(<Guild>::unregister(RuntimeOrigin::signed(user), index).unwrap_err()),
+            "IdNotRegistered"
+        );
+    });
+}
+
+#[test]
+fn duplicate_identity_claims() {
+    new_test_ext().execute_with(|| {
+        let operator = 0;
+        let user_1 = 1;
+        let user_2 = 2;
+        let seed = [1u8; 32];
+        let id_zero = Identity::Other([0u8; 64]);
+        let id_auth_zero = IdentityWithAuth::Other(id_zero, [0u8; 64]);
+
+        <Oracle>::register_operator(RuntimeOrigin::root(), operator).unwrap();
+        <Oracle>::activate_operator(RuntimeOrigin::signed(operator)).unwrap();
+
+        // both users sign their verification message with the same key
+        let (address, signature_1) = dummy_ecdsa_id_with_auth(user_1, seed);
+        let (_, signature_2) = dummy_ecdsa_id_with_auth(user_2, seed);
+        <Guild>::register(
+            RuntimeOrigin::signed(user_1),
+            IdentityWithAuth::Ecdsa(address, signature_1),
+            0,
+            EXPIRES_AT,
+        )
+        .unwrap();
+        assert_eq!(<Guild>::identity_owner(address), Some(user_1));
+
+        // user 1 registers an off-chain identity
+        <Guild>::register(RuntimeOrigin::signed(user_1), id_auth_zero, 1, EXPIRES_AT).unwrap();
+        let request_data = RequestData::Register {
+            identity_with_auth: id_auth_zero,
+            index: 1,
+            nonce: 1,
+            expires_at: EXPIRES_AT,
+        };
+        let answer = dummy_answer(vec![u8::from(true)], user_1, request_data);
+        <Guild>::callback(RuntimeOrigin::root(), answer.encode()).unwrap();
+        assert_eq!(<Guild>::identity_owner(id_zero), Some(user_1));
+
+        let request_data = RequestData::Register {
+            identity_with_auth: id_auth_zero,
+            index: 0,
+            nonce: 0,
+            expires_at: EXPIRES_AT,
+        };
+        let answer = dummy_answer(vec![u8::from(true)], user_2, request_data);
+
+        let failing_transactions = vec![
+            (
+                <Guild>::register(
+                    RuntimeOrigin::signed(user_2),
+                    IdentityWithAuth::Ecdsa(address, signature_2),
+                    0,
+                    EXPIRES_AT,
+                ),
+                "IdentityAlreadyClaimed",
+            ),
+            (
+                <Guild>::register(
+                    RuntimeOrigin::signed(user_1),
+                    IdentityWithAuth::Ecdsa(address, signature_1),
+                    2,
+                    EXPIRES_AT,
+                ),
+                "IdentityAlreadyClaimed",
+            ),
+            (
+                <Guild>::register(RuntimeOrigin::signed(user_2), id_auth_zero, 0, EXPIRES_AT),
+                "IdentityAlreadyClaimed",
+            ),
+            // identity claimed while the oracle request was pending
+            (
+                <Guild>::callback(RuntimeOrigin::root(), answer.encode()),
+                "IdentityAlreadyClaimed",
+            ),
+        ];
+
+        for (tx, raw_error_msg) in failing_transactions {
+            assert_eq!(error_msg(tx.unwrap_err()), raw_error_msg);
+        }
+
+        // re-registering the same identity under the same index is fine
+        let (_, signature_1) = dummy_ecdsa_id_with_auth(user_1, seed);
+        <Guild>::register(
+            RuntimeOrigin::signed(user_1),
+            IdentityWithAuth::Ecdsa(address, signature_1),
+            0,
+            EXPIRES_AT,
+        )
+        .unwrap();
+        assert_eq!(<Guild>::identity_owner(address), Some(user_1));
+
+        // overriding an identity releases the previous one
+        let id_one = Identity::Other([1u8; 64]);
+        let id_auth_one = IdentityWithAuth::Other(id_one, [0u8; 64]);
+        <Guild>::register(RuntimeOrigin::signed(user_1), id_auth_one, 1, EXPIRES_AT).unwrap();
+        let request_data = RequestData::Register {
+            identity_with_auth: id_auth_one,
+            index: 1,
+            nonce: 3,
+            expires_at: EXPIRES_AT,
+        };
+        let answer = dummy_answer(vec![u8::from(true)], user_1, request_data);
+        <Guild>::callback(RuntimeOrigin::root(), answer.encode()).unwrap();
+        assert_eq!(<Guild>::identity_owner(id_one), Some(user_1));
+        assert!(<Guild>::identity_owner(id_zero).is_none());
+
+        // deregistering an identity releases it
+        <Guild>::unregister(RuntimeOrigin::signed(user_1), 0).unwrap();
+        assert!(<Guild>::identity_owner(address).is_none());
+        <Guild>::register(
+            RuntimeOrigin::signed(user_2),
+            IdentityWithAuth::Ecdsa(address, signature_2),
+            0,
+            EXPIRES_AT,
+        )
+        .unwrap();
+        assert_eq!(<Guild>::identity_owner(address), Some(user_2));
+        assert_eq!(<Guild>::user_data(user_2, 0), Some(address));
+    });
+}
+
+#[test]
+fn identity_deposits() {
+    new_test_ext().execute_with(|| {
+        let user = 1;
+        let (address_0, signature_0) = dummy_ecdsa_id_with_auth(user, [1u8; 32]);
+
+        IdentityDeposit::set(5);
+        <Balances as Currency<AccountId>>::make_free_balance_be(&user, 5);
+
+        <Guild>::register(
+            RuntimeOrigin::signed(user),
+            IdentityWithAuth::Ecdsa(address_0, signature_0),
+            0,
+            EXPIRES_AT,
+        )
+        .unwrap();
+        assert_eq!(<Guild>::identity_deposit(user, 0), Some(5));
+        assert_eq!(Balances::reserved_balance(user), 5);
+
+        // overwriting an identity doesn't require a new deposit
+        let (address_1, signature_1) = dummy_ecdsa_id_with_auth(user, [2u8; 32]);
+        <Guild>::register(
+            RuntimeOrigin::signed(user),
+            IdentityWithAuth::Ecdsa(address_1, signature_1),
+            0,
+            EXPIRES_AT,
+        )
+        .unwrap();
+        assert_eq!(<Guild>::user_data(user, 0), Some(address_1));
+        assert_eq!(Balances::reserved_balance(user), 5);
+
+        let (address_0, signature_0) = dummy_ecdsa_id_with_auth(user, [1u8; 32]);
+        assert_eq!(
+            error_msg(
+                <Guild>::register(
+                    RuntimeOrigin::signed(user),
+                    IdentityWithAuth::Ecdsa(address_0, signature_0),
+                    1,
+                    EXPIRES_AT,
+                )
+                .unwrap_err()
+            ),
+            "InsufficientBalance"
+        );
+
+        <Guild>::unregister(RuntimeOrigin::signed(user), 0).unwrap();
+        assert!(<Guild>::identity_deposit(user, 0).is_none());
+        assert_eq!(Balances::reserved_balance(user), 0);
+        assert_eq!(Balances::free_balance(user), 5);
+    });
+}
+
+#[test]
+fn siwe_registrations() {
+    new_test_ext().execute_with(|| {
+        let user = 1;
+        let index = 0;
+        let keypair = sp_core::ecdsa::Pair::from_seed_slice(&[2u8; 32]).unwrap();
+        let sign = |msg: &str| EcdsaSignature(keypair.sign_prehashed(&eth_hash_message(msg)).0);
+        let address = eth_address(&recover_prehashed(eth_hash_message(""), &sign("").0).unwrap());
+        // NOTE the mock time is 2023-03-01T12:00:00Z
+        let message = siwe::SiweMessage {
+            expiration_time: Some("2023-03-01T12:10:00Z".into()),
+            not_before: Some("2023-03-01T12:00:00+00:00".into()),
+            ..siwe::SiweMessage::new(
+                "app.guild.xyz".into(),
+                address,
+                "https://app.guild.xyz/".into(),
+                1,
+                "2023-03-01T12:00:00.000Z".into(),
+            )
+        };
+        let bind = |message: siwe::SiweMessage, account: AccountId, expires_at: u64| {
+            message
+                .bind_registration(
+                    account,
+                    <System>::block_hash(0),
+                    <Guild>::registration_nonce(account),
+                    expires_at,
+                )
+                .to_string()
+        };
+        let siwe = |account: AccountId, expires_at: u64| bind(message.clone(), account, expires_at);
+        let invalid_messages = [
+            siwe::SiweMessage {
+                domain: "evil.xyz".into(),
+                ..message.clone()
+            },
+            siwe::SiweMessage {
+                chain_id: 5,
+                ..message.clone()
+            },
+            siwe::SiweMessage {
+                expiration_time: Some("2023-03-01T11:59:59Z".into()),
+                ..message.clone()
+            },
+            siwe::SiweMessage {
+                not_before: Some("2023-03-01T12:00:01Z".into()),
+                ..message.clone()
+            },
+        ]
+        .map(|message| bind(message, user, EXPIRES_AT));
+        let register = |signer: AccountId, msg: &str, signature: EcdsaSignature| {
+            <Guild>::register_siwe(
+                RuntimeOrigin::signed(signer),
+                msg.as_bytes().to_vec().try_into().unwrap(),
+                signature,
+                index,
+                EXPIRES_AT,
+            )
+        };
+
+        let msg = siwe(user, EXPIRES_AT);
+        let other_keypair = sp_core::ecdsa::Pair::from_seed_slice(&[3u8; 32]).unwrap();
+        let other_signature =
+            EcdsaSignature(other_keypair.sign_prehashed(&eth_hash_message(&msg)).0);
+        let failing_transactions = vec![
+            (
+                register(user, "invalid", sign("invalid")),
+                "InvalidSiweMessage",
+            ),
+            // message signed for another account
+            (register(user + 1, &msg, sign(&msg)), "InvalidSiweMessage"),
+            // message with another expiry
+            (
+                register(user, &siwe(user, EXPIRES_AT + 1), sign(&msg)),
+                "InvalidSiweMessage",
+            ),
+            (register(user, &msg, other_signature), "AccessDenied"),
+        ];
+        // messages of other domains or chains and expired messages
+        let failing_transactions = failing_transactions.into_iter().chain(
+            invalid_messages
+                .iter()
+                .map(|msg| (register(user, msg, sign(msg)), "InvalidSiweMessage")),
+        );
+
+        for (tx, raw_error_msg) in failing_transactions {
+            assert_eq!(error_msg(tx.unwrap_err()), raw_error_msg);
+        }
+
+        register(user, &msg, sign(&msg)).unwrap();
+        assert_eq!(last_event(), GuildEvent::IdRegistered(user, index));
+        assert_eq!(
+            <Guild>::user_data(user, index),
+            Some(Identity::Address20(address))
+        );
+        assert_eq!(<Guild>::registration_nonce(user), 1);
+
+        // the message cannot be replayed
+        <Guild>::unregister(RuntimeOrigin::signed(user), index).unwrap();
+        assert_eq!(
+            error_msg(register(user, &msg, sign(&msg)).unwrap_err()),
+            "InvalidSiweMessage"
+        );
+    });
+}
+
+#[test]
+fn eip712_registrations() {
+    new_test_ext().execute_with(|| {
+        let user = 1;
+        let index = 0;
+        let keypair = sp_core::ecdsa::Pair::from_seed_slice(&[2u8; 32]).unwrap();
+        let address = eth_address(
+            &recover_prehashed([1u8; 32], &keypair.sign_prehashed(&[1u8; 32]).0).unwrap(),
+        );
+        let digest = |account: AccountId, expires_at: u64| {
+            gn_common::eip712::registration_digest(
+                account,
+                <System>::block_hash(0),
+                <Guild>::registration_nonce(account),
+                expires_at,
+            )
+        };
+        let register = |signer: AccountId, signature: sp_core::ecdsa::Signature| {
+            <Guild>::register(
+                RuntimeOrigin::signed(signer),
+                IdentityWithAuth::Eip712(Identity::Address20(address), EcdsaSignature(signature.0)),
+                index,
+                EXPIRES_AT,
+            )
+        };
+
+        let failing_transactions = vec![
+            // plain verification message instead of typed data
+            register(
+                user,
+                keypair.sign_prehashed(&eth_hash_message(verification_msg(user))),
+            ),
+            // typed data signed for another account
+            register(user + 1, keypair.sign_prehashed(&digest(user, EXPIRES_AT))),
+            // typed data with another expiry
+            register(user, keypair.sign_prehashed(&digest(user, EXPIRES_AT + 1))),
+        ];
+
+        for tx in failing_transactions {
+            assert_eq!(error_msg(tx.unwrap_err()), "AccessDenied");
+        }
+
+        let signature = keypair.sign_prehashed(&digest(user, EXPIRES_AT));
+        register(user, signature.clone()).unwrap();
+        assert_eq!(last_event(), GuildEvent::IdRegistered(user, index));
+        assert_eq!(
+            <Guild>::user_data(user, index),
+            Some(Identity::Address20(address))
+        );
+        assert_eq!(<Guild>::registration_nonce(user), 1);
+
+        // the signature cannot be replayed
+        <Guild>::unregister(RuntimeOrigin::signed(user), index).unwrap();
+        assert_eq!(
+            error_msg(register(user, signature).unwrap_err()),
+            "AccessDenied"
+        );
+    });
+}
+
+#[test]
+fn contract_wallet_registrations() {
+    new_test_ext().execute_with(|| {
+        let operator = 0;
+        let user = 1;
+        let index = 0;
+        let contract = Identity::Address20([7u8; 20]);
+        let contract_signature = ContractSignature {
+            chain_id: 100,
+            signature: [0u8; 65],
+        };
+        let id_with_auth = IdentityWithAuth::Eip1271(contract, contract_signature);
+
+        // only evm addresses can be contract wallets
+        assert_eq!(
+            error_msg(
+                <Guild>::register(
+                    RuntimeOrigin::signed(user),
+                    IdentityWithAuth::Eip1271(Identity::Address32([7u8; 32]), contract_signature),
+                    index,
+                    EXPIRES_AT,
+                )
+                .unwrap_err()
+            ),
+            "AccessDenied"
+        );
+
+        <Oracle>::register_operator(RuntimeOrigin::root(), operator).unwrap();
+        <Oracle>::activate_operator(RuntimeOrigin::signed(operator)).unwrap();
+        // the signature is checked by the oracle
+        <Guild>::register(RuntimeOrigin::signed(user), id_with_auth, index, EXPIRES_AT).unwrap();
+        assert_eq!(<Guild>::user_data(user, index), None);
+        assert_eq!(<Guild>::registration_nonce(user), 0);
+
+        let request_data = RequestData::Register {
+            identity_with_auth: id_with_auth,
+            index,
+            nonce: 0,
+            expires_at: EXPIRES_AT,
+        };
+        let answer = dummy_answer(vec![u8::from(false)], user, request_data.clone());
+        assert_eq!(
+            error_msg(<Guild>::callback(RuntimeOrigin::root(), answer.encode()).unwrap_err()),
+            "AccessDenied"
+        );
+        assert_eq!(<Guild>::user_data(user, index), None);
+
+        let answer = dummy_answer(vec![u8::from(true)], user, request_data);
+        <Guild>::callback(RuntimeOrigin::root(), answer.encode()).unwrap();
+        assert_eq!(<Guild>::user_data(user, index), Some(contract));
+        assert_eq!(<Guild>::registration_nonce(user), 1);
+        assert_eq!(last_event(), GuildEvent::IdRegistered(user, index));
+    });
+}
@@ -43,6 +43,31 @@ pub trait WeightInfo {
 	fn join() -> Weight;
 	fn leave() -> Weight;
 	fn request_oracle_check() -> Weight;
+	fn transfer_guild_ownership() -> Weight;
+	fn accept_guild_ownership() -> Weight;
+	fn delete_role(n: u32, ) -> Weight;
+	fn delete_guild(r: u32, n: u32, ) -> Weight;
+	fn update_role_requirements(r: u32, s: u32, ) -> Weight;
+	fn update_role_filter(n: u32, ) -> Weight;
+	fn add_admin() -> Weight;
+	fn remove_admin() -> Weight;
+	fn update_guild_metadata(n: u32, ) -> Weight;
+	fn unregister(r: u32, ) -> Weight;
+	fn set_role_validity() -> Weight;
+	fn expire_memberships(n: u32, ) -> Weight;
+	fn reverify_members(n: u32, ) -> Weight;
+	fn kick_member(n: u32, ) -> Weight;
+	fn ban_account(r: u32, n: u32, ) -> Weight;
+	fn unban_account() -> Weight;
+	fn update_allowlist(n: u32, m: u32, ) -> Weight;
+	fn extend_allowlist(n: u32, ) -> Weight;
+	fn create_role_with_allowlist_root(r: u32, s: u32, ) -> Weight;
+	fn upload_allowlist_chunk(n: u32, ) -> Weight;
+	fn create_role_with_composite_filter(n: u32, r: u32, s: u32, ) -> Weight;
+	fn revoke_child_roles(n: u32, ) -> Weight;
+	fn join_many(n: u32, ) -> Weight;
+	fn register_siwe() -> Weight;
+	fn prune_free_calls(n: u32, ) -> Weight;
 }
 
 /// Weight functions for `pallet_guild`.
@@ -244,6 +269,573 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(8))
 			.saturating_add(T::DbWeight::get().writes(3))
 	}
+	/// Storage: Guild GuildIdMap (r:1 w:0)
+	/// Proof Skipped: Guild GuildIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Guilds (r:1 w:0)
+	/// Proof Skipped: Guild Guilds (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild PendingOwners (r:0 w:1)
+	/// Proof Skipped: Guild PendingOwners (max_values: None, max_size: None, mode: Measured)
+	fn transfer_guild_ownership() -> Weight {
+		// PLACEHOLDER: estimated by hand, not benchmarked yet
+		Weight::from_parts(28_112_000, 6866)
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: Guild GuildIdMap (r:1 w:0)
+	/// Proof Skipped: Guild GuildIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild PendingOwners (r:1 w:1)
+	/// Proof Skipped: Guild PendingOwners (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Guilds (r:1 w:1)
+	/// Proof Skipped: Guild Guilds (max_values: None, max_size: None, mode: Measured)
+	fn accept_guild_ownership() -> Weight {
+		// PLACEHOLDER: estimated by hand, not benchmarked yet
+		Weight::from_parts(33_904_000, 9927)
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: Guild GuildIdMap (r:1 w:0)
+	/// Proof Skipped: Guild GuildIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Guilds (r:1 w:1)
+	/// Proof Skipped: Guild Guilds (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild RoleIdMap (r:1 w:1)
+	/// Proof Skipped: Guild RoleIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Members (r:0 w:1000)
+	/// Proof Skipped: Guild Members (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Roles (r:1 w:1)
+	/// Proof Skipped: Guild Roles (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild RoleValidity (r:0 w:1)
+	/// Proof Skipped: Guild RoleValidity (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Deposits (r:1 w:1)
+	/// Proof Skipped: Guild Deposits (max_values: None, max_size: None, mode: Measured)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// The range of component `n` is `[0, 1000]`.
+	fn delete_role(n: u32, ) -> Weight {
+		// PLACEHOLDER: estimated by hand, not benchmarked yet
+		Weight::from_parts(44_307_519, 13148)
+			// Standard Error: 3_711
+			.saturating_add(Weight::from_ref_time(1_412_846).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(6))
+			.saturating_add(T::DbWeight::get().writes(6))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2531).saturating_mul(n.into()))
+	}
+	/// Storage: Guild GuildIdMap (r:1 w:1)
+	/// Proof Skipped: Guild GuildIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Guilds (r:1 w:1)
+	/// Proof Skipped: Guild Guilds (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild RoleIdMap (r:10 w:10)
+	/// Proof Skipped: Guild RoleIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Members (r:0 w:1000)
+	/// Proof Skipped: Guild Members (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Roles (r:10 w:10)
+	/// Proof Skipped: Guild Roles (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild RoleValidity (r:0 w:10)
+	/// Proof Skipped: Guild RoleValidity (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Admins (r:0 w:1)
+	/// Proof Skipped: Guild Admins (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Bans (r:0 w:1)
+	/// Proof Skipped: Guild Bans (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild PendingOwners (r:0 w:1)
+	/// Proof Skipped: Guild PendingOwners (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Deposits (r:11 w:11)
+	/// Proof Skipped: Guild Deposits (max_values: None, max_size: None, mode: Measured)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// The range of component `r` is `[0, 10]`.
+	/// The range of component `n` is `[0, 1000]`.
+	fn delete_guild(r: u32, n: u32, ) -> Weight {
+		// PLACEHOLDER: estimated by hand, not benchmarked yet
+		Weight::from_parts(36_872_204, 7864)
+			// Standard Error: 41_285
+			.saturating_add(Weight::from_ref_time(9_830_417).saturating_mul(r.into()))
+			// Standard Error: 3_852
+			.saturating_add(Weight::from_ref_time(1_409_112).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(r.into())))
+			.saturating_add(T::DbWeight::get().writes(7))
+			.saturating_add(T::DbWeight::get().writes((4_u64).saturating_mul(r.into())))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 5307).saturating_mul(r.into()))
+			.saturating_add(Weight::from_parts(0, 2531).saturating_mul(n.into()))
+	}
+	/// Storage: Guild GuildIdMap (r:1 w:0)
+	/// Proof Skipped: Guild GuildIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Guilds (r:1 w:0)
+	/// Proof Skipped: Guild Guilds (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild RoleIdMap (r:1 w:0)
+	/// Proof Skipped: Guild RoleIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Roles (r:1 w:1)
+	/// Proof Skipped: Guild Roles (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild RoleVersions (r:1 w:1)
+	/// Proof Skipped: Guild RoleVersions (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `r` is `[0, 10]`.
+	/// The range of component `s` is `[0, 256]`.
+	fn update_role_requirements(r: u32, s: u32, ) -> Weight {
+		// PLACEHOLDER: estimated by hand, not benchmarked yet
+		Weight::from_parts(40_662_315, 15921)
+			// Standard Error: 118_204
+			.saturating_add(Weight::from_ref_time(1_938_420).saturating_mul(r.into()))
+			// Standard Error: 4_617
+			.saturating_add(Weight::from_ref_time(47_015).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(T::DbWeight::get().writes(2))
+			.saturating_add(Weight::from_parts(0, 1295).saturating_mul(r.into()))
+			.saturating_add(Weight::from_parts(0, 50).saturating_mul(s.into()))
+	}
+	/// Storage: Guild GuildIdMap (r:2 w:0)
+	/// Proof Skipped: Guild GuildIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Guilds (r:1 w:0)
+	/// Proof Skipped: Guild Guilds (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild RoleIdMap (r:2 w:0)
+	/// Proof Skipped: Guild RoleIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Roles (r:1 w:1)
+	/// Proof Skipped: Guild Roles (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild ChildRoles (r:1 w:1)
+	/// Proof Skipped: Guild ChildRoles (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Deposits (r:1 w:1)
+	/// Proof Skipped: Guild Deposits (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild RoleVersions (r:1 w:1)
+	/// Proof Skipped: Guild RoleVersions (max_values: None, max_size: None, mode: Measured)
+	fn update_role_filter(n: u32, ) -> Weight {
+		// PLACEHOLDER: estimated by hand, not benchmarked yet
+		Weight::from_parts(58_214_000, 24861)
+			// Standard Error: 61_447
+			.saturating_add(Weight::from_ref_time(5_742_018).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(8))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(3))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 5090).saturating_mul(n.into()))
+	}
+	/// Storage: Guild GuildIdMap (r:1 w:0)
+	/// Proof Skipped: Guild GuildIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Guilds (r:1 w:0)
+	/// Proof Skipped: Guild Guilds (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Admins (r:1 w:1)
+	/// Proof Skipped: Guild Admins (max_values: None, max_size: None, mode: Measured)
+	fn add_admin() -> Weight {
+		// PLACEHOLDER: estimated by hand, not benchmarked yet
+		Weight::from_parts(33_118_000, 10335)
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: Guild GuildIdMap (r:1 w:0)
+	/// Proof Skipped: Guild GuildIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Guilds (r:1 w:0)
+	/// Proof Skipped: Guild Guilds (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Admins (r:1 w:1)
+	/// Proof Skipped: Guild Admins (max_values: None, max_size: None, mode: Measured)
+	fn remove_admin() -> Weight {
+		// PLACEHOLDER: estimated by hand, not benchmarked yet
+		Weight::from_parts(32_467_000, 10431)
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: Guild GuildIdMap (r:1 w:0)
+	/// Proof Skipped: Guild GuildIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Guilds (r:1 w:1)
+	/// Proof Skipped: Guild Guilds (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `n` is `[0, 256]`.
+	fn update_guild_metadata(n: u32, ) -> Weight {
+		// PLACEHOLDER: estimated by hand, not benchmarked yet
+		Weight::from_parts(28_341_655, 6390)
+			// Standard Error: 2_116
+			.saturating_add(Weight::from_ref_time(3_012).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: Guild UserData (r:1 w:1)
+	/// Proof Skipped: Guild UserData (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild IdentityOwners (r:0 w:1)
+	/// Proof Skipped: Guild IdentityOwners (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild IdentityDeposits (r:1 w:1)
+	/// Proof Skipped: Guild IdentityDeposits (max_values: None, max_size: None, mode: Measured)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Guild AccountRoles (r:1 w:1)
+	/// Proof Skipped: Guild AccountRoles (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild RoleNameMap (r:256 w:0)
+	/// Proof Skipped: Guild RoleNameMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Roles (r:256 w:0)
+	/// Proof Skipped: Guild Roles (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Members (r:256 w:256)
+	/// Proof Skipped: Guild Members (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild MemberCount (r:512 w:512)
+	/// Proof Skipped: Guild MemberCount (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild ChildRoles (r:512 w:0)
+	/// Proof Skipped: Guild ChildRoles (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `r` is `[0, 256]`.
+	fn unregister(r: u32, ) -> Weight {
+		// PLACEHOLDER: estimated by hand, not benchmarked yet
+		Weight::from_parts(21_730_000, 2722)
+			.saturating_add(Weight::from_ref_time(24_096_310).saturating_mul(r.into()))
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().reads((9_u64).saturating_mul(r.into())))
+			.saturating_add(T::DbWeight::get().writes(5))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(r.into())))
+			.saturating_add(Weight::from_parts(0, 18386).saturating_mul(r.into()))
+	}
+	/// Storage: Guild GuildIdMap (r:1 w:0)
+	/// Proof Skipped: Guild GuildIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Guilds (r:1 w:0)
+	/// Proof Skipped: Guild Guilds (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Admins (r:1 w:0)
+	/// Proof Skipped: Guild Admins (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild RoleIdMap (r:1 w:0)
+	/// Proof Skipped: Guild RoleIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild RoleVersions (r:1 w:1)
+	/// Proof Skipped: Guild RoleVersions (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild RoleValidity (r:0 w:1)
+	/// Proof Skipped: Guild RoleValidity (max_values: None, max_size: None, mode: Measured)
+	fn set_role_validity() -> Weight {
+		// PLACEHOLDER: estimated by hand, not benchmarked yet
+		Weight::from_parts(34_512_000, 15380)
+			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: Guild MembershipExpiries (r:1 w:1)
+	/// Proof Skipped: Guild MembershipExpiries (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild GuildIdMap (r:100 w:0)
+	/// Proof Skipped: Guild GuildIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild RoleIdMap (r:100 w:0)
+	/// Proof Skipped: Guild RoleIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Members (r:100 w:100)
+	/// Proof Skipped: Guild Members (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `n` is `[0, 100]`.
+	fn expire_memberships(n: u32, ) -> Weight {
+		// PLACEHOLDER: estimated by hand, not benchmarked yet
+		Weight::from_parts(7_902_233, 8319)
+			// Standard Error: 4_106
+			.saturating_add(Weight::from_ref_time(11_723_861).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(1))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2627).saturating_mul(n.into()))
+	}
+	/// Storage: Guild ReverificationQueue (r:2 w:2)
+	/// Proof Skipped: Guild ReverificationQueue (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild ScheduledReverifications (r:100 w:200)
+	/// Proof Skipped: Guild ScheduledReverifications (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild GuildIdMap (r:100 w:0)
+	/// Proof Skipped: Guild GuildIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild RoleIdMap (r:100 w:0)
+	/// Proof Skipped: Guild RoleIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Members (r:100 w:0)
+	/// Proof Skipped: Guild Members (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Roles (r:100 w:0)
+	/// Proof Skipped: Guild Roles (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Oracle ActiveOperators (r:1 w:0)
+	/// Proof Skipped: Oracle ActiveOperators (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Oracle NextOperator (r:1 w:1)
+	/// Proof Skipped: Oracle NextOperator (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Oracle NextRequestIdentifier (r:1 w:1)
+	/// Proof Skipped: Oracle NextRequestIdentifier (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Oracle Requests (r:0 w:100)
+	/// Proof Skipped: Oracle Requests (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `n` is `[0, 100]`.
+	fn reverify_members(n: u32, ) -> Weight {
+		// PLACEHOLDER: estimated by hand, not benchmarked yet
+		Weight::from_parts(14_216_000, 13889)
+			// Standard Error: 21_377
+			.saturating_add(Weight::from_ref_time(54_902_113).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(6))
+			.saturating_add(T::DbWeight::get().reads((5_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(5))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 10314).saturating_mul(n.into()))
+	}
+	/// Storage: Guild GuildIdMap (r:1 w:0)
+	/// Proof Skipped: Guild GuildIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Guilds (r:1 w:0)
+	/// Proof Skipped: Guild Guilds (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Admins (r:1 w:0)
+	/// Proof Skipped: Guild Admins (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild RoleIdMap (r:1 w:0)
+	/// Proof Skipped: Guild RoleIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Members (r:1 w:1)
+	/// Proof Skipped: Guild Members (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `n` is `[0, 256]`.
+	fn kick_member(n: u32, ) -> Weight {
+		// PLACEHOLDER: estimated by hand, not benchmarked yet
+		Weight::from_parts(36_219_431, 15931)
+			// Standard Error: 412
+			.saturating_add(Weight::from_ref_time(1_518).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: Guild GuildIdMap (r:1 w:0)
+	/// Proof Skipped: Guild GuildIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Guilds (r:1 w:0)
+	/// Proof Skipped: Guild Guilds (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Admins (r:1 w:0)
+	/// Proof Skipped: Guild Admins (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Bans (r:1 w:1)
+	/// Proof Skipped: Guild Bans (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild RoleIdMap (r:10 w:0)
+	/// Proof Skipped: Guild RoleIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Members (r:10 w:10)
+	/// Proof Skipped: Guild Members (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `r` is `[0, 10]`.
+	/// The range of component `n` is `[0, 256]`.
+	fn ban_account(r: u32, n: u32, ) -> Weight {
+		// PLACEHOLDER: estimated by hand, not benchmarked yet
+		Weight::from_parts(37_180_762, 17530)
+			// Standard Error: 26_734
+			.saturating_add(Weight::from_ref_time(9_211_480).saturating_mul(r.into()))
+			// Standard Error: 1_036
+			.saturating_add(Weight::from_ref_time(4_187).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(r.into())))
+			.saturating_add(T::DbWeight::get().writes(1))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(r.into())))
+			.saturating_add(Weight::from_parts(0, 5124).saturating_mul(r.into()))
+	}
+	/// Storage: Guild GuildIdMap (r:1 w:0)
+	/// Proof Skipped: Guild GuildIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Guilds (r:1 w:0)
+	/// Proof Skipped: Guild Guilds (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Admins (r:1 w:0)
+	/// Proof Skipped: Guild Admins (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Bans (r:1 w:1)
+	/// Proof Skipped: Guild Bans (max_values: None, max_size: None, mode: Measured)
+	fn unban_account() -> Weight {
+		// PLACEHOLDER: estimated by hand, not benchmarked yet
+		Weight::from_parts(43_110_000, 28953)
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: Guild GuildIdMap (r:1 w:0)
+	/// Proof Skipped: Guild GuildIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Guilds (r:1 w:0)
+	/// Proof Skipped: Guild Guilds (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Admins (r:1 w:0)
+	/// Proof Skipped: Guild Admins (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild RoleIdMap (r:1 w:0)
+	/// Proof Skipped: Guild RoleIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Roles (r:1 w:1)
+	/// Proof Skipped: Guild Roles (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Members (r:1001 w:1000)
+	/// Proof Skipped: Guild Members (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild UserData (r:1000 w:0)
+	/// Proof Skipped: Guild UserData (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Deposits (r:1 w:1)
+	/// Proof Skipped: Guild Deposits (max_values: None, max_size: None, mode: Measured)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Guild RoleVersions (r:1 w:1)
+	/// Proof Skipped: Guild RoleVersions (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `n` is `[1, 128]`.
+	/// The range of component `m` is `[0, 1000]`.
+	fn update_allowlist(n: u32, m: u32, ) -> Weight {
+		// PLACEHOLDER: estimated by hand, not benchmarked yet
+		Weight::from_parts(68_441_270, 21376)
+			// Standard Error: 28_916
+			.saturating_add(Weight::from_ref_time(1_587_310).saturating_mul(n.into()))
+			// Standard Error: 4_102
+			.saturating_add(Weight::from_ref_time(9_604_118).saturating_mul(m.into()))
+			.saturating_add(T::DbWeight::get().reads(9))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(m.into())))
+			.saturating_add(T::DbWeight::get().writes(4))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(m.into())))
+			.saturating_add(Weight::from_parts(0, 5062).saturating_mul(m.into()))
+	}
+	/// Storage: Guild GuildIdMap (r:1 w:0)
+	/// Proof Skipped: Guild GuildIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Guilds (r:1 w:0)
+	/// Proof Skipped: Guild Guilds (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Admins (r:1 w:0)
+	/// Proof Skipped: Guild Admins (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild RoleIdMap (r:1 w:0)
+	/// Proof Skipped: Guild RoleIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Roles (r:1 w:1)
+	/// Proof Skipped: Guild Roles (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Deposits (r:1 w:1)
+	/// Proof Skipped: Guild Deposits (max_values: None, max_size: None, mode: Measured)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Guild RoleVersions (r:1 w:1)
+	/// Proof Skipped: Guild RoleVersions (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `n` is `[2, 128]`.
+	fn extend_allowlist(n: u32, ) -> Weight {
+		// PLACEHOLDER: estimated by hand, not benchmarked yet
+		Weight::from_parts(70_106_842, 16314)
+			// Standard Error: 31_204
+			.saturating_add(Weight::from_ref_time(3_095_527).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(8))
+			.saturating_add(T::DbWeight::get().writes(4))
+	}
+	/// Storage: Guild GuildIdMap (r:1 w:0)
+	/// Proof Skipped: Guild GuildIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild RoleIdMap (r:1 w:1)
+	/// Proof Skipped: Guild RoleIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Guilds (r:1 w:1)
+	/// Proof Skipped: Guild Guilds (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Nonce (r:1 w:1)
+	/// Proof Skipped: Guild Nonce (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	/// Proof: RandomnessCollectiveFlip RandomMaterial (max_values: Some(1), max_size: Some(2594), added: 3089, mode: MaxEncodedLen)
+	/// Storage: Guild Roles (r:0 w:1)
+	/// Proof Skipped: Guild Roles (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Deposits (r:0 w:1)
+	/// Proof Skipped: Guild Deposits (max_values: None, max_size: None, mode: Measured)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// The range of component `r` is `[0, 10]`.
+	/// The range of component `s` is `[0, 256]`.
+	fn create_role_with_allowlist_root(r: u32, s: u32, ) -> Weight {
+		// PLACEHOLDER: estimated by hand, not benchmarked yet
+		Weight::from_parts(41_207_955, 14399)
+			// Standard Error: 121_804
+			.saturating_add(Weight::from_ref_time(2_014_329).saturating_mul(r.into()))
+			// Standard Error: 4_998
+			.saturating_add(Weight::from_ref_time(50_917).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(6))
+			.saturating_add(T::DbWeight::get().writes(6))
+	}
+	/// Storage: Guild GuildIdMap (r:1 w:0)
+	/// Proof Skipped: Guild GuildIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Guilds (r:1 w:0)
+	/// Proof Skipped: Guild Guilds (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Admins (r:1 w:0)
+	/// Proof Skipped: Guild Admins (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild RoleIdMap (r:1 w:0)
+	/// Proof Skipped: Guild RoleIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Roles (r:1 w:0)
+	/// Proof Skipped: Guild Roles (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `n` is `[1, 128]`.
+	fn upload_allowlist_chunk(n: u32, ) -> Weight {
+		// PLACEHOLDER: estimated by hand, not benchmarked yet
+		Weight::from_parts(35_018_637, 13297)
+			// Standard Error: 2_913
+			.saturating_add(Weight::from_ref_time(171_442).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(5))
+	}
+	/// Storage: Guild GuildIdMap (r:9 w:0)
+	/// Proof Skipped: Guild GuildIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild RoleIdMap (r:9 w:1)
+	/// Proof Skipped: Guild RoleIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Guilds (r:1 w:1)
+	/// Proof Skipped: Guild Guilds (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Nonce (r:1 w:1)
+	/// Proof Skipped: Guild Nonce (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	/// Proof: RandomnessCollectiveFlip RandomMaterial (max_values: Some(1), max_size: Some(2594), added: 3089, mode: MaxEncodedLen)
+	/// Storage: Guild Roles (r:0 w:1)
+	/// Proof Skipped: Guild Roles (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Deposits (r:0 w:1)
+	/// Proof Skipped: Guild Deposits (max_values: None, max_size: None, mode: Measured)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// The range of component `n` is `[1, 8]`.
+	/// The range of component `r` is `[0, 10]`.
+	/// The range of component `s` is `[0, 256]`.
+	fn create_role_with_composite_filter(n: u32, r: u32, s: u32, ) -> Weight {
+		// PLACEHOLDER: estimated by hand, not benchmarked yet
+		Weight::from_parts(45_873_515, 18227)
+			// Standard Error: 61_447
+			.saturating_add(Weight::from_ref_time(5_742_018).saturating_mul(n.into()))
+			// Standard Error: 49_106
+			.saturating_add(Weight::from_ref_time(2_097_650).saturating_mul(r.into()))
+			// Standard Error: 2_015
+			.saturating_add(Weight::from_ref_time(51_239).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(6))
+			.saturating_add(Weight::from_parts(0, 5090).saturating_mul(n.into()))
+	}
+	/// Storage: Guild ChildRoleCheckRange (r:1 w:1)
+	/// Proof Skipped: Guild ChildRoleCheckRange (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Guild ChildRoleChecks (r:50 w:50)
+	/// Proof Skipped: Guild ChildRoleChecks (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild GuildIdMap (r:101 w:0)
+	/// Proof Skipped: Guild GuildIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild RoleIdMap (r:101 w:0)
+	/// Proof Skipped: Guild RoleIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Roles (r:1 w:0)
+	/// Proof Skipped: Guild Roles (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Members (r:150 w:50)
+	/// Proof Skipped: Guild Members (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild ChildRoles (r:100 w:0)
+	/// Proof Skipped: Guild ChildRoles (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `n` is `[0, 50]`.
+	fn revoke_child_roles(n: u32, ) -> Weight {
+		// PLACEHOLDER: estimated by hand, not benchmarked yet
+		Weight::from_parts(14_377_520, 14208)
+			// Standard Error: 9_872
+			.saturating_add(Weight::from_ref_time(24_096_310).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(T::DbWeight::get().reads((8_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(1))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 18386).saturating_mul(n.into()))
+	}
+	/// Storage: Guild UserData (r:1 w:0)
+	/// Proof Skipped: Guild UserData (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild GuildIdMap (r:16 w:0)
+	/// Proof Skipped: Guild GuildIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild RoleIdMap (r:16 w:0)
+	/// Proof Skipped: Guild RoleIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Bans (r:16 w:0)
+	/// Proof Skipped: Guild Bans (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Roles (r:16 w:0)
+	/// Proof Skipped: Guild Roles (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Members (r:16 w:16)
+	/// Proof Skipped: Guild Members (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild AccountRoles (r:16 w:16)
+	/// Proof Skipped: Guild AccountRoles (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild RoleValidity (r:16 w:0)
+	/// Proof Skipped: Guild RoleValidity (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild MemberCount (r:32 w:32)
+	/// Proof Skipped: Guild MemberCount (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild ReverificationQueue (r:0 w:16)
+	/// Proof Skipped: Guild ReverificationQueue (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `n` is `[1, 16]`.
+	fn join_many(n: u32, ) -> Weight {
+		// PLACEHOLDER: estimated by hand, not benchmarked yet
+		Weight::from_parts(18_402_117, 5112)
+			// Standard Error: 14_608
+			.saturating_add(Weight::from_ref_time(52_614_093).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().reads((9_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes((5_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 20215).saturating_mul(n.into()))
+	}
+	/// Storage: System BlockHash (r:1 w:0)
+	/// Proof: System BlockHash (max_values: None, max_size: Some(44), added: 2519, mode: MaxEncodedLen)
+	/// Storage: Guild RegistrationNonces (r:1 w:1)
+	/// Proof Skipped: Guild RegistrationNonces (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild IdentityOwners (r:1 w:1)
+	/// Proof Skipped: Guild IdentityOwners (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild UserData (r:1 w:1)
+	/// Proof Skipped: Guild UserData (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild IdentityDeposits (r:0 w:1)
+	/// Proof Skipped: Guild IdentityDeposits (max_values: None, max_size: None, mode: Measured)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn register_siwe() -> Weight {
+		// PLACEHOLDER: estimated by hand, not benchmarked yet
+		Weight::from_parts(421_312_000, 10140)
+			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(T::DbWeight::get().writes(5))
+	}
+	/// Storage: Guild OldestFreeCallWindow (r:1 w:1)
+	/// Proof Skipped: Guild OldestFreeCallWindow (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Guild FreeCalls (r:0 w:100)
+	/// Proof Skipped: Guild FreeCalls (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `n` is `[0, 100]`.
+	fn prune_free_calls(n: u32, ) -> Weight {
+		// PLACEHOLDER: estimated by hand, not benchmarked yet
+		Weight::from_parts(4_125_000, 1485)
+			// Standard Error: 1_302
+			.saturating_add(Weight::from_ref_time(1_206_552).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(n.into())))
+	}
 }
 
 impl WeightInfo for () {
@@ -443,4 +1035,571 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(8))
 			.saturating_add(RocksDbWeight::get().writes(3))
 	}
+	/// Storage: Guild GuildIdMap (r:1 w:0)
+	/// Proof Skipped: Guild GuildIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Guilds (r:1 w:0)
+	/// Proof Skipped: Guild Guilds (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild PendingOwners (r:0 w:1)
+	/// Proof Skipped: Guild PendingOwners (max_values: None, max_size: None, mode: Measured)
+	fn transfer_guild_ownership() -> Weight {
+		// PLACEHOLDER: estimated by hand, not benchmarked yet
+		Weight::from_parts(28_112_000, 6866)
+			.saturating_add(RocksDbWeight::get().reads(2))
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+	/// Storage: Guild GuildIdMap (r:1 w:0)
+	/// Proof Skipped: Guild GuildIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild PendingOwners (r:1 w:1)
+	/// Proof Skipped: Guild PendingOwners (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Guilds (r:1 w:1)
+	/// Proof Skipped: Guild Guilds (max_values: None, max_size: None, mode: Measured)
+	fn accept_guild_ownership() -> Weight {
+		// PLACEHOLDER: estimated by hand, not benchmarked yet
+		Weight::from_parts(33_904_000, 9927)
+			.saturating_add(RocksDbWeight::get().reads(3))
+			.saturating_add(RocksDbWeight::get().writes(2))
+	}
+	/// Storage: Guild GuildIdMap (r:1 w:0)
+	/// Proof Skipped: Guild GuildIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Guilds (r:1 w:1)
+	/// Proof Skipped: Guild Guilds (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild RoleIdMap (r:1 w:1)
+	/// Proof Skipped: Guild RoleIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Members (r:0 w:1000)
+	/// Proof Skipped: Guild Members (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Roles (r:1 w:1)
+	/// Proof Skipped: Guild Roles (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild RoleValidity (r:0 w:1)
+	/// Proof Skipped: Guild RoleValidity (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Deposits (r:1 w:1)
+	/// Proof Skipped: Guild Deposits (max_values: None, max_size: None, mode: Measured)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// The range of component `n` is `[0, 1000]`.
+	fn delete_role(n: u32, ) -> Weight {
+		// PLACEHOLDER: estimated by hand, not benchmarked yet
+		Weight::from_parts(44_307_519, 13148)
+			// Standard Error: 3_711
+			.saturating_add(Weight::from_ref_time(1_412_846).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(6))
+			.saturating_add(RocksDbWeight::get().writes(6))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2531).saturating_mul(n.into()))
+	}
+	/// Storage: Guild GuildIdMap (r:1 w:1)
+	/// Proof Skipped: Guild GuildIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Guilds (r:1 w:1)
+	/// Proof Skipped: Guild Guilds (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild RoleIdMap (r:10 w:10)
+	/// Proof Skipped: Guild RoleIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Members (r:0 w:1000)
+	/// Proof Skipped: Guild Members (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Roles (r:10 w:10)
+	/// Proof Skipped: Guild Roles (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild RoleValidity (r:0 w:10)
+	/// Proof Skipped: Guild RoleValidity (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Admins (r:0 w:1)
+	/// Proof Skipped: Guild Admins (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Bans (r:0 w:1)
+	/// Proof Skipped: Guild Bans (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild PendingOwners (r:0 w:1)
+	/// Proof Skipped: Guild PendingOwners (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Deposits (r:11 w:11)
+	/// Proof Skipped: Guild Deposits (max_values: None, max_size: None, mode: Measured)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// The range of component `r` is `[0, 10]`.
+	/// The range of component `n` is `[0, 1000]`.
+	fn delete_guild(r: u32, n: u32, ) -> Weight {
+		// PLACEHOLDER: estimated by hand, not benchmarked yet
+		Weight::from_parts(36_872_204, 7864)
+			// Standard Error: 41_285
+			.saturating_add(Weight::from_ref_time(9_830_417).saturating_mul(r.into()))
+			// Standard Error: 3_852
+			.saturating_add(Weight::from_ref_time(1_409_112).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(4))
+			.saturating_add(RocksDbWeight::get().reads((3_u64).saturating_mul(r.into())))
+			.saturating_add(RocksDbWeight::get().writes(7))
+			.saturating_add(RocksDbWeight::get().writes((4_u64).saturating_mul(r.into())))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 5307).saturating_mul(r.into()))
+			.saturating_add(Weight::from_parts(0, 2531).saturating_mul(n.into()))
+	}
+	/// Storage: Guild GuildIdMap (r:1 w:0)
+	/// Proof Skipped: Guild GuildIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Guilds (r:1 w:0)
+	/// Proof Skipped: Guild Guilds (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild RoleIdMap (r:1 w:0)
+	/// Proof Skipped: Guild RoleIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Roles (r:1 w:1)
+	/// Proof Skipped: Guild Roles (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild RoleVersions (r:1 w:1)
+	/// Proof Skipped: Guild RoleVersions (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `r` is `[0, 10]`.
+	/// The range of component `s` is `[0, 256]`.
+	fn update_role_requirements(r: u32, s: u32, ) -> Weight {
+		// PLACEHOLDER: estimated by hand, not benchmarked yet
+		Weight::from_parts(40_662_315, 15921)
+			// Standard Error: 118_204
+			.saturating_add(Weight::from_ref_time(1_938_420).saturating_mul(r.into()))
+			// Standard Error: 4_617
+			.saturating_add(Weight::from_ref_time(47_015).saturating_mul(s.into()))
+			.saturating_add(RocksDbWeight::get().reads(5))
+			.saturating_add(RocksDbWeight::get().writes(2))
+			.saturating_add(Weight::from_parts(0, 1295).saturating_mul(r.into()))
+			.saturating_add(Weight::from_parts(0, 50).saturating_mul(s.into()))
+	}
+	/// Storage: Guild GuildIdMap (r:2 w:0)
+	/// Proof Skipped: Guild GuildIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Guilds (r:1 w:0)
+	/// Proof Skipped: Guild Guilds (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild RoleIdMap (r:2 w:0)
+	/// Proof Skipped: Guild RoleIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Roles (r:1 w:1)
+	/// Proof Skipped: Guild Roles (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild ChildRoles (r:1 w:1)
+	/// Proof Skipped: Guild ChildRoles (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Deposits (r:1 w:1)
+	/// Proof Skipped: Guild Deposits (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild RoleVersions (r:1 w:1)
+	/// Proof Skipped: Guild RoleVersions (max_values: None, max_size: None, mode: Measured)
+	fn update_role_filter(n: u32, ) -> Weight {
+		// PLACEHOLDER: estimated by hand, not benchmarked yet
+		Weight::from_parts(58_214_000, 24861)
+			// Standard Error: 61_447
+			.saturating_add(Weight::from_ref_time(5_742_018).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(8))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(3))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 5090).saturating_mul(n.into()))
+	}
+	/// Storage: Guild GuildIdMap (r:1 w:0)
+	/// Proof Skipped: Guild GuildIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Guilds (r:1 w:0)
+	/// Proof Skipped: Guild Guilds (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Admins (r:1 w:1)
+	/// Proof Skipped: Guild Admins (max_values: None, max_size: None, mode: Measured)
+	fn add_admin() -> Weight {
+		// PLACEHOLDER: estimated by hand, not benchmarked yet
+		Weight::from_parts(33_118_000, 10335)
+			.saturating_add(RocksDbWeight::get().reads(3))
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+	/// Storage: Guild GuildIdMap (r:1 w:0)
+	/// Proof Skipped: Guild GuildIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Guilds (r:1 w:0)
+	/// Proof Skipped: Guild Guilds (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Admins (r:1 w:1)
+	/// Proof Skipped: Guild Admins (max_values: None, max_size: None, mode: Measured)
+	fn remove_admin() -> Weight {
+		// PLACEHOLDER: estimated by hand, not benchmarked yet
+		Weight::from_parts(32_467_000, 10431)
+			.saturating_add(RocksDbWeight::get().reads(3))
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+	/// Storage: Guild GuildIdMap (r:1 w:0)
+	/// Proof Skipped: Guild GuildIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Guilds (r:1 w:1)
+	/// Proof Skipped: Guild Guilds (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `n` is `[0, 256]`.
+	fn update_guild_metadata(n: u32, ) -> Weight {
+		// PLACEHOLDER: estimated by hand, not benchmarked yet
+		Weight::from_parts(28_341_655, 6390)
+			// Standard Error: 2_116
+			.saturating_add(Weight::from_ref_time(3_012).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(2))
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+	/// Storage: Guild UserData (r:1 w:1)
+	/// Proof Skipped: Guild UserData (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild IdentityOwners (r:0 w:1)
+	/// Proof Skipped: Guild IdentityOwners (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild IdentityDeposits (r:1 w:1)
+	/// Proof Skipped: Guild IdentityDeposits (max_values: None, max_size: None, mode: Measured)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Guild AccountRoles (r:1 w:1)
+	/// Proof Skipped: Guild AccountRoles (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild RoleNameMap (r:256 w:0)
+	/// Proof Skipped: Guild RoleNameMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Roles (r:256 w:0)
+	/// Proof Skipped: Guild Roles (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Members (r:256 w:256)
+	/// Proof Skipped: Guild Members (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild MemberCount (r:512 w:512)
+	/// Proof Skipped: Guild MemberCount (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild ChildRoles (r:512 w:0)
+	/// Proof Skipped: Guild ChildRoles (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `r` is `[0, 256]`.
+	fn unregister(r: u32, ) -> Weight {
+		// PLACEHOLDER: estimated by hand, not benchmarked yet
+		Weight::from_parts(21_730_000, 2722)
+			.saturating_add(Weight::from_ref_time(24_096_310).saturating_mul(r.into()))
+			.saturating_add(RocksDbWeight::get().reads(4))
+			.saturating_add(RocksDbWeight::get().reads((9_u64).saturating_mul(r.into())))
+			.saturating_add(RocksDbWeight::get().writes(5))
+			.saturating_add(RocksDbWeight::get().writes((3_u64).saturating_mul(r.into())))
+			.saturating_add(Weight::from_parts(0, 18386).saturating_mul(r.into()))
+	}
+	/// Storage: Guild GuildIdMap (r:1 w:0)
+	/// Proof Skipped: Guild GuildIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Guilds (r:1 w:0)
+	/// Proof Skipped: Guild Guilds (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Admins (r:1 w:0)
+	/// Proof Skipped: Guild Admins (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild RoleIdMap (r:1 w:0)
+	/// Proof Skipped: Guild RoleIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild RoleVersions (r:1 w:1)
+	/// Proof Skipped: Guild RoleVersions (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild RoleValidity (r:0 w:1)
+	/// Proof Skipped: Guild RoleValidity (max_values: None, max_size: None, mode: Measured)
+	fn set_role_validity() -> Weight {
+		// PLACEHOLDER: estimated by hand, not benchmarked yet
+		Weight::from_parts(34_512_000, 15380)
+			.saturating_add(RocksDbWeight::get().reads(5))
+			.saturating_add(RocksDbWeight::get().writes(2))
+	}
+	/// Storage: Guild MembershipExpiries (r:1 w:1)
+	/// Proof Skipped: Guild MembershipExpiries (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild GuildIdMap (r:100 w:0)
+	/// Proof Skipped: Guild GuildIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild RoleIdMap (r:100 w:0)
+	/// Proof Skipped: Guild RoleIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Members (r:100 w:100)
+	/// Proof Skipped: Guild Members (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `n` is `[0, 100]`.
+	fn expire_memberships(n: u32, ) -> Weight {
+		// PLACEHOLDER: estimated by hand, not benchmarked yet
+		Weight::from_parts(7_902_233, 8319)
+			// Standard Error: 4_106
+			.saturating_add(Weight::from_ref_time(11_723_861).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(1))
+			.saturating_add(RocksDbWeight::get().reads((3_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(1))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2627).saturating_mul(n.into()))
+	}
+	/// Storage: Guild ReverificationQueue (r:2 w:2)
+	/// Proof Skipped: Guild ReverificationQueue (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild ScheduledReverifications (r:100 w:200)
+	/// Proof Skipped: Guild ScheduledReverifications (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild GuildIdMap (r:100 w:0)
+	/// Proof Skipped: Guild GuildIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild RoleIdMap (r:100 w:0)
+	/// Proof Skipped: Guild RoleIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Members (r:100 w:0)
+	/// Proof Skipped: Guild Members (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Roles (r:100 w:0)
+	/// Proof Skipped: Guild Roles (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Oracle ActiveOperators (r:1 w:0)
+	/// Proof Skipped: Oracle ActiveOperators (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Oracle NextOperator (r:1 w:1)
+	/// Proof Skipped: Oracle NextOperator (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Oracle NextRequestIdentifier (r:1 w:1)
+	/// Proof Skipped: Oracle NextRequestIdentifier (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Oracle Requests (r:0 w:100)
+	/// Proof Skipped: Oracle Requests (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `n` is `[0, 100]`.
+	fn reverify_members(n: u32, ) -> Weight {
+		// PLACEHOLDER: estimated by hand, not benchmarked yet
+		Weight::from_parts(14_216_000, 13889)
+			// Standard Error: 21_377
+			.saturating_add(Weight::from_ref_time(54_902_113).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(6))
+			.saturating_add(RocksDbWeight::get().reads((5_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(5))
+			.saturating_add(RocksDbWeight::get().writes((3_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 10314).saturating_mul(n.into()))
+	}
+	/// Storage: Guild GuildIdMap (r:1 w:0)
+	/// Proof Skipped: Guild GuildIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Guilds (r:1 w:0)
+	/// Proof Skipped: Guild Guilds (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Admins (r:1 w:0)
+	/// Proof Skipped: Guild Admins (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild RoleIdMap (r:1 w:0)
+	/// Proof Skipped: Guild RoleIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Members (r:1 w:1)
+	/// Proof Skipped: Guild Members (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `n` is `[0, 256]`.
+	fn kick_member(n: u32, ) -> Weight {
+		// PLACEHOLDER: estimated by hand, not benchmarked yet
+		Weight::from_parts(36_219_431, 15931)
+			// Standard Error: 412
+			.saturating_add(Weight::from_ref_time(1_518).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(5))
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+	/// Storage: Guild GuildIdMap (r:1 w:0)
+	/// Proof Skipped: Guild GuildIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Guilds (r:1 w:0)
+	/// Proof Skipped: Guild Guilds (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Admins (r:1 w:0)
+	/// Proof Skipped: Guild Admins (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Bans (r:1 w:1)
+	/// Proof Skipped: Guild Bans (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild RoleIdMap (r:10 w:0)
+	/// Proof Skipped: Guild RoleIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Members (r:10 w:10)
+	/// Proof Skipped: Guild Members (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `r` is `[0, 10]`.
+	/// The range of component `n` is `[0, 256]`.
+	fn ban_account(r: u32, n: u32, ) -> Weight {
+		// PLACEHOLDER: estimated by hand, not benchmarked yet
+		Weight::from_parts(37_180_762, 17530)
+			// Standard Error: 26_734
+			.saturating_add(Weight::from_ref_time(9_211_480).saturating_mul(r.into()))
+			// Standard Error: 1_036
+			.saturating_add(Weight::from_ref_time(4_187).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(4))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(r.into())))
+			.saturating_add(RocksDbWeight::get().writes(1))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(r.into())))
+			.saturating_add(Weight::from_parts(0, 5124).saturating_mul(r.into()))
+	}
+	/// Storage: Guild GuildIdMap (r:1 w:0)
+	/// Proof Skipped: Guild GuildIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Guilds (r:1 w:0)
+	/// Proof Skipped: Guild Guilds (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Admins (r:1 w:0)
+	/// Proof Skipped: Guild Admins (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Bans (r:1 w:1)
+	/// Proof Skipped: Guild Bans (max_values: None, max_size: None, mode: Measured)
+	fn unban_account() -> Weight {
+		// PLACEHOLDER: estimated by hand, not benchmarked yet
+		Weight::from_parts(43_110_000, 28953)
+			.saturating_add(RocksDbWeight::get().reads(4))
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+	/// Storage: Guild GuildIdMap (r:1 w:0)
+	/// Proof Skipped: Guild GuildIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Guilds (r:1 w:0)
+	/// Proof Skipped: Guild Guilds (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Admins (r:1 w:0)
+	/// Proof Skipped: Guild Admins (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild RoleIdMap (r:1 w:0)
+	/// Proof Skipped: Guild RoleIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Roles (r:1 w:1)
+	/// Proof Skipped: Guild Roles (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Members (r:1001 w:1000)
+	/// Proof Skipped: Guild Members (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild UserData (r:1000 w:0)
+	/// Proof Skipped: Guild UserData (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Deposits (r:1 w:1)
+	/// Proof Skipped: Guild Deposits (max_values: None, max_size: None, mode: Measured)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Guild RoleVersions (r:1 w:1)
+	/// Proof Skipped: Guild RoleVersions (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `n` is `[1, 128]`.
+	/// The range of component `m` is `[0, 1000]`.
+	fn update_allowlist(n: u32, m: u32, ) -> Weight {
+		// PLACEHOLDER: estimated by hand, not benchmarked yet
+		Weight::from_parts(68_441_270, 21376)
+			// Standard Error: 28_916
+			.saturating_add(Weight::from_ref_time(1_587_310).saturating_mul(n.into()))
+			// Standard Error: 4_102
+			.saturating_add(Weight::from_ref_time(9_604_118).saturating_mul(m.into()))
+			.saturating_add(RocksDbWeight::get().reads(9))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(m.into())))
+			.saturating_add(RocksDbWeight::get().writes(4))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(m.into())))
+			.saturating_add(Weight::from_parts(0, 5062).saturating_mul(m.into()))
+	}
+	/// Storage: Guild GuildIdMap (r:1 w:0)
+	/// Proof Skipped: Guild GuildIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Guilds (r:1 w:0)
+	/// Proof Skipped: Guild Guilds (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Admins (r:1 w:0)
+	/// Proof Skipped: Guild Admins (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild RoleIdMap (r:1 w:0)
+	/// Proof Skipped: Guild RoleIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Roles (r:1 w:1)
+	/// Proof Skipped: Guild Roles (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Deposits (r:1 w:1)
+	/// Proof Skipped: Guild Deposits (max_values: None, max_size: None, mode: Measured)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Guild RoleVersions (r:1 w:1)
+	/// Proof Skipped: Guild RoleVersions (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `n` is `[2, 128]`.
+	fn extend_allowlist(n: u32, ) -> Weight {
+		// PLACEHOLDER: estimated by hand, not benchmarked yet
+		Weight::from_parts(70_106_842, 16314)
+			// Standard Error: 31_204
+			.saturating_add(Weight::from_ref_time(3_095_527).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(8))
+			.saturating_add(RocksDbWeight::get().writes(4))
+	}
+	/// Storage: Guild GuildIdMap (r:1 w:0)
+	/// Proof Skipped: Guild GuildIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild RoleIdMap (r:1 w:1)
+	/// Proof Skipped: Guild RoleIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Guilds (r:1 w:1)
+	/// Proof Skipped: Guild Guilds (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Nonce (r:1 w:1)
+	/// Proof Skipped: Guild Nonce (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	/// Proof: RandomnessCollectiveFlip RandomMaterial (max_values: Some(1), max_size: Some(2594), added: 3089, mode: MaxEncodedLen)
+	/// Storage: Guild Roles (r:0 w:1)
+	/// Proof Skipped: Guild Roles (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Deposits (r:0 w:1)
+	/// Proof Skipped: Guild Deposits (max_values: None, max_size: None, mode: Measured)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// The range of component `r` is `[0, 10]`.
+	/// The range of component `s` is `[0, 256]`.
+	fn create_role_with_allowlist_root(r: u32, s: u32, ) -> Weight {
+		// PLACEHOLDER: estimated by hand, not benchmarked yet
+		Weight::from_parts(41_207_955, 14399)
+			// Standard Error: 121_804
+			.saturating_add(Weight::from_ref_time(2_014_329).saturating_mul(r.into()))
+			// Standard Error: 4_998
+			.saturating_add(Weight::from_ref_time(50_917).saturating_mul(s.into()))
+			.saturating_add(RocksDbWeight::get().reads(6))
+			.saturating_add(RocksDbWeight::get().writes(6))
+	}
+	/// Storage: Guild GuildIdMap (r:1 w:0)
+	/// Proof Skipped: Guild GuildIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Guilds (r:1 w:0)
+	/// Proof Skipped: Guild Guilds (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Admins (r:1 w:0)
+	/// Proof Skipped: Guild Admins (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild RoleIdMap (r:1 w:0)
+	/// Proof Skipped: Guild RoleIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Roles (r:1 w:0)
+	/// Proof Skipped: Guild Roles (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `n` is `[1, 128]`.
+	fn upload_allowlist_chunk(n: u32, ) -> Weight {
+		// PLACEHOLDER: estimated by hand, not benchmarked yet
+		Weight::from_parts(35_018_637, 13297)
+			// Standard Error: 2_913
+			.saturating_add(Weight::from_ref_time(171_442).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(5))
+	}
+	/// Storage: Guild GuildIdMap (r:9 w:0)
+	/// Proof Skipped: Guild GuildIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild RoleIdMap (r:9 w:1)
+	/// Proof Skipped: Guild RoleIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Guilds (r:1 w:1)
+	/// Proof Skipped: Guild Guilds (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Nonce (r:1 w:1)
+	/// Proof Skipped: Guild Nonce (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	/// Proof: RandomnessCollectiveFlip RandomMaterial (max_values: Some(1), max_size: Some(2594), added: 3089, mode: MaxEncodedLen)
+	/// Storage: Guild Roles (r:0 w:1)
+	/// Proof Skipped: Guild Roles (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Deposits (r:0 w:1)
+	/// Proof Skipped: Guild Deposits (max_values: None, max_size: None, mode: Measured)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// The range of component `n` is `[1, 8]`.
+	/// The range of component `r` is `[0, 10]`.
+	/// The range of component `s` is `[0, 256]`.
+	fn create_role_with_composite_filter(n: u32, r: u32, s: u32, ) -> Weight {
+		// PLACEHOLDER: estimated by hand, not benchmarked yet
+		Weight::from_parts(45_873_515, 18227)
+			// Standard Error: 61_447
+			.saturating_add(Weight::from_ref_time(5_742_018).saturating_mul(n.into()))
+			// Standard Error: 49_106
+			.saturating_add(Weight::from_ref_time(2_097_650).saturating_mul(r.into()))
+			// Standard Error: 2_015
+			.saturating_add(Weight::from_ref_time(51_239).saturating_mul(s.into()))
+			.saturating_add(RocksDbWeight::get().reads(5))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(6))
+			.saturating_add(Weight::from_parts(0, 5090).saturating_mul(n.into()))
+	}
+	/// Storage: Guild ChildRoleCheckRange (r:1 w:1)
+	/// Proof Skipped: Guild ChildRoleCheckRange (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Guild ChildRoleChecks (r:50 w:50)
+	/// Proof Skipped: Guild ChildRoleChecks (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild GuildIdMap (r:101 w:0)
+	/// Proof Skipped: Guild GuildIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild RoleIdMap (r:101 w:0)
+	/// Proof Skipped: Guild RoleIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Roles (r:1 w:0)
+	/// Proof Skipped: Guild Roles (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Members (r:150 w:50)
+	/// Proof Skipped: Guild Members (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild ChildRoles (r:100 w:0)
+	/// Proof Skipped: Guild ChildRoles (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `n` is `[0, 50]`.
+	fn revoke_child_roles(n: u32, ) -> Weight {
+		// PLACEHOLDER: estimated by hand, not benchmarked yet
+		Weight::from_parts(14_377_520, 14208)
+			// Standard Error: 9_872
+			.saturating_add(Weight::from_ref_time(24_096_310).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(5))
+			.saturating_add(RocksDbWeight::get().reads((8_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(1))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 18386).saturating_mul(n.into()))
+	}
+	/// Storage: Guild UserData (r:1 w:0)
+	/// Proof Skipped: Guild UserData (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild GuildIdMap (r:16 w:0)
+	/// Proof Skipped: Guild GuildIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild RoleIdMap (r:16 w:0)
+	/// Proof Skipped: Guild RoleIdMap (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Bans (r:16 w:0)
+	/// Proof Skipped: Guild Bans (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Roles (r:16 w:0)
+	/// Proof Skipped: Guild Roles (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild Members (r:16 w:16)
+	/// Proof Skipped: Guild Members (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild AccountRoles (r:16 w:16)
+	/// Proof Skipped: Guild AccountRoles (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild RoleValidity (r:16 w:0)
+	/// Proof Skipped: Guild RoleValidity (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild MemberCount (r:32 w:32)
+	/// Proof Skipped: Guild MemberCount (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild ReverificationQueue (r:0 w:16)
+	/// Proof Skipped: Guild ReverificationQueue (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `n` is `[1, 16]`.
+	fn join_many(n: u32, ) -> Weight {
+		// PLACEHOLDER: estimated by hand, not benchmarked yet
+		Weight::from_parts(18_402_117, 5112)
+			// Standard Error: 14_608
+			.saturating_add(Weight::from_ref_time(52_614_093).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(1))
+			.saturating_add(RocksDbWeight::get().reads((9_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes((5_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 20215).saturating_mul(n.into()))
+	}
+	/// Storage: System BlockHash (r:1 w:0)
+	/// Proof: System BlockHash (max_values: None, max_size: Some(44), added: 2519, mode: MaxEncodedLen)
+	/// Storage: Guild RegistrationNonces (r:1 w:1)
+	/// Proof Skipped: Guild RegistrationNonces (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild IdentityOwners (r:1 w:1)
+	/// Proof Skipped: Guild IdentityOwners (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild UserData (r:1 w:1)
+	/// Proof Skipped: Guild UserData (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Guild IdentityDeposits (r:0 w:1)
+	/// Proof Skipped: Guild IdentityDeposits (max_values: None, max_size: None, mode: Measured)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn register_siwe() -> Weight {
+		// PLACEHOLDER: estimated by hand, not benchmarked yet
+		Weight::from_parts(421_312_000, 10140)
+			.saturating_add(RocksDbWeight::get().reads(5))
+			.saturating_add(RocksDbWeight::get().writes(5))
+	}
+	/// Storage: Guild OldestFreeCallWindow (r:1 w:1)
+	/// Proof Skipped: Guild OldestFreeCallWindow (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Guild FreeCalls (r:0 w:100)
+	/// Proof Skipped: Guild FreeCalls (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `n` is `[0, 100]`.
+	fn prune_free_calls(n: u32, ) -> Weight {
+		// PLACEHOLDER: estimated by hand, not benchmarked yet
+		Weight::from_parts(4_125_000, 1485)
+			// Standard Error: 1_302
+			.saturating_add(Weight::from_ref_time(1_206_552).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(1))
+			.saturating_add(RocksDbWeight::get().writes(1))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(n.into())))
+	}
 }
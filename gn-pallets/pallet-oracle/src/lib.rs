@@ -21,6 +21,7 @@
 
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmark;
+pub mod migration;
 #[cfg(test)]
 mod mock;
 #[cfg(test)]
@@ -146,7 +147,8 @@ pub mod pallet {
 
     #[pallet::storage]
     #[pallet::getter(fn active_operators)]
-    pub type ActiveOperators<T: Config> = StorageValue<_, SpVec<T::AccountId>, ValueQuery>;
+    pub type ActiveOperators<T: Config> =
+        StorageValue<_, BoundedVec<T::AccountId, T::MaxOperators>, ValueQuery>;
 
     #[pallet::storage]
     #[pallet::getter(fn request_identifier)]
@@ -179,13 +181,18 @@ pub mod pallet {
         BalanceOf<T>,
     >;
 
+    // NOTE callbacks are runtime calls that don't have a maximum encoded
+    // length, however, requests expire after 'ValidityPeriod'
     #[pallet::storage]
+    #[pallet::unbounded]
     #[pallet::getter(fn request)]
     pub type Requests<T: Config> =
         StorageMap<_, Blake2_128Concat, RequestIdentifier, OracleRequest<T>, OptionQuery>;
 
+    pub(crate) const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
     #[pallet::pallet]
-    #[pallet::without_storage_info]
+    #[pallet::storage_version(STORAGE_VERSION)]
     #[pallet::generate_store(pub(super) trait Store)]
     pub struct Pallet<T>(_);
 
@@ -246,13 +253,17 @@ pub mod pallet {
             );
 
             ActiveOperators::<T>::try_mutate(|operators| {
-                if operators.binary_search(&operator).is_ok() {
-                    Err(Error::<T>::OperatorAlreadyActivated.into())
-                } else {
-                    operators.push(operator.clone());
-                    operators.sort(); // needed for binary search
-                    Self::deposit_event(Event::OperatorActivated(operator));
-                    Ok(())
+                match operators.binary_search(&operator) {
+                    Ok(_) => Err(Error::<T>::OperatorAlreadyActivated.into()),
+                    Err(index) => {
+                        // NOTE inserting at the searched index keeps the
+                        // operators sorted, which is needed for binary search
+                        operators
+                            .try_insert(index, operator.clone())
+                            .map_err(|_| Error::<T>::MaxOperatorsRegistered)?;
+                        Self::deposit_event(Event::OperatorActivated(operator));
+                        Ok(())
+                    }
                 }
             })
         }
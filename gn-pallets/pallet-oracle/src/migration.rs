@@ -0,0 +1,53 @@
+use super::*;
+
+pub mod v1 {
+    use super::*;
+    use frame_support::pallet_prelude::*;
+    use frame_support::traits::OnRuntimeUpgrade;
+    use sp_std::vec::Vec as SpVec;
+
+    /// Moves the active operators into a bounded vector. The list is only
+    /// truncated if it exceeds `MaxOperators`, which is not possible unless
+    /// the limit has been lowered, because operators have to be registered
+    /// before they can be activated.
+    pub struct MigrateToV1<T>(PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let onchain_version = Pallet::<T>::on_chain_storage_version();
+            if onchain_version != 0 {
+                return T::DbWeight::get().reads(1);
+            }
+
+            let _ = ActiveOperators::<T>::translate::<SpVec<T::AccountId>, _>(|operators| {
+                operators.map(BoundedVec::truncate_from)
+            });
+            StorageVersion::new(1).put::<Pallet<T>>();
+
+            T::DbWeight::get().reads_writes(2, 2)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<SpVec<u8>, &'static str> {
+            // NOTE only the length prefix is decoded, thus it works with
+            // lists exceeding the bound as well
+            let n_operators = ActiveOperators::<T>::decode_len().unwrap_or_default() as u32;
+            Ok(n_operators.encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: SpVec<u8>) -> Result<(), &'static str> {
+            let n_operators =
+                u32::decode(&mut state.as_slice()).map_err(|_| "invalid pre-upgrade state")?;
+            ensure!(
+                ActiveOperators::<T>::get().len() as u32 == n_operators.min(T::MaxOperators::get()),
+                "active operators were lost during the migration"
+            );
+            ensure!(
+                Pallet::<T>::on_chain_storage_version() == 1,
+                "storage version was not updated"
+            );
+            Ok(())
+        }
+    }
+}
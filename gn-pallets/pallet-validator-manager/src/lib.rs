@@ -16,6 +16,7 @@
 #![deny(clippy::dbg_macro)]
 #![deny(unused_crate_dependencies)]
 
+pub mod migration;
 #[cfg(test)]
 mod mock;
 #[cfg(test)]
@@ -51,24 +52,33 @@ pub mod pallet {
         /// Minimum number of validators to leave in the validator set during
         /// auto removal.
         type MinAuthorities: Get<u32>;
+
+        /// Maximum number of validators in the validator set.
+        #[pallet::constant]
+        type MaxValidators: Get<u32>;
     }
 
+    pub(crate) const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
     #[pallet::pallet]
     #[pallet::generate_store(pub(super) trait Store)]
-    #[pallet::without_storage_info]
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(_);
 
     #[pallet::storage]
     #[pallet::getter(fn validators)]
-    pub type Validators<T: Config> = StorageValue<_, Vec<T::AccountId>, ValueQuery>;
+    pub type Validators<T: Config> =
+        StorageValue<_, BoundedVec<T::AccountId, T::MaxValidators>, ValueQuery>;
 
     #[pallet::storage]
     #[pallet::getter(fn approved_validators)]
-    pub type ApprovedValidators<T: Config> = StorageValue<_, Vec<T::AccountId>, ValueQuery>;
+    pub type ApprovedValidators<T: Config> =
+        StorageValue<_, BoundedVec<T::AccountId, T::MaxValidators>, ValueQuery>;
 
     #[pallet::storage]
     #[pallet::getter(fn validators_to_remove)]
-    pub type OfflineValidators<T: Config> = StorageValue<_, Vec<T::AccountId>, ValueQuery>;
+    pub type OfflineValidators<T: Config> =
+        StorageValue<_, BoundedVec<T::AccountId, T::MaxValidators>, ValueQuery>;
 
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
@@ -91,6 +101,8 @@ pub mod pallet {
         ValidatorNotApproved,
         /// Only the validator can add itself back after coming online.
         BadOrigin,
+        /// Target validator count is above the maximum.
+        TooManyValidators,
     }
 
     #[pallet::hooks]
@@ -189,7 +201,10 @@ impl<T: Config> Pallet<T> {
             "Validators are already initialized!"
         );
 
-        <Validators<T>>::put(validators);
+        let validators =
+            BoundedVec::<T::AccountId, T::MaxValidators>::try_from(validators.to_vec())
+                .expect("Initial set of validators must be at most T::MaxValidators");
+        <Validators<T>>::put(&validators);
         <ApprovedValidators<T>>::put(validators);
     }
 
@@ -198,7 +213,8 @@ impl<T: Config> Pallet<T> {
             !<Validators<T>>::get().contains(&validator_id),
             Error::<T>::Duplicate
         );
-        <Validators<T>>::mutate(|v| v.push(validator_id.clone()));
+        <Validators<T>>::try_mutate(|v| v.try_push(validator_id.clone()))
+            .map_err(|_| Error::<T>::TooManyValidators)?;
 
         Self::deposit_event(Event::ValidatorAdditionInitiated(validator_id));
         log::debug!(target: LOG_TARGET, "Validator addition initiated.");
@@ -231,7 +247,8 @@ impl<T: Config> Pallet<T> {
             !<ApprovedValidators<T>>::get().contains(&validator_id),
             Error::<T>::Duplicate
         );
-        <ApprovedValidators<T>>::mutate(|v| v.push(validator_id.clone()));
+        <ApprovedValidators<T>>::try_mutate(|v| v.try_push(validator_id.clone()))
+            .map_err(|_| Error::<T>::TooManyValidators)?;
         Ok(())
     }
 
@@ -244,7 +261,13 @@ impl<T: Config> Pallet<T> {
 
     // Adds offline validators to a local cache for removal at new session.
     fn mark_for_removal(validator_id: T::AccountId) {
-        <OfflineValidators<T>>::mutate(|v| v.push(validator_id));
+        // NOTE offenders are validators, so the cache cannot be full unless
+        // the same validator is reported multiple times
+        <OfflineValidators<T>>::mutate(|v| {
+            if !v.contains(&validator_id) {
+                let _ = v.try_push(validator_id);
+            }
+        });
     }
 
     // Removes offline validators from the validator set and clears the offline
@@ -265,7 +288,7 @@ impl<T: Config> Pallet<T> {
         );
 
         // Clear the offline validator list to avoid repeated deletion.
-        <OfflineValidators<T>>::put(Vec::<T::AccountId>::new());
+        <OfflineValidators<T>>::kill();
     }
 }
 
@@ -283,7 +306,7 @@ impl<T: Config> pallet_session::SessionManager<T::AccountId> for Pallet<T> {
             "New session called; updated validator set provided."
         );
 
-        Some(Self::validators())
+        Some(Self::validators().into_inner())
     }
 
     fn end_session(_end_index: u32) {}
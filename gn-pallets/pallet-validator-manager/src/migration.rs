@@ -0,0 +1,64 @@
+use super::*;
+
+pub mod v1 {
+    use super::*;
+    use frame_support::traits::OnRuntimeUpgrade;
+
+    /// Moves the validator lists into bounded vectors. A list is only
+    /// truncated if it exceeds `MaxValidators`, in which case the last
+    /// validators are dropped.
+    pub struct MigrateToV1<T>(PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let onchain_version = Pallet::<T>::on_chain_storage_version();
+            if onchain_version != 0 {
+                return T::DbWeight::get().reads(1);
+            }
+
+            let _ = Validators::<T>::translate::<Vec<T::AccountId>, _>(|validators| {
+                validators.map(BoundedVec::truncate_from)
+            });
+            let _ = ApprovedValidators::<T>::translate::<Vec<T::AccountId>, _>(|validators| {
+                validators.map(BoundedVec::truncate_from)
+            });
+            let _ = OfflineValidators::<T>::translate::<Vec<T::AccountId>, _>(|validators| {
+                validators.map(BoundedVec::truncate_from)
+            });
+            StorageVersion::new(1).put::<Pallet<T>>();
+
+            T::DbWeight::get().reads_writes(4, 4)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+            // NOTE only the length prefixes are decoded, thus it works with
+            // lists exceeding the bound as well
+            let n_validators = (
+                Validators::<T>::decode_len().unwrap_or_default() as u32,
+                ApprovedValidators::<T>::decode_len().unwrap_or_default() as u32,
+            );
+            Ok(n_validators.encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+            let (n_validators, n_approved) = <(u32, u32)>::decode(&mut state.as_slice())
+                .map_err(|_| "invalid pre-upgrade state")?;
+            let max_validators = T::MaxValidators::get();
+            ensure!(
+                Validators::<T>::get().len() as u32 == n_validators.min(max_validators),
+                "validators were lost during the migration"
+            );
+            ensure!(
+                ApprovedValidators::<T>::get().len() as u32 == n_approved.min(max_validators),
+                "approved validators were lost during the migration"
+            );
+            ensure!(
+                Pallet::<T>::on_chain_storage_version() == 1,
+                "storage version was not updated"
+            );
+            Ok(())
+        }
+    }
+}
@@ -185,12 +185,14 @@ impl frame_system::Config for Test {
 
 parameter_types! {
     pub const MinAuthorities: u32 = 2;
+    pub const MaxValidators: u32 = 4;
 }
 
 impl validator_set::Config for Test {
     type AddRemoveOrigin = EnsureRoot<Self::AccountId>;
     type RuntimeEvent = RuntimeEvent;
     type MinAuthorities = MinAuthorities;
+    type MaxValidators = MaxValidators;
 }
 
 impl pallet_session::Config for Test {
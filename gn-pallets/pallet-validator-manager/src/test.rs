@@ -12,7 +12,7 @@ fn simple_setup_should_work() {
             authorities(),
             vec![UintAuthorityId(1), UintAuthorityId(2), UintAuthorityId(3)]
         );
-        assert_eq!(ValidatorSet::validators(), &[1, 2, 3]);
+        assert_eq!(ValidatorSet::validators().as_slice(), &[1, 2, 3]);
         assert_eq!(Session::validators(), &[1, 2, 3]);
     });
 }
@@ -21,8 +21,11 @@ fn simple_setup_should_work() {
 fn add_validator_updates_validators_list() {
     new_test_ext().execute_with(|| {
         assert_ok!(ValidatorSet::add_validator(RuntimeOrigin::root(), 4));
-        assert_eq!(ValidatorSet::validators(), &[1, 2, 3, 4]);
-        assert_eq!(ValidatorSet::approved_validators(), &[1, 2, 3, 4]);
+        assert_eq!(ValidatorSet::validators().as_slice(), &[1, 2, 3, 4]);
+        assert_eq!(
+            ValidatorSet::approved_validators().as_slice(),
+            &[1, 2, 3, 4]
+        );
     });
 }
 
@@ -30,12 +33,12 @@ fn add_validator_updates_validators_list() {
 fn remove_validator_updates_validators_list() {
     new_test_ext().execute_with(|| {
         assert_ok!(ValidatorSet::remove_validator(RuntimeOrigin::root(), 2));
-        assert_eq!(ValidatorSet::validators(), &[1, 3]);
-        assert_eq!(ValidatorSet::approved_validators(), &[1, 3]);
+        assert_eq!(ValidatorSet::validators().as_slice(), &[1, 3]);
+        assert_eq!(ValidatorSet::approved_validators().as_slice(), &[1, 3]);
         // add again
         assert_ok!(ValidatorSet::add_validator(RuntimeOrigin::root(), 2));
-        assert_eq!(ValidatorSet::validators(), &[1, 3, 2]);
-        assert_eq!(ValidatorSet::approved_validators(), &[1, 3, 2]);
+        assert_eq!(ValidatorSet::validators().as_slice(), &[1, 3, 2]);
+        assert_eq!(ValidatorSet::approved_validators().as_slice(), &[1, 3, 2]);
     });
 }
 
@@ -63,10 +66,21 @@ fn remove_validator_fails_with_invalid_origin() {
 fn duplicate_check() {
     new_test_ext().execute_with(|| {
         assert_ok!(ValidatorSet::add_validator(RuntimeOrigin::root(), 4));
-        assert_eq!(ValidatorSet::validators(), &[1, 2, 3, 4]);
+        assert_eq!(ValidatorSet::validators().as_slice(), &[1, 2, 3, 4]);
         assert_noop!(
             ValidatorSet::add_validator(RuntimeOrigin::root(), 4),
             Error::<Test>::Duplicate
         );
     });
 }
+
+#[test]
+fn add_validator_fails_above_max_validators() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(ValidatorSet::add_validator(RuntimeOrigin::root(), 4));
+        assert_noop!(
+            ValidatorSet::add_validator(RuntimeOrigin::root(), 5),
+            Error::<Test>::TooManyValidators
+        );
+    });
+}
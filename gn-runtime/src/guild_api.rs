@@ -0,0 +1,34 @@
+//! Runtime API for reading the guild state.
+//!
+//! Clients would otherwise have to assemble raw storage keys of the guild
+//! pallet themselves. Every method takes names instead of the internal
+//! guild and role ids and returns the unbounded `gn_common` types.
+
+use gn_common::identity::Identity;
+use gn_common::{Guild, GuildName, Role, RoleName};
+use parity_scale_codec::Codec;
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+    pub trait GuildApi<AccountId> where AccountId: Codec {
+        /// Returns the guild with the given name.
+        fn guild(guild_name: GuildName) -> Option<Guild<AccountId>>;
+        /// Returns the names of the roles of a guild.
+        fn roles(guild_name: GuildName) -> Vec<RoleName>;
+        /// Returns the filter and the requirements of a role.
+        fn role(guild_name: GuildName, role_name: RoleName) -> Option<Role>;
+        /// Checks whether the account is a member of the role.
+        fn is_member(guild_name: GuildName, role_name: RoleName, account: AccountId) -> bool;
+        /// Returns at most `limit` members of a role, starting after the
+        /// `start` account.
+        fn members(
+            guild_name: GuildName,
+            role_name: RoleName,
+            start: Option<AccountId>,
+            limit: u32,
+        ) -> Vec<AccountId>;
+        /// Returns the registered identities of an account with their
+        /// indices.
+        fn identities(account: AccountId) -> Vec<(u8, Identity)>;
+    }
+}
@@ -8,6 +8,10 @@
 #[cfg(feature = "std")]
 include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 
+pub mod guild_api;
+mod rate_limit;
+pub use rate_limit::CheckFreeGuildCalls;
+
 pub use frame_system::Call as SystemCall;
 use frame_system::EnsureRoot;
 pub use pallet_balances::Call as BalancesCall;
@@ -29,6 +33,7 @@ use sp_runtime::{
     ApplyExtrinsicResult, SaturatedConversion,
 };
 
+use gn_common::{GuildName, RoleName};
 use gn_sig::MultiSignature;
 #[cfg(feature = "try-runtime")]
 use sp_staking::offence::ReportOffence;
@@ -46,6 +51,7 @@ use frame_support::{
         constants::{RocksDbWeight, WEIGHT_REF_TIME_PER_SECOND},
         IdentityFee, Weight,
     },
+    PalletId,
 };
 
 /// An index to a block.
@@ -104,10 +110,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
     //   `spec_version`, and `authoring_version` are the same between Wasm and native.
     // This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
     //   the compatible custom types.
-    spec_version: 104,
+    spec_version: 105,
     impl_version: 1,
     apis: RUNTIME_API_VERSIONS,
-    transaction_version: 1,
+    transaction_version: 2,
     state_version: 1,
 };
 
@@ -158,6 +164,15 @@ parameter_types! {
     pub const MaxKeys: u32 = 10_000;
     pub const MaxPeerInHeartbeats: u32 = 10_000;
     pub const MaxPeerDataEncodingSize: u32 = 1_000;
+    pub const ReverificationPeriod: BlockNumber = DAYS;
+    pub const GuildDeposit: Balance = 1_000_000_000;
+    pub const RoleDeposit: Balance = 100_000_000;
+    pub const AllowlistByteDeposit: Balance = 1_000_000;
+    pub const IdentityDeposit: Balance = 10_000_000;
+    pub const FreeGuildCallsPerWindow: u32 = 20;
+    pub const FreeGuildCallWindow: BlockNumber = 10 * MINUTES;
+    pub const GuildPalletId: PalletId = PalletId(*b"gn/guild");
+    pub const SiweDomain: &'static str = "app.guild.xyz";
 }
 
 // Configure FRAME pallets to include in runtime.
@@ -246,6 +261,7 @@ where
             frame_system::CheckEra::<Runtime>::from(era),
             frame_system::CheckNonce::<Runtime>::from(nonce),
             frame_system::CheckWeight::<Runtime>::new(),
+            CheckFreeGuildCalls::new(),
             pallet_transaction_payment::ChargeTransactionPayment::<Runtime>::from(tip),
         );
         let raw_payload = SignedPayload::new(call, extra)
@@ -347,13 +363,37 @@ impl pallet_sudo::Config for Runtime {
 }
 
 impl pallet_guild::Config for Runtime {
+    type AllowlistByteDeposit = AllowlistByteDeposit;
+    type FreeCallWindow = FreeGuildCallWindow;
+    type FreeCallsPerWindow = FreeGuildCallsPerWindow;
+    type GuildDeposit = GuildDeposit;
+    type IdentityDeposit = IdentityDeposit;
+    type MaxAdminsPerGuild = ConstU32<10>;
+    type MaxAllowlistChunks = ConstU32<1024>;
     type MaxAllowlistLen = ConstU32<128>;
+    type MaxBansPerGuild = ConstU32<256>;
+    type MaxChildChecksPerBlock = ConstU32<50>;
+    type MaxChildrenPerRole = ConstU32<32>;
+    type MaxCompositeFilterLen = ConstU32<8>;
+    type MaxExpiriesPerBlock = ConstU32<100>;
+    type MaxFreeCallPrunesPerBlock = ConstU32<100>;
     type MaxIdentities = ConstU8<10>;
+    type MaxJoinBatchLen = ConstU32<16>;
+    type MaxRolesPerAccount = ConstU32<256>;
     type MaxRolesPerGuild = ConstU32<10>;
     type MaxReqsPerRole = ConstU32<10>;
+    type MaxReverificationsPerBlock = ConstU32<100>;
+    type MaxScheduleDelay = ConstU32<50>;
     type MaxSerializedLen = ConstU32<256>;
+    type MaxSiweMessageLen = ConstU32<1024>;
     type MyRandomness = RandomnessCollectiveFlip;
+    type PalletId = GuildPalletId;
+    type ReverificationPeriod = ReverificationPeriod;
+    type RoleDeposit = RoleDeposit;
     type RuntimeEvent = RuntimeEvent;
+    type SiweChainId = ConstU64<1>;
+    type SiweDomain = SiweDomain;
+    type UnixTime = Timestamp;
     type WeightInfo = pallet_guild::weights::SubstrateWeight<Runtime>;
 }
 
@@ -383,6 +423,7 @@ impl pallet_validator_manager::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
     type AddRemoveOrigin = EnsureRoot<AccountId>;
     type MinAuthorities = MinAuthorities;
+    type MaxValidators = ConstU32<32>;
 }
 
 // Create the runtime by composing the FRAME pallets that were previously configured.
@@ -427,6 +468,7 @@ pub type SignedExtra = (
     frame_system::CheckEra<Runtime>,
     frame_system::CheckNonce<Runtime>,
     frame_system::CheckWeight<Runtime>,
+    CheckFreeGuildCalls,
     pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
 );
 /// Unchecked extrinsic type as expected by this runtime.
@@ -441,7 +483,16 @@ pub type Executive = frame_executive::Executive<
     frame_system::ChainContext<Runtime>,
     Runtime,
     AllPalletsWithSystem,
-    ActivateImOnlinePallet,
+    (
+        ActivateImOnlinePallet,
+        pallet_guild::migration::v1::MigrateToV1<Runtime>,
+        pallet_guild::migration::v2::MigrateToV2<Runtime>,
+        pallet_guild::migration::v3::MigrateToV3<Runtime>,
+        pallet_guild::migration::v4::MigrateToV4<Runtime>,
+        pallet_guild::migration::v5::MigrateToV5<Runtime>,
+        pallet_oracle::migration::v1::MigrateToV1<Runtime>,
+        pallet_validator_manager::migration::v1::MigrateToV1<Runtime>,
+    ),
 >;
 
 // TODO remove this before the next upgrade
@@ -632,6 +683,37 @@ impl_runtime_apis! {
         }
     }
 
+    impl guild_api::GuildApi<Block, AccountId> for Runtime {
+        fn guild(guild_name: GuildName) -> Option<gn_common::Guild<AccountId>> {
+            Guild::guild_by_name(&guild_name)
+        }
+
+        fn roles(guild_name: GuildName) -> Vec<RoleName> {
+            Guild::role_names(&guild_name)
+        }
+
+        fn role(guild_name: GuildName, role_name: RoleName) -> Option<gn_common::Role> {
+            Guild::role_by_name(&guild_name, &role_name)
+        }
+
+        fn is_member(guild_name: GuildName, role_name: RoleName, account: AccountId) -> bool {
+            Guild::is_member_of(&guild_name, &role_name, &account)
+        }
+
+        fn members(
+            guild_name: GuildName,
+            role_name: RoleName,
+            start: Option<AccountId>,
+            limit: u32,
+        ) -> Vec<AccountId> {
+            Guild::members_page(&guild_name, &role_name, start, limit)
+        }
+
+        fn identities(account: AccountId) -> Vec<(u8, gn_common::identity::Identity)> {
+            Guild::identities_of(&account)
+        }
+    }
+
     #[cfg(feature = "runtime-benchmarks")]
     impl frame_benchmarking::Benchmark<Block> for Runtime {
         fn benchmark_metadata(extra: bool) -> (
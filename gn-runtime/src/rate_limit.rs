@@ -0,0 +1,229 @@
+//! Per-account rate limiting of feeless guild calls.
+//!
+//! Most guild calls are dispatched with `Pays::No`, so without a limit they
+//! could be spammed for free. Every account may submit
+//! [`FreeGuildCallsPerWindow`] feeless guild calls within a window of
+//! [`FreeGuildCallWindow`] blocks. Once the quota is used up, further guild
+//! calls in the same window are charged as if they were `Pays::Yes` calls and
+//! are rejected from the transaction pool if the account cannot afford the
+//! fee. The number of calls is stored in `pallet_guild`, which also prunes
+//! the counters of expired windows.
+//!
+//! Calls are only counted once they are included in a block, so an account
+//! may have a single feeless guild call waiting in the transaction pool at a
+//! time. Further feeless calls of the account are rejected by the pool until
+//! the pending one is included, clients submitting several guild calls at
+//! once have to wait for the inclusion of each.
+
+use crate::{
+    AccountId, Balances, FreeGuildCallWindow, FreeGuildCallsPerWindow, Guild, Runtime, RuntimeCall,
+    System,
+};
+use frame_support::dispatch::{DispatchInfo, Pays};
+use frame_support::traits::{Currency, ExistenceRequirement, WithdrawReasons};
+use parity_scale_codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_runtime::traits::{DispatchInfoOf, SignedExtension};
+use sp_runtime::transaction_validity::{
+    InvalidTransaction, TransactionValidity, TransactionValidityError, ValidTransaction,
+};
+use sp_runtime::RuntimeDebug;
+
+/// Limits the number of feeless guild calls an account may submit per
+/// window and charges the regular fee for the calls exceeding the quota.
+#[derive(Encode, Decode, Clone, Default, Eq, PartialEq, TypeInfo, RuntimeDebug)]
+pub struct CheckFreeGuildCalls;
+
+impl CheckFreeGuildCalls {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn is_free_guild_call(call: &RuntimeCall, info: &DispatchInfo) -> bool {
+        matches!(call, RuntimeCall::Guild(_)) && info.pays_fee == Pays::No
+    }
+
+    /// The fee the call would cost if it wasn't feeless.
+    fn paid_fee(info: &DispatchInfo, len: usize) -> crate::Balance {
+        let paid_info = DispatchInfo {
+            pays_fee: Pays::Yes,
+            ..*info
+        };
+        pallet_transaction_payment::Pallet::<Runtime>::compute_fee(len as u32, &paid_info, 0)
+    }
+}
+
+impl SignedExtension for CheckFreeGuildCalls {
+    const IDENTIFIER: &'static str = "CheckFreeGuildCalls";
+    type AccountId = AccountId;
+    type Call = RuntimeCall;
+    type AdditionalSigned = ();
+    type Pre = ();
+
+    fn additional_signed(&self) -> Result<Self::AdditionalSigned, TransactionValidityError> {
+        Ok(())
+    }
+
+    fn validate(
+        &self,
+        who: &Self::AccountId,
+        call: &Self::Call,
+        info: &DispatchInfoOf<Self::Call>,
+        len: usize,
+    ) -> TransactionValidity {
+        if !Self::is_free_guild_call(call, info) {
+            return Ok(ValidTransaction::default());
+        }
+
+        let left = Guild::free_calls_left(who);
+        if left == 0 {
+            if Balances::free_balance(who) < Self::paid_fee(info, len) {
+                return Err(InvalidTransaction::Payment.into());
+            }
+            return Ok(ValidTransaction::default());
+        }
+
+        // NOTE usage is only counted in 'pre_dispatch', so pending feeless
+        // calls of an account provide the same tag until one of them is
+        // included. Thus the pool cannot be flooded with calls that would
+        // exceed the quota, at the cost of allowing only one pending feeless
+        // call per account. The tag expires along with the window
+        let now = System::block_number();
+        let window = Guild::free_call_window(now);
+        let used = FreeGuildCallsPerWindow::get() - left;
+        let window_end = window
+            .saturating_add(1)
+            .saturating_mul(FreeGuildCallWindow::get());
+        ValidTransaction::with_tag_prefix(Self::IDENTIFIER)
+            .and_provides((who, window, used))
+            .longevity(window_end.saturating_sub(now).into())
+            .build()
+    }
+
+    fn pre_dispatch(
+        self,
+        who: &Self::AccountId,
+        call: &Self::Call,
+        info: &DispatchInfoOf<Self::Call>,
+        len: usize,
+    ) -> Result<Self::Pre, TransactionValidityError> {
+        if !Self::is_free_guild_call(call, info) {
+            return Ok(());
+        }
+
+        if Guild::use_free_call(who) {
+            return Ok(());
+        }
+
+        // NOTE the quota is used up, so the call falls back to paid
+        // execution. Regular fees are burned (there is no handler for them
+        // in 'CurrencyAdapter'), so is this one when the imbalance is dropped
+        let fee = Balances::withdraw(
+            who,
+            Self::paid_fee(info, len),
+            WithdrawReasons::TRANSACTION_PAYMENT,
+            ExistenceRequirement::KeepAlive,
+        )
+        .map_err(|_| InvalidTransaction::Payment)?;
+        drop(fee);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use frame_support::dispatch::GetDispatchInfo;
+    use frame_support::sp_io::TestExternalities;
+    use sp_runtime::BuildStorage;
+
+    fn new_test_ext() -> TestExternalities {
+        let storage = frame_system::GenesisConfig::default()
+            .build_storage::<Runtime>()
+            .unwrap();
+        let mut ext = TestExternalities::new(storage);
+        ext.execute_with(|| System::set_block_number(1));
+        ext
+    }
+
+    #[test]
+    fn free_guild_calls_are_rate_limited() {
+        new_test_ext().execute_with(|| {
+            let who = AccountId::from([1; 32]);
+            let guild_call = RuntimeCall::Guild(pallet_guild::Call::leave {
+                guild_name: [0; 32],
+                role_name: [0; 32],
+            });
+            let info = guild_call.get_dispatch_info();
+            assert_eq!(info.pays_fee, Pays::No);
+            let len = guild_call.encoded_size();
+
+            let mut tags = Vec::new();
+            for _ in 0..FreeGuildCallsPerWindow::get() {
+                let first = CheckFreeGuildCalls::new()
+                    .validate(&who, &guild_call, &info, len)
+                    .unwrap();
+                let second = CheckFreeGuildCalls::new()
+                    .validate(&who, &guild_call, &info, len)
+                    .unwrap();
+                // only one feeless call of an account may be pending in the
+                // pool, the second one provides the same tag
+                assert_eq!(first.provides, second.provides);
+                assert!(CheckFreeGuildCalls::new()
+                    .pre_dispatch(&who, &guild_call, &info, len)
+                    .is_ok());
+                tags.push(first.provides);
+            }
+            // a new feeless call may be pending once the previous one is
+            // included
+            tags.dedup();
+            assert_eq!(tags.len(), FreeGuildCallsPerWindow::get() as usize);
+
+            // quota used up and the account cannot pay the fee
+            assert_eq!(
+                CheckFreeGuildCalls::new().validate(&who, &guild_call, &info, len),
+                Err(InvalidTransaction::Payment.into())
+            );
+            assert_eq!(
+                CheckFreeGuildCalls::new().pre_dispatch(&who, &guild_call, &info, len),
+                Err(InvalidTransaction::Payment.into())
+            );
+
+            // calls outside of the guild pallet are not limited
+            let transfer_call = RuntimeCall::Balances(pallet_balances::Call::transfer {
+                dest: who.clone().into(),
+                value: 1,
+            });
+            let transfer_info = transfer_call.get_dispatch_info();
+            assert!(CheckFreeGuildCalls::new()
+                .validate(&who, &transfer_call, &transfer_info, 0)
+                .is_ok());
+
+            // falls back to paid execution
+            let balance = 1_000_000_000_000_000;
+            let fee = CheckFreeGuildCalls::paid_fee(&info, len);
+            Balances::make_free_balance_be(&who, balance);
+            assert!(CheckFreeGuildCalls::new()
+                .validate(&who, &guild_call, &info, len)
+                .is_ok());
+            assert!(CheckFreeGuildCalls::new()
+                .pre_dispatch(&who, &guild_call, &info, len)
+                .is_ok());
+            assert_eq!(Balances::free_balance(&who), balance - fee);
+
+            // the quota is renewed in the next window
+            System::set_block_number(1 + FreeGuildCallWindow::get());
+            assert!(CheckFreeGuildCalls::new()
+                .pre_dispatch(&who, &guild_call, &info, len)
+                .is_ok());
+            assert_eq!(Balances::free_balance(&who), balance - fee);
+            let window = Guild::free_call_window(System::block_number());
+            assert_eq!(Guild::free_calls(window, &who), 1);
+            assert_eq!(
+                Guild::free_calls(window - 1, &who),
+                FreeGuildCallsPerWindow::get()
+            );
+        });
+    }
+}
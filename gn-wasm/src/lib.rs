@@ -2,12 +2,17 @@
 #![deny(clippy::dbg_macro)]
 #![deny(unused_crate_dependencies)]
 
-use gn_api::{query, AccountId, Api};
+use gn_api::{query, AccountId, Api, H256};
+use gn_common::eip712::Registration;
 use gn_common::filter::Guild as GuildFilter;
-use gn_common::identity::Identity;
+use gn_common::identity::{
+    siwe::{self, SiweMessage},
+    Identity,
+};
 use gn_common::merkle::Proof;
+use gn_common::pad::{pad_to_n_bytes, unpad_from_n_bytes};
 use gn_common::SerializedRequirements;
-use gn_common::{pad::pad_to_n_bytes, GuildName, RoleName};
+use gn_common::{GuildName, RoleName};
 use gn_engine::RequirementsWithLogic;
 use serde_wasm_bindgen::{from_value as deserialize_from_value, to_value as serialize_to_value};
 use wasm_bindgen::prelude::*;
@@ -47,6 +52,30 @@ pub async fn query_members(
     serialize_to_value(&members).map_err(|e| JsValue::from(e.to_string()))
 }
 
+#[wasm_bindgen(js_name = "queryMemberCount")]
+pub async fn query_member_count(
+    guild: String,
+    role: Option<String>,
+    url: String,
+) -> Result<JsValue, JsValue> {
+    let api = Api::from_url(&url)
+        .await
+        .map_err(|e| JsValue::from(e.to_string()))?;
+
+    let guild_name = sanitize_name(guild)?;
+    let role_name: Option<RoleName> = role.map(sanitize_name).transpose()?;
+    let filter = GuildFilter {
+        name: guild_name,
+        role: role_name,
+    };
+
+    let member_count = query::member_count(api, &filter)
+        .await
+        .map_err(|e| JsValue::from(e.to_string()))?;
+
+    serialize_to_value(&member_count).map_err(|e| JsValue::from(e.to_string()))
+}
+
 #[wasm_bindgen(js_name = "queryGuilds")]
 pub async fn query_guilds(guild: Option<String>, url: String) -> Result<JsValue, JsValue> {
     let api = Api::from_url(&url)
@@ -62,6 +91,21 @@ pub async fn query_guilds(guild: Option<String>, url: String) -> Result<JsValue,
     serialize_to_value(&guilds).map_err(|e| JsValue::from(e.to_string()))
 }
 
+#[wasm_bindgen(js_name = "queryAdmins")]
+pub async fn query_admins(guild: String, url: String) -> Result<JsValue, JsValue> {
+    let api = Api::from_url(&url)
+        .await
+        .map_err(|e| JsValue::from(e.to_string()))?;
+
+    let guild_name = sanitize_name(guild)?;
+
+    let admins = query::admins(api, guild_name)
+        .await
+        .map_err(|e| JsValue::from(e.to_string()))?;
+
+    serialize_to_value(&admins).map_err(|e| JsValue::from(e.to_string()))
+}
+
 #[wasm_bindgen(js_name = "queryRequirements")]
 pub async fn query_requirements(
     guild: String,
@@ -95,6 +139,44 @@ pub async fn query_user_identity(address: String, url: String) -> Result<JsValue
     serialize_to_value(&identities).map_err(|e| JsValue::from(e.to_string()))
 }
 
+/// Returns a page of `[guildName, roleName]` pairs along with the cursor of
+/// the next page, which is `undefined` once there are no more roles.
+#[wasm_bindgen(js_name = "queryUserRoles")]
+pub async fn query_user_roles(
+    address: String,
+    page_size: u32,
+    cursor: Option<String>,
+    url: String,
+) -> Result<JsValue, JsValue> {
+    let id = AccountId::from_str(&address).map_err(|e| JsValue::from(e.to_string()))?;
+    let start = cursor
+        .map(|cursor| H256::from_str(&cursor))
+        .transpose()
+        .map_err(|e| JsValue::from(e.to_string()))?;
+    let api = Api::from_url(&url)
+        .await
+        .map_err(|e| JsValue::from(e.to_string()))?;
+
+    let page = query::roles_of(api, &id, start, page_size)
+        .await
+        .map_err(|e| JsValue::from(e.to_string()))?;
+    let next_cursor = page
+        .last()
+        .filter(|_| page.len() == page_size as usize)
+        .map(|(role_id, _, _)| format!("{role_id:?}"));
+    let roles: Vec<(String, String)> = page
+        .iter()
+        .map(|(_, guild_name, role_name)| {
+            (
+                unpad_from_n_bytes::<PAD_BYTES>(guild_name),
+                unpad_from_n_bytes::<PAD_BYTES>(role_name),
+            )
+        })
+        .collect();
+
+    serialize_to_value(&(roles, next_cursor)).map_err(|e| JsValue::from(e.to_string()))
+}
+
 #[wasm_bindgen(js_name = "queryAllowlist")]
 pub async fn query_allowlist(guild: String, role: String, url: String) -> Result<JsValue, JsValue> {
     let api = Api::from_url(&url)
@@ -122,10 +204,96 @@ pub fn generate_merkle_proof(
         .map_err(|e| JsValue::from(e.to_string()))
 }
 
+#[wasm_bindgen(js_name = "queryRegistrationNonce")]
+pub async fn query_registration_nonce(address: String, url: String) -> Result<JsValue, JsValue> {
+    let id = AccountId::from_str(&address).map_err(|e| JsValue::from(e.to_string()))?;
+    let api = Api::from_url(&url)
+        .await
+        .map_err(|e| JsValue::from(e.to_string()))?;
+
+    let nonce = query::registration_nonce(api, &id)
+        .await
+        .map_err(|e| JsValue::from(e.to_string()))?;
+
+    serialize_to_value(&nonce).map_err(|e| JsValue::from(e.to_string()))
+}
+
 #[wasm_bindgen(js_name = "verificationMsg")]
-pub fn verification_msg(address: String) -> Result<String, JsValue> {
+pub fn verification_msg(
+    address: String,
+    genesis_hash: String,
+    nonce: u64,
+    expires_at: u32,
+) -> Result<String, JsValue> {
+    let account_id = AccountId::from_str(&address).map_err(|e| JsValue::from(e.to_string()))?;
+    let genesis_hash = H256::from_str(&genesis_hash).map_err(|e| JsValue::from(e.to_string()))?;
+    Ok(gn_common::utils::verification_msg(
+        account_id,
+        genesis_hash,
+        nonce,
+        expires_at.into(),
+    ))
+}
+
+/// Returns the EIP-712 typed data (`eth_signTypedData_v4` input) that an EVM
+/// address should sign in order to be registered as an identity of `address`.
+#[wasm_bindgen(js_name = "registrationTypedData")]
+pub fn registration_typed_data(
+    address: String,
+    genesis_hash: String,
+    nonce: u64,
+    expires_at: u32,
+) -> Result<String, JsValue> {
+    let account_id = AccountId::from_str(&address).map_err(|e| JsValue::from(e.to_string()))?;
+    let genesis_hash = H256::from_str(&genesis_hash).map_err(|e| JsValue::from(e.to_string()))?;
+    Ok(Registration::new(account_id, genesis_hash, nonce, expires_at.into()).typed_data_json())
+}
+
+/// Returns the EIP-712 typed data that the contract wallet at `contract` on
+/// the chain with `chain_id` should sign in order to be registered as an
+/// identity of `address`.
+#[wasm_bindgen(js_name = "contractRegistrationTypedData")]
+pub fn contract_registration_typed_data(
+    address: String,
+    genesis_hash: String,
+    nonce: u64,
+    expires_at: u32,
+    contract: String,
+    chain_id: u64,
+) -> Result<String, JsValue> {
     let account_id = AccountId::from_str(&address).map_err(|e| JsValue::from(e.to_string()))?;
-    Ok(gn_common::utils::verification_msg(account_id))
+    let genesis_hash = H256::from_str(&genesis_hash).map_err(|e| JsValue::from(e.to_string()))?;
+    let contract = siwe::parse_address(&contract).map_err(JsValue::from)?;
+    Ok(
+        Registration::new(account_id, genesis_hash, nonce, expires_at.into())
+            .contract_typed_data_json(chain_id, contract),
+    )
+}
+
+/// Builds the EIP-4361 (Sign-In with Ethereum) message that `evm_address`
+/// should sign in order to be registered as an identity of `address`.
+#[allow(clippy::too_many_arguments)]
+#[wasm_bindgen(js_name = "siweMessage")]
+pub fn siwe_message(
+    address: String,
+    evm_address: String,
+    genesis_hash: String,
+    nonce: u64,
+    expires_at: u32,
+    domain: String,
+    uri: String,
+    chain_id: u64,
+    issued_at: String,
+) -> Result<String, JsValue> {
+    let account_id = AccountId::from_str(&address).map_err(|e| JsValue::from(e.to_string()))?;
+    let genesis_hash = H256::from_str(&genesis_hash).map_err(|e| JsValue::from(e.to_string()))?;
+    let evm_address = siwe::parse_address(&evm_address).map_err(JsValue::from)?;
+    let message = SiweMessage::new(domain, evm_address, uri, chain_id, issued_at)
+        .bind_registration(account_id, genesis_hash, nonce, expires_at.into())
+        .to_string();
+    // validate user provided fields
+    SiweMessage::from_str(&message).map_err(JsValue::from)?;
+    Ok(message)
 }
 
 #[wasm_bindgen(js_name = "serializeRequirements")]
@@ -175,11 +343,113 @@ mod test {
         let account_id = AccountId::from_str(account_id_str).unwrap();
         assert_eq!(account_id.to_string(), account_id_str);
 
-        let expected_msg = "Guild Network registration id: d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d";
-        let msg = verification_msg(account_id_str.to_string()).unwrap();
+        let genesis_hash = format!("0x{}", "01".repeat(32));
+        let expected_msg = format!(
+            "Guild Network registration\n\
+            version: 1\n\
+            id: d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d\n\
+            genesis: {}\n\
+            nonce: 3\n\
+            expires at block: 100",
+            "01".repeat(32)
+        );
+        let msg = verification_msg(account_id_str.to_string(), genesis_hash, 3, 100).unwrap();
         assert_eq!(msg, expected_msg);
     }
 
+    #[wasm_bindgen_test]
+    async fn test_registration_typed_data_wrapper() {
+        init_tracing();
+
+        let account_id_str = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+        let genesis_hash = format!("0x{}", "01".repeat(32));
+        let typed_data =
+            registration_typed_data(account_id_str.to_string(), genesis_hash, 3, 100).unwrap();
+        assert!(typed_data.contains("\"primaryType\":\"GuildNetworkRegistration\""));
+        assert!(typed_data.contains(
+            "\"account\":\"0xd43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d\""
+        ));
+        assert!(typed_data.contains("\"nonce\":\"3\",\"expiresAt\":\"100\""));
+        assert!(
+            registration_typed_data(account_id_str.to_string(), "0x01".to_string(), 3, 100)
+                .is_err()
+        );
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_contract_registration_typed_data_wrapper() {
+        init_tracing();
+
+        let account_id_str = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+        let genesis_hash = format!("0x{}", "01".repeat(32));
+        let contract = format!("0x{}", "07".repeat(20));
+        let typed_data = contract_registration_typed_data(
+            account_id_str.to_string(),
+            genesis_hash.clone(),
+            3,
+            100,
+            contract.clone(),
+            100,
+        )
+        .unwrap();
+        assert!(typed_data.contains(&format!(
+            "\"chainId\":100,\"verifyingContract\":\"{contract}\""
+        )));
+        assert!(contract_registration_typed_data(
+            account_id_str.to_string(),
+            genesis_hash,
+            3,
+            100,
+            "0x07".to_string(),
+            100,
+        )
+        .is_err());
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_siwe_message_wrapper() {
+        init_tracing();
+
+        let account_id_str = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+        let genesis_hash = format!("0x{}", "01".repeat(32));
+        let evm_address = "0xfb6916095ca1df60bb79ce92ce3ea74c37c5d359";
+        let expected_msg = format!(
+            "app.guild.xyz wants you to sign in with your Ethereum account:\n\
+            0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359\n\
+            \n\
+            Register this address as an identity on Guild Network.\n\
+            \n\
+            URI: https://app.guild.xyz/\n\
+            Version: 1\n\
+            Chain ID: 1\n\
+            Nonce: 00000003\n\
+            Issued At: 2023-03-01T12:00:00.000Z\n\
+            Resources:\n\
+            - urn:guild-network:{}:d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d:100",
+            "01".repeat(32)
+        );
+
+        let siwe = |domain: &str, issued_at: &str| {
+            siwe_message(
+                account_id_str.to_string(),
+                evm_address.to_string(),
+                genesis_hash.clone(),
+                3,
+                100,
+                domain.to_string(),
+                "https://app.guild.xyz/".to_string(),
+                1,
+                issued_at.to_string(),
+            )
+        };
+        assert_eq!(
+            siwe("app.guild.xyz", "2023-03-01T12:00:00.000Z").unwrap(),
+            expected_msg
+        );
+        assert!(siwe("app.guild.xyz", "yesterday").is_err());
+        assert!(siwe("app guild", "2023-03-01T12:00:00.000Z").is_err());
+    }
+
     #[wasm_bindgen_test]
     async fn test_serialization_roundtrip() {
         let tokens: Vec<Option<TokenType<EvmAddress, U256>>> = vec![
@@ -279,6 +549,26 @@ mod test {
             assert_eq!(members_vec.len(), N_TEST_ACCOUNTS / 2);
         }
 
+        #[wasm_bindgen_test]
+        async fn test_query_member_count() {
+            let guild = "myguild".to_string();
+            let count_js = query_member_count(guild, None, URL.to_string())
+                .await
+                .unwrap();
+            let count: u32 = deserialize_from_value(count_js).unwrap();
+
+            assert_eq!(count as usize, N_TEST_ACCOUNTS);
+
+            let guild = "mysecondguild".to_string();
+            let role = Some("myrole".to_string());
+            let count_js = query_member_count(guild, role, URL.to_string())
+                .await
+                .unwrap();
+            let count: u32 = deserialize_from_value(count_js).unwrap();
+
+            assert_eq!(count as usize, N_TEST_ACCOUNTS / 2);
+        }
+
         #[wasm_bindgen_test]
         async fn test_query_guilds() {
             let guilds_js = query_guilds(None, URL.to_string()).await.unwrap();
@@ -365,6 +655,42 @@ mod test {
             ));
         }
 
+        #[wasm_bindgen_test]
+        async fn test_query_registration_nonce() {
+            let nonce_js = query_registration_nonce(TEST_ADDRESS.to_string(), URL.to_string())
+                .await
+                .unwrap();
+            let nonce: u64 = deserialize_from_value(nonce_js).unwrap();
+
+            // every registration consumes a nonce
+            assert!(nonce > 0);
+        }
+
+        #[wasm_bindgen_test]
+        async fn test_query_user_roles() {
+            let mut roles = Vec::new();
+            let mut cursor = None;
+            loop {
+                let page_js =
+                    query_user_roles(TEST_ADDRESS.to_string(), 1, cursor, URL.to_string())
+                        .await
+                        .unwrap();
+                let (page, next_cursor): (Vec<(String, String)>, Option<String>) =
+                    deserialize_from_value(page_js).unwrap();
+                assert!(page.len() <= 1);
+                roles.extend(page);
+                if next_cursor.is_none() {
+                    break;
+                }
+                cursor = next_cursor;
+            }
+
+            assert!(!roles.is_empty());
+            assert!(roles
+                .iter()
+                .any(|(guild_name, role_name)| guild_name == "myguild" && role_name == "myrole"));
+        }
+
         #[wasm_bindgen_test]
         async fn test_generate_proof() {
             let guild_name = "myguild".to_string();